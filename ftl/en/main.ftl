@@ -3,13 +3,88 @@ alpha = Alpha
     .hover = ?
 absolute-retention-time = Absolute
     .hover = Absolute retention time
+absolute-value = Absolute value
+    .hover = Compare the absolute value
+add-fatty-acid = Add the fatty acid
+add-mode = Add the mode
+add-property = Add a property
+add-ramp = Add the temperature ramp
+adjusted-retention-time = Adjusted retention time
+and = And
+    .hover = All conditions are met
 angle = Angle
+    .hover = Angle of the slope, in degrees
+apply = Apply
+    .hover = Apply the changes of the metadata
+apply-dead-time = Apply
+    .hover = Write the calculated dead times to the dataset
 ascending-order = Ascending
     .hover = Direct order (from min to max)
+authors = Authors
+    .hint = Name Surname, Name Surname
+average-linkage = Average linkage
+    .hover = Mean distance between all the members of the clusters (UPGMA)
+both = Both
+    .hover = Both fatty acids of the pair
 calculate = Calculate
+cancel = Cancel
+candidates = Candidates
+    .hover = Number of the nearest fatty acids to the peak
+carbons = Carbons
+    .hover = Number of carbons within the range
+carrier-gas = Carrier gas
+cell-size = Cell size
 chain-length = Chain length
     .hover = ECL, FCL and ECN
+changes = Changes
+cis = Cis
+clear-sort-keys = Clear the sort keys
+clipboard = Clipboard
+cluster = Cluster
+clustering = Clustering
+    .hover = Hierarchical clustering of the fatty acids by the retention behaviour across all the columns and modes
+clustering-feature = Feature
+    .hover = Value compared across the columns and modes
+clusters = Clusters
+    .hover = Count of the clusters to cut the dendrogram into
+color-scale = Color scale
+    .hover = Colors of the values from the minimum to the maximum
+column = Column
+    .hover = Gas chromatography column (stationary phase, manufacturer and dimensions)
+column-a = Column A
+    .hover = First compared column
+column-b = Column B
+    .hover = Second compared column
+column-diameter = Inner diameter
+column-length = Length
+columns = Columns
+    .hover = Visibility, order, precision and units of the table columns
+comparison = Comparison
+    .hover = Equivalent chain lengths of the fatty acids on two columns at the same modes
+comparison-settings = { -gear } Comparison settings
+complete-linkage = Complete linkage
+    .hover = Maximum distance between the members of the clusters
+constant-flow = Constant flow
+    .hover = The inlet pressure follows the column temperature to keep the flow
+constant-pressure = Constant pressure
+    .hover = The inlet pressure is kept, the flow drops with the column temperature
+copy-as-asciidoc = Copy as AsciiDoc
+    .hover = Copy the selected cells as an AsciiDoc table with the header
+copy-as-markdown = Copy as Markdown
+    .hover = Copy the selected cells as a Markdown table with the header
+copy-as-tsv = Copy as TSV
+    .hover = Copy the selected cells as tab separated values, pastes into spreadsheets
+count = Count
+create = Create
+    .hover = Create a source pane from the data
 database = Database
+date = Date
+dead-time = Dead time
+    .abbreviation = tM
+    .hover = Dead time (min) at the onset temperature
+dead-time-calculator = Dead time calculator
+delta = Δ
+    .hover = Double bond at the position from the carboxyl end
 delta-degrees-of-freedom = Delta degrees of freedom
     .abbreviation = DDOF
 delta-retention-time = Delta
@@ -18,66 +93,219 @@ derivative = Derivative
     .hover = Derivative calculations
 descending-order = Descending
     .hover = Reverse order (from max to min)
+description = Description
 distance = Distance
     .hover = Euclidean distance
 distance-settings = { -gear } Distance settings
+double-bonds = Double bonds
+    .hover = Number of double bonds within the range
+edit = Edit
+    .hover = Click to edit the value, Enter to apply, Escape to cancel
+entry = Entry
+    .hover = Enter the modes, the fatty acids and the replicate retention times of a new dataset
 equivalent-carbon-number = Equivalent carbon number
     .abbreviation = ECN
 equivalent-chain-length = Equivalent chain length
     .abbreviation = ECL
+equivalent-chain-length-difference = Equivalent chain length difference
+    .abbreviation = ΔECL
+    .hover = Difference of the equivalent chain lengths on the column B and the column A
 equivalent-chain-length-distance = Equivalent chain length
     .abbreviation = ΔECL
     .hover = Equivalent chain length distance between two fatty acids
 euclidean-distance = Euclidean distance
     .abbreviation = d
     .hover = Euclidean distance (in coordinates retention time and equivalent chain length) between two fatty acids
+exclude = Exclude
+    .hover = Hide the selected values
+explain = Explain
+export-project = Export project
+    .hover = Save the panes and the presets to a file, drop the file to import it
 fatty-acid = Fatty acid
     .abbreviation = FA
+fatty-acids = Fatty acids
+film-thickness = Film thickness
 filter = Filter
+filter-by-cluster = Cluster
+    .hover = Filter by cluster
+filter-by-column = Columns
+    .hover = Filter by column
 filter-by-fatty-acids = Fatty acids
     .hover = Filter by fatty acids
 filter-by-onset-temperature = Onset temperature
     .hover = Filter by onset temperature
+filter-by-structure = Structure
+    .hover = Filter by the structure of the fatty acids
 filter-by-temperature-step = Temperature step
     .hover = Filter by temperature step
+filter-matches = Show the matched rows only
+final-temperature = Final temperature
+flow = Flow
+    .hover = Carrier gas flow at 25 °C and 101.325 kPa
+formatting = Formatting
+    .hover = Colors of the cells by the rules, evaluated over the computed table
 fractional-chain-length = Fractional chain length
     .abbreviation = FCL
 from = From
+    .hover = The first fatty acid of the pair
+grayscale = Grayscale
 grid = Grid
+group-by-mode = Mode
+    .hover = Group the rows by the column and the mode
+group-by-pair = Pair
+    .hover = Group the rows by the pair of the fatty acids
+grouping = Grouping
+    .hover = Collapsible groups of the rows with the summary line
+heatmap-field = Value
+    .hover = Distance of the heatmap cells
+helium = Helium
+    .hover = He
+highlight = Highlight
+    .hover = Color of the cells satisfying the threshold
+hold = Hold
 horizontal = Horizontal
+hydrogen = Hydrogen
+    .hover = H₂
+identification = Identification
 identifier = Identifier
     .abbreviation = ID
     .hover = Measurement identifier
+import-project = Import project
+    .hover = The project has panes, the open panes are replaced or kept
+include = Include
+    .hover = Show only the selected values
 index = Index
+inlet-pressure = Inlet pressure
+    .hover = Inlet gauge pressure
+instrument = Instrument
+interpolation = Interpolation
+key = Key
 language = Language
 legend = Legend
+linkage = Linkage
+    .hover = Distance between the clusters to merge
 logarithm-of-the-retention-time = Logarithmic
     .hover = Calculate the equivalent chain length using the logarithms of the retention times
+magma = Magma
+mark-saturation = Mark saturation
+    .hover = Tint the rows of the saturated and the unsaturated fatty acids
 mass = Mass
     .hover = Related masses of [RCO]+, [RCOO]-, RCOOH and RCOOCH3
+maximum = Maximum
+maximum-temperature = Maximum temperature
+    .hover = Maximum allowable operating temperature of the column, the elution temperature is clipped to it
+mean-relative-standard-deviation = Mean RSD
+merge-panes = Merge
+    .hover = Add the panes of the project to the open ones
+metadata = Metadata
+    .hover = Name, description, authors, version, date, notes and properties of the dataset
+method = Method
+method-translation = Method translation
+    .hover = Translate the temperature program to another column or carrier gas
+minimum = Minimum
+minimum-alpha = Minimum α
 mode = Mode
     .hover = Measurement mode: onset temperature and temperature step
+modes = Modes
+move-down = Move down
+move-up = Move up
+name = Name
+nearest-count = Count
+    .hover = Count of the nearest pairs of each fatty acid
+next-match = Next match
+next-saturated = Next saturated
+nitrogen = Nitrogen
+    .hover = N₂
+no-grouping = None
+    .hover = Do not group the rows
+no-retention-times = No retention times
+omega = ω
+    .hover = ω-family, position of the last double bond from the methyl end
 onset-temperature = Onset temperature
     .abbreviation = T₀
+onset-temperature-range = Onset temperature range
+    .hover = Show only the modes with the onset temperature within the range
+operation = Operation
+or = Or
+    .hover = Any condition is met
 order = Order
     .hover = Order by
+original = Original:
+outlet-pressure = Outlet pressure
+    .hover = Outlet absolute pressure (0 for a vacuum outlet)
+pairing = Pairing
+    .hover = Pair selection strategy
+pairing-adjacent = Adjacent
+    .hover = Neighbours in the elution order
+pairing-all = All
+    .hover = All pairs within the column and mode
+pairing-nearest = Nearest
+    .hover = The nearest pairs of each fatty acid by the retention time or the equivalent chain length
+paste = Paste
+    .hover = Paste tab separated fatty acid names and replicate retention times
+    .hint = 18:1Δ9c ⇥ 21.342 ⇥ 21.347
+peak = Peak
+    .hover = Equivalent chain lengths of the peak on the columns A and B
+phase-ratio-mismatch = The phase ratios of the columns differ, equivalent chain lengths are preserved only approximately
+pivot-field = Value
+    .hover = Value in the cells of the pivot table
+pivot-view = Pivot
+    .hover = Fatty acids in the rows and modes in the columns
 precision = Precision
+preset-name = Preset name
+presets = Presets
+    .hover = Filter and sort presets shared by the source and the distance panes
+previous-match = Previous match
+previous-saturated = Previous saturated
+properties = Properties
 radius-of-points = Radius of points
     .hover = The radius of all points on the plot
+rank = Rank
+    .hover = Rank of the pair within the mode by the separation of the sort field, the tightest pair is the first
 relative-fatty-acid = Relative
     .hover = The fatty acid to calculate relative retention time
 relative-retention-time = Relative
     .hover = Relative retention time calculated through the selected fatty acid
+relative-standard-deviation = Relative standard deviation
+    .abbreviation = RSD
+    .hover = Relative standard deviation of the retention time repeats (%)
+remove-fatty-acid = Remove the fatty acid
+remove-mode = Remove the mode
+remove-preset = Remove preset
+remove-property = Remove the property
+replace-panes = Replace
+    .hover = Close the open panes and open the panes of the project
+replicates = Replicates
+    .hover = Replicate retention times separated by spaces or semicolons
+    .hint = 21.342 21.347
+reset = Reset
 reset-gui = Reset GUI
 reset-state = Reset state
 resize = Resize
+resolution = Resolution
+    .abbreviation = Rs
+    .hover = Purnell resolution of the pair by the theoretical plates, the selectivity and the retention factor of the later fatty acid
 retention-time = Retention time
     .abbreviation = RT
     .hover = Retention time (absolute, relative and distance)
+retention-time-delta = Delta retention time
 retention-time-distance = Retention time
     .abbreviation = ΔRT
     .hover = Retention time distance between two fatty acids
+retention-time-relative = Relative retention time
+retention-time-span = Retention time span
+reverse = Reverse
+    .hover = Reverse the color scale
+revert = Revert the change
+saturated = Saturated
+    .hover = No unsaturated bonds
+save-preset = Save the current filter and sort as a preset
+search = Search
+    .hover = Fatty acid in any notation (18:1Δ9c, 18:1n-9), mode (70/1), number or text; Enter for the next match, Shift+Enter for the previous one
+single-linkage = Single linkage
+    .hover = Minimum distance between the members of the clusters
 slope = Slope
+    .hover = Slope of the equivalent chain length by the retention time
 sort-by = Sort
     .hover = Sort by
 sort-by-distance = Sort
@@ -94,34 +322,76 @@ sort-by-retention-time = Retention time
     .hover = Sort by equivalent carbon number and retention time
 sort-by-retention-time-distance = Retention time
     .hover = Sort by retention time distance
+sort-keys = Sort keys
+    .hover = Click a table header to sort by it, shift-click to add a secondary key
 source = Source
+source-column = Source column
 source-settings = { -gear } Source settings
+speed-up = Speed-up
+    .hover = Translate with the speed-up factor, the target inlet pressure is calculated
+standard-deviation = Standard deviation
+    .hover = Standard deviation of the retention time repeats
 sticky = Sticky
+structure-target = Structure target
+    .hover = Fatty acids of the pair the structure filter is applied to
 tabs = Tabs
+target-column = Target column
 temperature = Temperature
     .abbreviation = T
     .hover = Eluating temperature
+temperature-program = Temperature program
 temperature-step = Temperature step
     .abbreviation = ΔT
     .hover = Temperature increment per minute
+temperature-step-range = Temperature step range
+    .hover = Show only the modes with the temperature step within the range
+theoretical-plates = Theoretical plates
+    .hover = Number of the theoretical plates of the column, used by the resolution
+thresholds = Thresholds
+    .hover = Keep only the rows with the computed values within the thresholds
 to = To
+    .hover = The second fatty acid of the pair
+trans = Trans
+    .hover = Any trans double bond
+translate = Translate
+    .hover = Open the dataset with the translated modes and predicted retention times
+translated = translated
+translated-temperature-step = Translated temperature step
+translation-factor = Translation factor
+    .abbreviation = G
+    .hover = Ratio of the source dead time to the target dead time
 truncate = Truncate
     .hover = Truncate table headers
+unknown-isomerism = Unknown
+unreachable-speed-up = Unreachable speed-up
+    .hover = The target dead time needs an inlet pressure above the maximum
+untitled = Untitled
+value = Value
+version = Version
 vertical = Vertical
+viridis = Viridis
 
 sort-by-key = Key
     .hover = Sort by key
 sort-by-value = Value
     .hover = Sort by value
+sort-by-field = Field
+    .hover = Distance to sort by
 sort-by-aggregation = Aggregation
-    .hover = Use some aggregation function to calculate the value to sort
+    .hover = Use some aggregation function over the modes to calculate the value of the pair to sort
 sort-by-maximum = Maximum
     .hover = Select the maximum of the values to sort
+sort-by-mean = Mean
+    .hover = Select the mean of the values to sort
 sort-by-median = Median
     .hover = Select the median of the values to sort
 sort-by-minimum = Minimum
     .hover = Select the minimum of the values to sort
+sort-by-mode = Mode
+    .hover = Select the value at the mode to sort
 
+heatmap-view = Heatmap
+    .hover = View as heatmap
 plot-view = { -plot } Plot
     .hover = View as plot
 table-view = { -table } Table
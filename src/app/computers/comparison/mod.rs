@@ -0,0 +1,126 @@
+use crate::{
+    app::{
+        computers::filter::filter,
+        panes::{
+            comparison::settings::Settings,
+            source::settings::{Filter, Selection},
+        },
+    },
+    special::data_frame::DataFrameExt as _,
+};
+use egui::util::cache::{ComputerMut, FrameCache};
+use polars::prelude::*;
+use std::hash::{Hash, Hasher};
+
+/// Comparison computed
+pub(crate) type Computed = FrameCache<DataFrame, Computer>;
+
+/// Comparison computer
+#[derive(Default)]
+pub(crate) struct Computer;
+
+impl Computer {
+    fn try_compute(&mut self, key: Key<'_>) -> PolarsResult<DataFrame> {
+        let (Some(a), Some(b)) = (&key.settings.columns[0], &key.settings.columns[1]) else {
+            return Ok(DataFrame::empty());
+        };
+        // Program, the modes of the different temperature programs are not
+        // paired
+        let mut data_frame = key.data_frame.clone();
+        let programs = data_frame.mode().programs()?;
+        data_frame.with_column(Series::new("Program".into(), programs))?;
+        let mut lazy_frame = data_frame.lazy();
+        // Filter, the columns are chosen by the A and B settings
+        let filter = Filter {
+            columns: Selection::new(),
+            ..key.settings.filter.clone()
+        };
+        if let Some(predicate) = self::filter(&filter, &[col("FattyAcid")]) {
+            lazy_frame = lazy_frame.filter(predicate);
+        }
+        // Join
+        lazy_frame = column(lazy_frame.clone(), a, "A").join(
+            column(lazy_frame, b, "B"),
+            [
+                col("OnsetTemperature"),
+                col("TemperatureStep"),
+                col("Program"),
+                col("FattyAcid"),
+            ],
+            [
+                col("OnsetTemperature"),
+                col("TemperatureStep"),
+                col("Program"),
+                col("FattyAcid"),
+            ],
+            JoinArgs::new(JoinType::Inner),
+        );
+        // Select
+        lazy_frame = lazy_frame
+            .select([
+                as_struct(vec![
+                    col("OnsetTemperature"),
+                    col("TemperatureStep"),
+                    col("Program"),
+                ])
+                .alias("Mode"),
+                col("FattyAcid"),
+                as_struct(vec![
+                    col("A"),
+                    col("B"),
+                    (col("B") - col("A")).alias("Delta"),
+                ])
+                .alias("EquivalentChainLength"),
+            ])
+            .sort_by_exprs(
+                [col("Mode"), col("FattyAcid")],
+                SortMultipleOptions::new().with_nulls_last(true),
+            );
+        lazy_frame.collect()
+    }
+}
+
+impl ComputerMut<Key<'_>, DataFrame> for Computer {
+    fn compute(&mut self, key: Key<'_>) -> DataFrame {
+        self.try_compute(key).expect("compute comparison")
+    }
+}
+
+/// Comparison key
+#[derive(Clone, Copy, Debug)]
+pub struct Key<'a> {
+    pub(crate) data_frame: &'a DataFrame,
+    pub(crate) hash: u64,
+    pub(crate) settings: &'a Settings,
+}
+
+impl Hash for Key<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+        self.settings.columns.hash(state);
+        self.settings.filter.hash(state);
+    }
+}
+
+pub(crate) mod plot;
+
+/// Equivalent chain lengths of the column
+fn column(lazy_frame: LazyFrame, name: &str, alias: &str) -> LazyFrame {
+    lazy_frame
+        .filter(
+            col("Column")
+                .struct_()
+                .field_by_name("Name")
+                .eq(lit(name.to_owned())),
+        )
+        .select([
+            col("Mode").struct_().field_by_name("OnsetTemperature"),
+            col("Mode").struct_().field_by_name("TemperatureStep"),
+            col("Program"),
+            col("FattyAcid"),
+            col("ChainLength")
+                .struct_()
+                .field_by_name("EquivalentChainLength")
+                .alias(alias),
+        ])
+}
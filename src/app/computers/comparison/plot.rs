@@ -0,0 +1,144 @@
+use crate::app::{computers::distance::euclidean_distance, panes::comparison::settings::Settings};
+use egui::util::cache::{ComputerMut, FrameCache};
+use lipid::prelude::*;
+use polars::prelude::*;
+use std::hash::{Hash, Hasher};
+
+/// Comparison plot computed
+pub(crate) type Computed = FrameCache<Value, Computer>;
+
+/// Comparison plot computer
+#[derive(Default)]
+pub(crate) struct Computer;
+
+impl Computer {
+    fn try_compute(&mut self, key: Key<'_>) -> PolarsResult<Value> {
+        if key.data_frame.is_empty() {
+            return Ok(Value::default());
+        }
+        let mode = key.data_frame["Mode"].struct_()?;
+        let (onset_temperature, temperature_step) = match key.settings.mode {
+            Some(mode) => (mode.onset_temperature, mode.temperature_step),
+            None => match (
+                mode.field_by_name("OnsetTemperature")?.f64()?.get(0),
+                mode.field_by_name("TemperatureStep")?.f64()?.get(0),
+            ) {
+                (Some(onset_temperature), Some(temperature_step)) => {
+                    (onset_temperature, temperature_step)
+                }
+                _ => return Ok(Value::default()),
+            },
+        };
+        let data_frame = key
+            .data_frame
+            .clone()
+            .lazy()
+            .filter(
+                col("Mode")
+                    .struct_()
+                    .field_by_name("OnsetTemperature")
+                    .eq(lit(onset_temperature))
+                    .and(
+                        col("Mode")
+                            .struct_()
+                            .field_by_name("TemperatureStep")
+                            .eq(lit(temperature_step)),
+                    ),
+            )
+            .select([
+                col("FattyAcid"),
+                col("EquivalentChainLength")
+                    .struct_()
+                    .field_by_name("A")
+                    .name()
+                    .keep(),
+                col("EquivalentChainLength")
+                    .struct_()
+                    .field_by_name("B")
+                    .name()
+                    .keep(),
+            ])
+            .with_column(
+                euclidean_distance(
+                    col("A") - lit(key.settings.peak.a),
+                    col("B") - lit(key.settings.peak.b),
+                )
+                .alias("Distance"),
+            )
+            .sort(
+                ["Distance"],
+                SortMultipleOptions::new().with_nulls_last(true),
+            )
+            .collect()?;
+        let mut value = Value::default();
+        for (index, (((fatty_acid, a), b), distance)) in data_frame["FattyAcid"]
+            .fa()
+            .into_iter()
+            .zip(data_frame["A"].f64()?)
+            .zip(data_frame["B"].f64()?)
+            .zip(data_frame["Distance"].f64()?)
+            .enumerate()
+        {
+            let Some(fatty_acid) = fatty_acid else {
+                polars_bail!(NoData: "FattyAcid[{index}]");
+            };
+            let (Some(a), Some(b), Some(distance)) = (a, b, distance) else {
+                continue;
+            };
+            let point = Point {
+                fatty_acid,
+                coordinates: [a, b],
+                distance,
+            };
+            if index < key.settings.candidates {
+                value.candidates.push(point.clone());
+            }
+            value.points.push(point);
+        }
+        Ok(value)
+    }
+}
+
+impl ComputerMut<Key<'_>, Value> for Computer {
+    fn compute(&mut self, key: Key<'_>) -> Value {
+        self.try_compute(key).expect("compute comparison plot")
+    }
+}
+
+/// Comparison plot key
+#[derive(Clone, Copy, Debug)]
+pub struct Key<'a> {
+    pub(crate) data_frame: &'a DataFrame,
+    pub(crate) hash: u64,
+    pub(crate) settings: &'a Settings,
+}
+
+impl Hash for Key<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+        self.settings.columns.hash(state);
+        self.settings.filter.hash(state);
+        self.settings.mode.hash(state);
+        self.settings.peak.hash(state);
+        self.settings.candidates.hash(state);
+    }
+}
+
+/// Comparison plot value
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Value {
+    /// Fatty acids at the mode
+    pub(crate) points: Vec<Point>,
+    /// Nearest fatty acids to the peak
+    pub(crate) candidates: Vec<Point>,
+}
+
+/// Comparison plot point
+#[derive(Clone, Debug)]
+pub(crate) struct Point {
+    pub(crate) fatty_acid: FattyAcid,
+    /// Equivalent chain lengths on the columns A and B
+    pub(crate) coordinates: [f64; 2],
+    /// Euclidean distance to the peak
+    pub(crate) distance: f64,
+}
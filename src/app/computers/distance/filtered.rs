@@ -1,9 +1,11 @@
-use crate::app::panes::{
-    distance::settings::{Aggregation, Settings, Sort, SortBy},
-    source::settings::{Filter, Order},
+use crate::app::{
+    computers::filter::{filter, thresholds},
+    panes::{
+        distance::settings::{Aggregation, Field, Settings, Sort, SortBy},
+        source::settings::{Order, SortKeys},
+    },
 };
 use egui::util::cache::{ComputerMut, FrameCache};
-use lipid::prelude::*;
 use polars::prelude::*;
 use std::hash::{Hash, Hasher};
 
@@ -16,13 +18,44 @@ pub(crate) struct Computer;
 
 impl Computer {
     fn try_compute(&mut self, key: Key<'_>) -> PolarsResult<DataFrame> {
-        let mut lazy_frame = key.data_frame.clone().lazy();
+        let mut lazy_frame = key
+            .data_frame
+            .clone()
+            .lazy()
+            .with_column(resolution(key.settings.plates).alias("Resolution"));
         // Filter
-        if let Some(predicate) = filter(&key.settings.filter) {
+        if let Some(predicate) = filter(
+            &key.settings.filter,
+            &[
+                col("FattyAcid").struct_().field_by_name("From"),
+                col("FattyAcid").struct_().field_by_name("To"),
+            ],
+        ) {
             lazy_frame = lazy_frame.filter(predicate);
         }
+        // Thresholds
+        if let Some(predicate) = thresholds(&key.settings.thresholds, field) {
+            lazy_frame = lazy_frame.filter(predicate);
+        }
+        // Rank
+        lazy_frame = lazy_frame.with_column(
+            separation(key.settings.sort.field)
+                .rank(
+                    RankOptions {
+                        method: RankMethod::Min,
+                        descending: false,
+                    },
+                    None,
+                )
+                .over([col("Column"), col("Mode")])
+                .alias("Rank"),
+        );
         // Sort
-        let (by_exprs, sort_options) = sort(key.settings.sort);
+        let (by_exprs, sort_options) = if !key.settings.sort_keys.is_empty() {
+            sort_by_keys(&key.settings.sort_keys)
+        } else {
+            sort(key.settings.sort)
+        };
         lazy_frame = lazy_frame.sort_by_exprs(by_exprs, sort_options);
         lazy_frame.collect()
     }
@@ -34,87 +67,123 @@ impl ComputerMut<Key<'_>, DataFrame> for Computer {
     }
 }
 
-fn sort(sort: Sort) -> (Vec<Expr>, SortMultipleOptions) {
-    let mut sort_options = SortMultipleOptions::new().with_nulls_last(true);
-    if sort.order == Order::Descending {
-        sort_options = sort_options.with_order_descending(true);
-    };
-    let sort_by = match sort.by {
-        SortBy::Key => vec![
-            col("Mode"),
-            col("FattyAcid").struct_().field_by_name("From"),
-            col("FattyAcid").struct_().field_by_name("To"),
-        ],
-        SortBy::Value => vec![col("Alpha").aggregate(sort.aggregation)],
-    };
-    (sort_by, sort_options)
+/// Value of the field
+pub(crate) fn field(field: Field) -> Expr {
+    match field {
+        Field::Alpha => col("Alpha"),
+        Field::RetentionTime => col("RetentionTime").struct_().field_by_name("Delta"),
+        Field::EquivalentChainLength => col("EquivalentChainLength")
+            .struct_()
+            .field_by_name("Delta"),
+        Field::EuclideanDistance => col("EuclideanDistance"),
+        Field::Resolution => col("Resolution"),
+    }
 }
 
-fn filter(filter: &Filter) -> Option<Expr> {
-    let mut expr = None;
-    if !filter.onset_temperatures.is_empty() {
-        for &onset_temperature in &filter.onset_temperatures {
-            expr = Some(
-                expr.unwrap_or(lit(true)).and(
-                    col("Mode")
-                        .struct_()
-                        .field_by_name("OnsetTemperature")
-                        .neq(onset_temperature),
-                ),
-            );
-        }
+/// Separation of the pair, the less the tighter
+fn separation(field: Field) -> Expr {
+    match field {
+        Field::Alpha => selectivity() - lit(1),
+        field => self::field(field).abs(),
     }
-    if !filter.temperature_steps.is_empty() {
-        for &temperature_step in &filter.temperature_steps {
-            expr = Some(
-                expr.unwrap_or(lit(true)).and(
-                    col("Mode")
-                        .struct_()
-                        .field_by_name("TemperatureStep")
-                        .neq(temperature_step),
-                ),
-            );
-        }
+}
+
+/// Selectivity, the alpha of the later fatty acid to the earlier one
+fn selectivity() -> Expr {
+    when(col("Alpha").lt(lit(1)))
+        .then(lit(1) / col("Alpha"))
+        .otherwise(col("Alpha"))
+}
+
+/// Purnell resolution of the pair
+///
+/// The retention factor is of the later fatty acid.
+fn resolution(plates: u32) -> Expr {
+    let retention_time = |name| col("RetentionTime").struct_().field_by_name(name);
+    let later = when(retention_time("From").gt(retention_time("To")))
+        .then(retention_time("From"))
+        .otherwise(retention_time("To"));
+    let retention_factor = (later - col("DeadTime")) / col("DeadTime");
+    lit((plates as f64).sqrt() / 4.0) * (selectivity() - lit(1)) / selectivity()
+        * retention_factor.clone()
+        / (retention_factor + lit(1))
+}
+
+fn sort(sort: Sort) -> (Vec<Expr>, SortMultipleOptions) {
+    let descending = sort.order == Order::Descending;
+    let sort_options = SortMultipleOptions::new().with_nulls_last(true);
+    match sort.by {
+        SortBy::Key => (
+            vec![
+                col("Column"),
+                col("Mode"),
+                col("FattyAcid").struct_().field_by_name("From"),
+                col("FattyAcid").struct_().field_by_name("To"),
+            ],
+            sort_options.with_order_descending(descending),
+        ),
+        // The rows of a pair follow each other
+        SortBy::Value => (
+            vec![
+                field(sort.field).aggregate(sort),
+                col("Column"),
+                col("FattyAcid").struct_().field_by_name("From"),
+                col("FattyAcid").struct_().field_by_name("To"),
+                col("Mode"),
+            ],
+            sort_options.with_order_descending_multi([descending, false, false, false, false]),
+        ),
     }
-    if !filter.fatty_acids.is_empty() {
-        for fatty_acid in &filter.fatty_acids {
-            expr = Some(
-                expr.unwrap_or(lit(true))
-                    .and(
-                        col("FattyAcid")
-                            .struct_()
-                            .field_by_name("From")
-                            .fa()
-                            .equal(fatty_acid)
-                            .not(),
-                    )
-                    .and(
-                        col("FattyAcid")
-                            .struct_()
-                            .field_by_name("To")
-                            .fa()
-                            .equal(fatty_acid)
-                            .not(),
-                    ),
-            );
-        }
+}
+
+/// Sorts by the sort keys within the column and mode groups
+fn sort_by_keys(sort_keys: &SortKeys<Field>) -> (Vec<Expr>, SortMultipleOptions) {
+    let mut by_exprs = vec![col("Column"), col("Mode")];
+    let mut descending = vec![false, false];
+    for key in &sort_keys.keys {
+        by_exprs.push(field(key.field));
+        descending.push(key.order == Order::Descending);
     }
-    expr
+    let sort_options = SortMultipleOptions::new()
+        .with_order_descending_multi(descending)
+        .with_nulls_last(true)
+        .with_maintain_order(true);
+    (by_exprs, sort_options)
 }
 
 /// Extension methods for [`Expr`]
 trait ExprExt {
-    fn aggregate(self, aggregation: Aggregation) -> Expr;
+    fn aggregate(self, sort: Sort) -> Expr;
 }
 
 impl ExprExt for Expr {
-    fn aggregate(self, aggregation: Aggregation) -> Expr {
-        match aggregation {
-            Aggregation::Maximum => self.abs().max(),
-            Aggregation::Median => self.abs().median(),
-            Aggregation::Minimum => self.abs().min(),
+    /// Aggregates the absolute values of the pair over the modes
+    fn aggregate(self, sort: Sort) -> Expr {
+        let expr = self.abs();
+        match sort.aggregation {
+            Aggregation::Maximum => expr.max(),
+            Aggregation::Mean => expr.mean(),
+            Aggregation::Median => expr.median(),
+            Aggregation::Minimum => expr.min(),
+            Aggregation::Mode => match sort.mode {
+                Some(mode) => expr
+                    .filter(
+                        col("Mode")
+                            .struct_()
+                            .field_by_name("OnsetTemperature")
+                            .eq(lit(mode.onset_temperature))
+                            .and(
+                                col("Mode")
+                                    .struct_()
+                                    .field_by_name("TemperatureStep")
+                                    .eq(lit(mode.temperature_step)),
+                            ),
+                    )
+                    .first(),
+                None => expr.first(),
+            },
         }
-        .over([col("Mode")])
+        .over([col("Column"), col("FattyAcid")])
     }
 }
 
@@ -122,12 +191,18 @@ impl ExprExt for Expr {
 #[derive(Clone, Copy, Debug)]
 pub struct Key<'a> {
     pub(crate) data_frame: &'a DataFrame,
+    pub(crate) hash: u64,
     pub(crate) settings: &'a Settings,
 }
 
 impl Hash for Key<'_> {
     fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+        self.settings.pairing.hash(state);
         self.settings.filter.hash(state);
+        self.settings.plates.hash(state);
+        self.settings.thresholds.hash(state);
         self.settings.sort.hash(state);
+        self.settings.sort_keys.hash(state);
     }
 }
@@ -0,0 +1,194 @@
+use super::filtered::field;
+use crate::{
+    app::panes::distance::settings::{Field, Settings},
+    special::data_frame::DataFrameExt as _,
+};
+use egui::util::cache::{ComputerMut, FrameCache};
+use lipid::prelude::*;
+use polars::prelude::*;
+use std::hash::{Hash, Hasher};
+
+/// Distance heatmap computed
+pub(crate) type Computed = FrameCache<Value, Computer>;
+
+/// Distance heatmap computer
+#[derive(Default)]
+pub(crate) struct Computer;
+
+impl Computer {
+    fn try_compute(&mut self, key: Key<'_>) -> PolarsResult<Value> {
+        // Column
+        let mut data_frame = key.data_frame.clone();
+        if let Some(gc_column) = key.data_frame.gc_column() {
+            let name = match &key.settings.heatmap.column {
+                Some(name) => Some(name.clone()),
+                None => gc_column.name.str()?.get(0).map(ToOwned::to_owned),
+            };
+            let column = col("Column").struct_().field_by_name("Name");
+            data_frame = data_frame
+                .lazy()
+                .filter(match name {
+                    Some(name) => column.eq(lit(name)).fill_null(false),
+                    None => column.is_null(),
+                })
+                .collect()?;
+        }
+        // Mode
+        let mode = match key.settings.heatmap.mode {
+            Some(mode) => Some((mode.onset_temperature, mode.temperature_step)),
+            None if data_frame.is_empty() => None,
+            None => {
+                let mode = data_frame["Mode"].struct_()?;
+                mode.field_by_name("OnsetTemperature")?
+                    .f64()?
+                    .get(0)
+                    .zip(mode.field_by_name("TemperatureStep")?.f64()?.get(0))
+            }
+        };
+        let Some((onset_temperature, temperature_step)) = mode else {
+            return Ok(Value::default());
+        };
+        let lazy_frame = data_frame
+            .lazy()
+            .filter(
+                col("Mode")
+                    .struct_()
+                    .field_by_name("OnsetTemperature")
+                    .eq(lit(onset_temperature))
+                    .and(
+                        col("Mode")
+                            .struct_()
+                            .field_by_name("TemperatureStep")
+                            .eq(lit(temperature_step)),
+                    ),
+            )
+            .select([
+                col("FattyAcid")
+                    .struct_()
+                    .field_by_name("From")
+                    .alias("From"),
+                col("FattyAcid").struct_().field_by_name("To").alias("To"),
+                col("RetentionTime")
+                    .struct_()
+                    .field_by_name("From")
+                    .alias("FromRetentionTime"),
+                col("RetentionTime")
+                    .struct_()
+                    .field_by_name("To")
+                    .alias("ToRetentionTime"),
+                field(key.settings.heatmap.field).alias("Value"),
+            ]);
+        let data_frame = lazy_frame.collect()?;
+        let from = data_frame["From"].fa();
+        let to = data_frame["To"].fa();
+        let from_retention_time = data_frame["FromRetentionTime"].f64()?;
+        let to_retention_time = data_frame["ToRetentionTime"].f64()?;
+        let values = data_frame["Value"].f64()?;
+        // Fatty acids in the elution order
+        let mut fatty_acids = Vec::<(FattyAcid, f64)>::new();
+        for row in 0..data_frame.height() {
+            for (fatty_acid, retention_time) in [
+                (from.get(row)?, from_retention_time.get(row)),
+                (to.get(row)?, to_retention_time.get(row)),
+            ] {
+                if let Some(fatty_acid) = fatty_acid {
+                    if !fatty_acids.iter().any(|(target, _)| *target == fatty_acid) {
+                        fatty_acids.push((fatty_acid, retention_time.unwrap_or(f64::NAN)));
+                    }
+                }
+            }
+        }
+        fatty_acids.sort_by(|left, right| left.1.total_cmp(&right.1));
+        let fatty_acids = fatty_acids
+            .into_iter()
+            .map(|(fatty_acid, _)| fatty_acid)
+            .collect::<Vec<_>>();
+        // Cells
+        let len = fatty_acids.len();
+        let mut cells = vec![None; len * len];
+        let position = |fatty_acid: Option<FattyAcid>| {
+            fatty_acid
+                .and_then(|fatty_acid| fatty_acids.iter().position(|target| *target == fatty_acid))
+        };
+        for row in 0..data_frame.height() {
+            let (Some(from), Some(to), Some(value)) = (
+                position(from.get(row)?),
+                position(to.get(row)?),
+                values.get(row),
+            ) else {
+                continue;
+            };
+            cells[from * len + to] = Some(value);
+            cells[to * len + from] = Some(mirror(key.settings.heatmap.field, value));
+        }
+        let intensities = cells
+            .iter()
+            .flatten()
+            .map(|&value| intensity(key.settings.heatmap.field, value))
+            .filter(|intensity| intensity.is_finite());
+        let range = intensities.fold(None, |range, intensity| match range {
+            None => Some([intensity, intensity]),
+            Some([min, max]) => Some([f64::min(min, intensity), f64::max(max, intensity)]),
+        });
+        Ok(Value {
+            fatty_acids,
+            cells,
+            range,
+        })
+    }
+}
+
+impl ComputerMut<Key<'_>, Value> for Computer {
+    fn compute(&mut self, key: Key<'_>) -> Value {
+        self.try_compute(key).expect("compute distance heatmap")
+    }
+}
+
+/// Distance heatmap key
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Key<'a> {
+    pub(crate) data_frame: &'a DataFrame,
+    pub(crate) hash: u64,
+    pub(crate) settings: &'a Settings,
+}
+
+impl Hash for Key<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+        self.settings.pairing.hash(state);
+        self.settings.plates.hash(state);
+        self.settings.filter.hash(state);
+        self.settings.thresholds.hash(state);
+        self.settings.heatmap.field.hash(state);
+        self.settings.heatmap.column.hash(state);
+        self.settings.heatmap.mode.hash(state);
+    }
+}
+
+/// Distance heatmap value
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Value {
+    /// Fatty acids in the elution order
+    pub(crate) fatty_acids: Vec<FattyAcid>,
+    /// Values of the row fatty acid to the column one, row-major
+    pub(crate) cells: Vec<Option<f64>>,
+    /// Range of the intensities
+    pub(crate) range: Option<[f64; 2]>,
+}
+
+/// Value of the reversed pair
+fn mirror(field: Field, value: f64) -> f64 {
+    match field {
+        Field::Alpha => 1.0 / value,
+        Field::RetentionTime | Field::EquivalentChainLength => -value,
+        Field::EuclideanDistance | Field::Resolution => value,
+    }
+}
+
+/// Intensity of the value on the color scale, independent of the pair order
+pub(crate) fn intensity(field: Field, value: f64) -> f64 {
+    match field {
+        Field::Alpha => f64::max(value, 1.0 / value),
+        _ => value.abs(),
+    }
+}
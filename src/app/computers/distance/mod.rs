@@ -1,3 +1,4 @@
+use crate::app::panes::distance::settings::{Metric, Pairing};
 use egui::util::cache::{ComputerMut, FrameCache};
 use polars::prelude::*;
 use polars_ext::ExprExt;
@@ -13,60 +14,18 @@ pub(crate) struct Computer;
 impl Computer {
     fn try_compute(&mut self, key: Key<'_>) -> PolarsResult<DataFrame> {
         let mut lazy_frame = key.data_frame.clone().lazy();
-        // Join
-        lazy_frame = lazy_frame
-            .clone()
-            .select([
-                col("Mode").hash().alias("LeftHash"),
-                as_struct(vec![
-                    col("FattyAcid"),
-                    col("RetentionTime")
-                        .struct_()
-                        .field_by_name("Absolute")
-                        .struct_()
-                        .field_by_name("Mean")
-                        .name()
-                        .keep(),
-                    col("ChainLength")
-                        .struct_()
-                        .field_by_name("EquivalentChainLength"),
-                ])
-                .alias("From"),
-                col("Mode"),
-                col("DeadTime"),
-            ])
-            .with_row_index("LeftIndex", None)
-            .join_builder()
-            .with(
-                lazy_frame
-                    .select([
-                        col("Mode").hash().alias("RightHash"),
-                        as_struct(vec![
-                            col("FattyAcid"),
-                            col("RetentionTime")
-                                .struct_()
-                                .field_by_name("Absolute")
-                                .struct_()
-                                .field_by_name("Mean")
-                                .name()
-                                .keep(),
-                            col("ChainLength")
-                                .struct_()
-                                .field_by_name("EquivalentChainLength"),
-                        ])
-                        .alias("To"),
-                    ])
-                    .with_row_index("RightIndex", None),
-            )
-            .join_where(vec![
-                // Same modes
-                col("LeftHash").eq(col("RightHash")),
-                // Fatty asids not equals combination
-                col("LeftIndex").lt(col("RightIndex")),
-            ]);
+        // Pairs
+        lazy_frame = match key.pairing {
+            Pairing::All => all_pairs(lazy_frame),
+            Pairing::Adjacent => neighbours(lazy_frame, 1, Metric::RetentionTime)?,
+            Pairing::Nearest { count, metric } => {
+                nearest(neighbours(lazy_frame, count, metric)?, count, metric)?
+            }
+        };
         // Select
         lazy_frame = lazy_frame
             .select([
+                col("Column"),
                 col("Mode"),
                 col("DeadTime"),
                 as_struct(vec![
@@ -91,7 +50,7 @@ impl Computer {
                         .keep(),
                     (col("To").struct_().field_by_name("RetentionTime")
                         - col("From").struct_().field_by_name("RetentionTime"))
-                    .over([col("Mode")])
+                    .over([col("Column"), col("Mode")])
                     .alias("Delta"),
                 ])
                 .alias("RetentionTime"),
@@ -108,22 +67,22 @@ impl Computer {
                         .keep(),
                     (col("To").struct_().field_by_name("EquivalentChainLength")
                         - col("From").struct_().field_by_name("EquivalentChainLength"))
-                    .over([col("Mode")])
+                    .over([col("Column"), col("Mode")])
                     .alias("Delta"),
                 ])
                 .alias("EquivalentChainLength"),
                 ((col("From").struct_().field_by_name("RetentionTime") - col("DeadTime"))
                     / (col("To").struct_().field_by_name("RetentionTime") - col("DeadTime"))
-                        .over([col("Mode")]))
+                        .over([col("Column"), col("Mode")]))
                 .alias("Alpha"),
             ])
             .with_column(
-                (col("RetentionTime").struct_().field_by_name("Delta").pow(2)
-                    + col("EquivalentChainLength")
+                euclidean_distance(
+                    col("RetentionTime").struct_().field_by_name("Delta"),
+                    col("EquivalentChainLength")
                         .struct_()
-                        .field_by_name("Delta")
-                        .pow(2))
-                .sqrt()
+                        .field_by_name("Delta"),
+                )
                 .alias("EuclideanDistance"),
             );
         lazy_frame.collect()
@@ -141,13 +100,159 @@ impl ComputerMut<Key<'_>, DataFrame> for Computer {
 pub struct Key<'a> {
     pub(crate) data_frame: &'a DataFrame,
     pub(crate) hash: u64,
+    pub(crate) pairing: Pairing,
 }
 
 impl Hash for Key<'_> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.hash.hash(state);
+        self.pairing.hash(state);
     }
 }
 
+/// Fatty acid with its coordinates
+fn fatty_acid() -> Expr {
+    as_struct(vec![
+        col("FattyAcid"),
+        col("RetentionTime")
+            .struct_()
+            .field_by_name("Absolute")
+            .struct_()
+            .field_by_name("Mean")
+            .name()
+            .keep(),
+        col("ChainLength")
+            .struct_()
+            .field_by_name("EquivalentChainLength"),
+    ])
+}
+
+/// All pairs within the same columns and modes
+fn all_pairs(lazy_frame: LazyFrame) -> LazyFrame {
+    lazy_frame
+        .clone()
+        .select([
+            as_struct(vec![col("Column"), col("Mode")])
+                .hash()
+                .alias("LeftHash"),
+            fatty_acid().alias("From"),
+            col("Column"),
+            col("Mode"),
+            col("DeadTime"),
+        ])
+        .with_row_index("LeftIndex", None)
+        .join_builder()
+        .with(
+            lazy_frame
+                .select([
+                    as_struct(vec![col("Column"), col("Mode")])
+                        .hash()
+                        .alias("RightHash"),
+                    fatty_acid().alias("To"),
+                ])
+                .with_row_index("RightIndex", None),
+        )
+        .join_where(vec![
+            // Same columns and modes
+            col("LeftHash").eq(col("RightHash")),
+            // Fatty asids not equals combination
+            col("LeftIndex").lt(col("RightIndex")),
+        ])
+}
+
+/// Pairs of each fatty acid with the following ones by the metric
+///
+/// The frame grows linearly, the count of the following fatty acids times the
+/// count of the fatty acids.
+fn neighbours(lazy_frame: LazyFrame, count: usize, metric: Metric) -> PolarsResult<LazyFrame> {
+    let sorted = lazy_frame
+        .select([
+            col("Column"),
+            col("Mode"),
+            col("DeadTime"),
+            fatty_acid().alias("From"),
+        ])
+        .sort_by_exprs(
+            [col("Column"), col("Mode"), coordinate("From", metric)],
+            SortMultipleOptions::new().with_nulls_last(true),
+        )
+        .with_row_index("LeftIndex", None);
+    let offsets = (1..=count as i64)
+        .map(|offset| {
+            sorted
+                .clone()
+                .with_columns([
+                    col("From")
+                        .shift(lit(-offset))
+                        .over([col("Column"), col("Mode")])
+                        .alias("To"),
+                    col("LeftIndex")
+                        .shift(lit(-offset))
+                        .over([col("Column"), col("Mode")])
+                        .alias("RightIndex"),
+                ])
+                .filter(col("RightIndex").is_not_null())
+        })
+        .collect::<Vec<_>>();
+    concat(offsets, UnionArgs::default())
+}
+
+/// Keeps the pairs which are one of the nearest ones of any of its fatty acids
+fn nearest(lazy_frame: LazyFrame, count: usize, metric: Metric) -> PolarsResult<LazyFrame> {
+    let lazy_frame = lazy_frame.with_row_index("PairIndex", None);
+    let distance = (coordinate("To", metric) - coordinate("From", metric))
+        .abs()
+        .alias("Distance");
+    let nearest = concat(
+        [
+            lazy_frame.clone().select([
+                col("PairIndex"),
+                col("LeftIndex").alias("Index"),
+                distance.clone(),
+            ]),
+            lazy_frame.clone().select([
+                col("PairIndex"),
+                col("RightIndex").alias("Index"),
+                distance,
+            ]),
+        ],
+        UnionArgs::default(),
+    )?
+    .filter(
+        col("Distance")
+            .rank(
+                RankOptions {
+                    method: RankMethod::Ordinal,
+                    descending: false,
+                },
+                None,
+            )
+            .over([col("Index")])
+            .lt_eq(lit(count as IdxSize)),
+    )
+    .select([col("PairIndex")])
+    .unique(None, UniqueKeepStrategy::Any);
+    Ok(lazy_frame.join(
+        nearest,
+        [col("PairIndex")],
+        [col("PairIndex")],
+        JoinArgs::new(JoinType::Inner),
+    ))
+}
+
+fn coordinate(name: &str, metric: Metric) -> Expr {
+    let field = match metric {
+        Metric::RetentionTime => "RetentionTime",
+        Metric::EquivalentChainLength => "EquivalentChainLength",
+    };
+    col(name).struct_().field_by_name(field)
+}
+
+/// Euclidean distance of the coordinate differences
+pub(crate) fn euclidean_distance(x: Expr, y: Expr) -> Expr {
+    (x.pow(2) + y.pow(2)).sqrt()
+}
+
 pub(crate) mod filtered;
+pub(crate) mod heatmap;
 pub(crate) mod plot;
@@ -55,13 +55,19 @@ impl ComputerMut<Key<'_>, Value> for Computer {
 #[derive(Clone, Copy, Debug)]
 pub(crate) struct Key<'a> {
     pub(crate) data_frame: &'a DataFrame,
+    pub(crate) hash: u64,
     pub(crate) settings: &'a Settings,
 }
 
 impl Hash for Key<'_> {
     fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+        self.settings.pairing.hash(state);
+        self.settings.plates.hash(state);
         self.settings.filter.hash(state);
+        self.settings.thresholds.hash(state);
         self.settings.sort.hash(state);
+        self.settings.sort_keys.hash(state);
     }
 }
 
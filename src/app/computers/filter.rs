@@ -0,0 +1,268 @@
+use crate::app::panes::source::settings::{
+    Bounds, Comparison, Condition, Filter, Kind, Operator, Selection, Structure, Target, Threshold,
+};
+use lipid::prelude::*;
+use polars::prelude::*;
+
+/// Filter predicate
+///
+/// The fatty acid selection is applied to each of the fatty acid expressions,
+/// the structure conditions to the target ones.
+pub(crate) fn filter(filter: &Filter, fatty_acids: &[Expr]) -> Option<Expr> {
+    let mut predicates = Vec::new();
+    predicates.extend(selection(&filter.columns, |name| {
+        col("Column")
+            .struct_()
+            .field_by_name("Name")
+            .eq(lit(name.clone()))
+            .fill_null(false)
+    }));
+    predicates.extend(selection(
+        &filter.onset_temperatures,
+        |&onset_temperature| {
+            col("Mode")
+                .struct_()
+                .field_by_name("OnsetTemperature")
+                .eq(onset_temperature)
+        },
+    ));
+    predicates.extend(selection(&filter.temperature_steps, |&temperature_step| {
+        col("Mode")
+            .struct_()
+            .field_by_name("TemperatureStep")
+            .eq(temperature_step)
+    }));
+    predicates.extend(bounds(
+        filter.onset_temperature,
+        col("Mode").struct_().field_by_name("OnsetTemperature"),
+    ));
+    predicates.extend(bounds(
+        filter.temperature_step,
+        col("Mode").struct_().field_by_name("TemperatureStep"),
+    ));
+    for expr in fatty_acids {
+        predicates.extend(selection(&filter.fatty_acids, |fatty_acid| {
+            expr.clone().fa().equal(fatty_acid)
+        }));
+    }
+    let targets = match filter.structure.target {
+        Target::From => &fatty_acids[..1],
+        Target::To => &fatty_acids[fatty_acids.len() - 1..],
+        Target::Both => fatty_acids,
+    };
+    for expr in targets {
+        predicates.extend(structure(&filter.structure, expr));
+    }
+    predicates.into_iter().reduce(Expr::and)
+}
+
+/// Thresholds predicate
+///
+/// All of the thresholds must be satisfied, the field expression is provided by
+/// the caller.
+pub(crate) fn thresholds<F: Copy>(
+    thresholds: &[Threshold<F>],
+    field: impl Fn(F) -> Expr,
+) -> Option<Expr> {
+    thresholds
+        .iter()
+        .map(|threshold| self::threshold(threshold, &field))
+        .reduce(Expr::and)
+}
+
+/// Threshold predicate
+pub(crate) fn threshold<F: Copy>(threshold: &Threshold<F>, field: impl Fn(F) -> Expr) -> Expr {
+    let mut expr = field(threshold.field);
+    if threshold.absolute {
+        expr = expr.abs();
+    }
+    match threshold.comparison {
+        Comparison::Less => expr.lt(lit(threshold.value)),
+        Comparison::Greater => expr.gt(lit(threshold.value)),
+    }
+}
+
+/// Fatty acid without the unsaturated bonds
+pub(crate) fn saturated(fatty_acid: Expr) -> Expr {
+    fatty_acid
+        .struct_()
+        .field_by_name("Unsaturated")
+        .list()
+        .len()
+        .eq(lit(0))
+}
+
+/// Structure conditions of the fatty acid
+///
+/// Isomerism of the unsaturated bonds is negative for trans, unsaturation is
+/// one for double bonds. The trans, the ω and the Δ conditions consider the
+/// double bonds only, the ω family is the position of the last double bond
+/// from the methyl end.
+fn structure(structure: &Structure, expr: &Expr) -> Option<Expr> {
+    let carbons = || expr.clone().struct_().field_by_name("Carbons");
+    let unsaturated = || expr.clone().struct_().field_by_name("Unsaturated").list();
+    let count = |predicate: Expr| {
+        unsaturated()
+            .eval(predicate.cast(DataType::UInt32), false)
+            .list()
+            .sum()
+    };
+    let bond = |name| col("").struct_().field_by_name(name);
+    let double = || bond("Unsaturation").eq(lit(1));
+    let double_bonds = || count(double());
+    let conditions = structure
+        .conditions
+        .iter()
+        .map(|&condition| match condition {
+            Condition::Carbons { min, max } => {
+                carbons().is_between(lit(min), lit(max), ClosedInterval::Both)
+            }
+            Condition::DoubleBonds { min, max } => {
+                double_bonds().is_between(lit(min), lit(max), ClosedInterval::Both)
+            }
+            Condition::Trans => count(double().and(bond("Isomerism").lt(lit(0)))).gt(lit(0)),
+            Condition::Omega(n) => (carbons().cast(DataType::Int32)
+                - unsaturated()
+                    .eval(bond("Index").filter(double()), false)
+                    .list()
+                    .max()
+                    .cast(DataType::Int32))
+            .eq(lit(n as i32))
+            .fill_null(false),
+            Condition::Delta(n) => count(double().and(bond("Index").eq(lit(n)))).gt(lit(0)),
+            Condition::Saturated => unsaturated().len().eq(lit(0)),
+        });
+    match structure.operator {
+        Operator::And => conditions.reduce(Expr::and),
+        Operator::Or => conditions.reduce(Expr::or),
+    }
+}
+
+/// Keeps the rows equal to any of the values or to none of them
+pub(crate) fn selection<T>(selection: &Selection<T>, f: impl Fn(&T) -> Expr) -> Option<Expr> {
+    let any = selection.values.iter().map(f).reduce(Expr::or)?;
+    Some(match selection.kind {
+        Kind::Include => any,
+        Kind::Exclude => any.not(),
+    })
+}
+
+fn bounds(bounds: Bounds, expr: Expr) -> Option<Expr> {
+    let min = bounds.min.map(|min| expr.clone().gt_eq(lit(min)));
+    let max = bounds.max.map(|max| expr.lt_eq(lit(max)));
+    min.into_iter().chain(max).reduce(Expr::and)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Fatty acids of the carbons and the bonds (index, isomerism,
+    /// unsaturation)
+    fn data_frame(fatty_acids: &[(u8, &[(u8, i8, u8)])]) -> DataFrame {
+        let carbons = Series::from_iter(fatty_acids.iter().map(|&(carbons, _)| carbons))
+            .with_name("Carbons".into());
+        let unsaturated = fatty_acids
+            .iter()
+            .map(|&(_, bonds)| -> PolarsResult<Option<Series>> {
+                let field = |name: &str, f: fn(&(u8, i8, u8)) -> i64| {
+                    Series::from_iter(bonds.iter().map(f)).with_name(name.into())
+                };
+                Ok(Some(
+                    StructChunked::from_series(
+                        PlSmallStr::EMPTY,
+                        bonds.len(),
+                        [
+                            field("Index", |bond| bond.0 as _).cast(&DataType::UInt8)?,
+                            field("Isomerism", |bond| bond.1 as _).cast(&DataType::Int8)?,
+                            field("Unsaturation", |bond| bond.2 as _).cast(&DataType::UInt8)?,
+                        ]
+                        .iter(),
+                    )?
+                    .into_series(),
+                ))
+            })
+            .collect::<PolarsResult<ListChunked>>()
+            .unwrap()
+            .with_name("Unsaturated".into())
+            .into_series();
+        let fatty_acid = StructChunked::from_series(
+            "FattyAcid".into(),
+            fatty_acids.len(),
+            [carbons, unsaturated].iter(),
+        )
+        .unwrap()
+        .into_series();
+        DataFrame::new(vec![fatty_acid.into_column()]).unwrap()
+    }
+
+    /// Indices of the rows satisfying the condition
+    fn filtered(data_frame: &DataFrame, condition: Condition) -> Vec<u32> {
+        let structure = Structure {
+            conditions: vec![condition],
+            ..Structure::new()
+        };
+        data_frame
+            .clone()
+            .lazy()
+            .with_row_index("Index", None)
+            .filter(self::structure(&structure, &col("FattyAcid")).unwrap())
+            .collect()
+            .unwrap()["Index"]
+            .u32()
+            .unwrap()
+            .into_no_null_iter()
+            .collect()
+    }
+
+    fn fatty_acids() -> DataFrame {
+        data_frame(&[
+            // 16:0
+            (16, &[]),
+            // 18:1Δ9c
+            (18, &[(9, 1, 1)]),
+            // 18:2Δ9c,12c
+            (18, &[(9, 1, 1), (12, 1, 1)]),
+            // 18:1Δ9t
+            (18, &[(9, -1, 1)]),
+            // 18:3Δ9c,12c,15c
+            (18, &[(9, 1, 1), (12, 1, 1), (15, 1, 1)]),
+            // 18:2Δ9c,15 with the triple bond at 15
+            (18, &[(9, 1, 1), (15, 0, 2)]),
+        ])
+    }
+
+    #[test]
+    fn carbons_and_double_bonds() {
+        let data_frame = fatty_acids();
+        assert_eq!(
+            filtered(&data_frame, Condition::Carbons { min: 16, max: 16 }),
+            [0]
+        );
+        assert_eq!(
+            filtered(&data_frame, Condition::DoubleBonds { min: 2, max: 3 }),
+            [2, 4]
+        );
+        assert_eq!(filtered(&data_frame, Condition::Saturated), [0]);
+    }
+
+    #[test]
+    fn trans() {
+        assert_eq!(filtered(&fatty_acids(), Condition::Trans), [3]);
+    }
+
+    #[test]
+    fn omega() {
+        let data_frame = fatty_acids();
+        assert_eq!(filtered(&data_frame, Condition::Omega(9)), [1, 3, 5]);
+        assert_eq!(filtered(&data_frame, Condition::Omega(6)), [2]);
+        assert_eq!(filtered(&data_frame, Condition::Omega(3)), [4]);
+    }
+
+    #[test]
+    fn delta() {
+        let data_frame = fatty_acids();
+        assert_eq!(filtered(&data_frame, Condition::Delta(12)), [2, 4]);
+        assert_eq!(filtered(&data_frame, Condition::Delta(15)), [4]);
+    }
+}
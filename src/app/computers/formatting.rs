@@ -0,0 +1,82 @@
+use super::filter::{saturated, threshold};
+use crate::app::panes::source::settings::{Formatting, Rule};
+use egui::util::cache::{ComputerMut, FrameCache};
+use polars::prelude::*;
+use std::{
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+};
+
+/// Formatting computed
+pub(crate) type Computed<F> = FrameCache<DataFrame, Computer<F>>;
+
+/// Formatting computer
+///
+/// Evaluates the rules over the computed frame, one column per rule and the
+/// saturation of the rows.
+pub(crate) struct Computer<F>(PhantomData<F>);
+
+impl<F> Default for Computer<F> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<F: Copy> Computer<F> {
+    fn try_compute(&mut self, key: Key<'_, F>) -> PolarsResult<DataFrame> {
+        let mut exprs = Vec::new();
+        for (index, rule) in key.formatting.rules.iter().enumerate() {
+            let expr = match *rule {
+                Rule::Scale { field, reverse } => {
+                    let value = (key.field)(field);
+                    let scale =
+                        (value.clone() - value.clone().min()) / (value.clone().max() - value.min());
+                    if reverse { lit(1) - scale } else { scale }
+                }
+                Rule::Highlight { threshold: t, .. } => threshold(&t, key.field),
+            };
+            exprs.push(expr.alias(index.to_string()));
+        }
+        if key.formatting.saturation {
+            exprs.push((key.saturated)().alias("Saturated"));
+        }
+        if exprs.is_empty() {
+            return Ok(DataFrame::empty());
+        }
+        key.data_frame.clone().lazy().select(exprs).collect()
+    }
+}
+
+impl<F: Copy> ComputerMut<Key<'_, F>, DataFrame> for Computer<F> {
+    fn compute(&mut self, key: Key<'_, F>) -> DataFrame {
+        self.try_compute(key).expect("compute formatting")
+    }
+}
+
+/// Formatting key
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Key<'a, F> {
+    pub(crate) data_frame: &'a DataFrame,
+    pub(crate) hash: u64,
+    pub(crate) formatting: &'a Formatting<F>,
+    pub(crate) field: fn(F) -> Expr,
+    pub(crate) saturated: fn() -> Expr,
+}
+
+impl<F: Hash> Hash for Key<'_, F> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+        self.formatting.hash(state);
+    }
+}
+
+/// Saturation of the fatty acid of the row
+pub(crate) fn fatty_acid() -> Expr {
+    saturated(col("FattyAcid"))
+}
+
+/// Saturation of both of the fatty acids of the pair
+pub(crate) fn pair() -> Expr {
+    saturated(col("FattyAcid").struct_().field_by_name("From"))
+        .and(saturated(col("FattyAcid").struct_().field_by_name("To")))
+}
@@ -0,0 +1,169 @@
+use crate::app::panes::source::settings::Grouping;
+use egui::util::{
+    cache::{ComputerMut, FrameCache},
+    hash,
+};
+use polars::prelude::*;
+use std::{
+    collections::HashMap,
+    hash::{Hash, Hasher},
+};
+
+/// Grouping computed
+pub(crate) type Computed = FrameCache<Vec<Group>, Computer>;
+
+/// Grouping computer
+///
+/// Groups the rows of the computed frame in the order of the first rows, the
+/// key of a group is the hash of the grouped values.
+#[derive(Default)]
+pub(crate) struct Computer;
+
+impl Computer {
+    fn try_compute(&mut self, key: Key<'_>) -> PolarsResult<Vec<Group>> {
+        let names: &[&str] = match key.grouping {
+            Grouping::None => return Ok(Vec::new()),
+            Grouping::Mode => &["Column", "Mode"],
+            Grouping::Pair => &["FattyAcid"],
+        };
+        let columns = names
+            .iter()
+            .map(|name| key.data_frame.column(name))
+            .collect::<PolarsResult<Vec<_>>>()?;
+        let mut indices = HashMap::new();
+        let mut groups = Vec::<(u64, Vec<usize>)>::new();
+        for row in 0..key.data_frame.height() {
+            let values = columns
+                .iter()
+                .map(|column| column.str_value(row))
+                .collect::<PolarsResult<Vec<_>>>()?;
+            let group = hash(&values);
+            let index = *indices.entry(group).or_insert_with(|| {
+                groups.push((group, Vec::new()));
+                groups.len() - 1
+            });
+            groups[index].1.push(row);
+        }
+        groups
+            .into_iter()
+            .map(|(group, rows)| {
+                Ok(Group {
+                    key: group,
+                    summary: (key.summary)(key.data_frame, &rows)?,
+                    rows,
+                })
+            })
+            .collect()
+    }
+}
+
+impl ComputerMut<Key<'_>, Vec<Group>> for Computer {
+    fn compute(&mut self, key: Key<'_>) -> Vec<Group> {
+        self.try_compute(key).expect("compute grouping")
+    }
+}
+
+/// Grouping key
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Key<'a> {
+    pub(crate) data_frame: &'a DataFrame,
+    pub(crate) hash: u64,
+    pub(crate) grouping: Grouping,
+    pub(crate) summary: fn(&DataFrame, &[usize]) -> PolarsResult<Summary>,
+}
+
+impl Hash for Key<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+        self.grouping.hash(state);
+    }
+}
+
+/// Group of the rows
+#[derive(Clone, Debug)]
+pub(crate) struct Group {
+    pub(crate) key: u64,
+    pub(crate) rows: Vec<usize>,
+    pub(crate) summary: Summary,
+}
+
+/// Summary of the group
+///
+/// The span of the retention times, the minimum alpha and the mean relative
+/// standard deviation of the retention times (%).
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Summary {
+    pub(crate) count: usize,
+    pub(crate) retention_time: Option<[f64; 2]>,
+    pub(crate) alpha: Option<f64>,
+    pub(crate) relative_standard_deviation: Option<f64>,
+}
+
+/// Summary of the source rows, the alpha is of the adjacent fatty acids in
+/// the elution order
+pub(crate) fn source(data_frame: &DataFrame, rows: &[usize]) -> PolarsResult<Summary> {
+    let absolute = data_frame["RetentionTime"]
+        .struct_()?
+        .field_by_name("Absolute")?;
+    let absolute = absolute.struct_()?;
+    let mean = absolute.field_by_name("Mean")?;
+    let mean = mean.f64()?;
+    let standard_deviation = absolute.field_by_name("StandardDeviation")?;
+    let standard_deviation = standard_deviation.f64()?;
+    let dead_time = data_frame["DeadTime"].f64()?;
+    let mut retention_times = rows
+        .iter()
+        .filter_map(|&row| Some((mean.get(row)?, dead_time.get(row))))
+        .collect::<Vec<_>>();
+    retention_times.sort_by(|left, right| left.0.total_cmp(&right.0));
+    let alpha = retention_times
+        .windows(2)
+        .filter_map(|pair| {
+            let dead_time = pair[0].1?;
+            Some((pair[1].0 - dead_time) / (pair[0].0 - dead_time))
+        })
+        .min_by(f64::total_cmp);
+    let relative_standard_deviations = rows
+        .iter()
+        .filter_map(|&row| Some(standard_deviation.get(row)? / mean.get(row)? * 100.0))
+        .collect::<Vec<_>>();
+    Ok(Summary {
+        count: rows.len(),
+        retention_time: span(
+            retention_times
+                .iter()
+                .map(|&(retention_time, _)| retention_time),
+        ),
+        alpha,
+        relative_standard_deviation: average(&relative_standard_deviations),
+    })
+}
+
+/// Summary of the distance rows, the retention time is the distance
+pub(crate) fn pair(data_frame: &DataFrame, rows: &[usize]) -> PolarsResult<Summary> {
+    let delta = data_frame["RetentionTime"]
+        .struct_()?
+        .field_by_name("Delta")?;
+    let delta = delta.f64()?;
+    let alpha = data_frame["Alpha"].f64()?;
+    Ok(Summary {
+        count: rows.len(),
+        retention_time: span(rows.iter().filter_map(|&row| delta.get(row))),
+        alpha: rows
+            .iter()
+            .filter_map(|&row| alpha.get(row))
+            .min_by(f64::total_cmp),
+        relative_standard_deviation: None,
+    })
+}
+
+fn span(values: impl Iterator<Item = f64>) -> Option<[f64; 2]> {
+    values.fold(None, |span, value| match span {
+        None => Some([value, value]),
+        Some([min, max]) => Some([min.min(value), max.max(value)]),
+    })
+}
+
+fn average(values: &[f64]) -> Option<f64> {
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
@@ -1,13 +1,32 @@
 pub(crate) use self::{
+    comparison::{
+        Computed as ComparisonComputed, Key as ComparisonKey,
+        plot::{
+            Computed as ComparisonPlotComputed, Key as ComparisonPlotKey,
+            Value as ComparisonPlotValue,
+        },
+    },
     distance::{
         Computed as DistanceComputed, Key as DistanceKey,
         filtered::{Computed as DistanceFilteredComputed, Key as DistanceFilteredKey},
+        heatmap::{
+            Computed as DistanceHeatmapComputed, Key as DistanceHeatmapKey,
+            Value as DistanceHeatmapValue,
+        },
         plot::{
             Computed as DistancePlotComputed, Key as DistancePlotKey, Value as DistancePlotValue,
         },
     },
+    formatting::{Computed as FormattingComputed, Key as FormattingKey},
+    grouping::{Computed as GroupingComputed, Key as GroupingKey},
+    search::{Computed as SearchComputed, Key as SearchKey},
     source::{
         Computed as SourceComputed, Key as SourceKey,
+        clustering::{Computed as SourceClusteringComputed, Key as SourceClusteringKey},
+        explain::{Computed as SourceExplainComputed, Key as SourceExplainKey},
+        pivot::{
+            Computed as SourcePivotComputed, Key as SourcePivotKey, Value as SourcePivotValue,
+        },
         plot::{Computed as SourcePlotComputed, Key as SourcePlotKey, Value as SourcePlotValue},
     },
 };
@@ -46,5 +65,10 @@ pub(crate) mod plot {
     }
 }
 
+pub(crate) mod comparison;
 pub(crate) mod distance;
+pub(crate) mod filter;
+pub(crate) mod formatting;
+pub(crate) mod grouping;
+pub(crate) mod search;
 pub(crate) mod source;
@@ -0,0 +1,69 @@
+use crate::{
+    app::panes::search::{Query, Searchable},
+    special::name::Name,
+};
+use egui::util::cache::{ComputerMut, FrameCache};
+use polars::prelude::*;
+use std::hash::{Hash, Hasher};
+
+/// Search computed
+pub(crate) type Computed = FrameCache<Vec<usize>, Computer>;
+
+/// Search computer
+///
+/// Matched rows of the displayed table, empty for the empty query. The row
+/// matches if any of its cells does.
+#[derive(Default)]
+pub(crate) struct Computer;
+
+impl Computer {
+    fn try_compute(&mut self, key: Key<'_>) -> PolarsResult<Vec<usize>> {
+        let Some(query) = Query::new(key.query) else {
+            return Ok(Vec::new());
+        };
+        let table = key.table;
+        // Names of the fatty acid columns, decoded once for the query
+        let mut names = Vec::with_capacity(table.width());
+        for column in 0..table.width() {
+            names.push(match (&query, table.fatty_acids(column)?) {
+                (Query::FattyAcid(_), Some(series)) => Some(Name::from_series(&series)?),
+                _ => None,
+            });
+        }
+        let mut rows = Vec::new();
+        for row in 0..table.height() {
+            for (column, names) in names.iter().enumerate() {
+                let matches = match names {
+                    Some(names) => query.name(names[row].as_ref()),
+                    None => table.matches(&query, row, column)?,
+                };
+                if matches {
+                    rows.push(row);
+                    break;
+                }
+            }
+        }
+        Ok(rows)
+    }
+}
+
+impl ComputerMut<Key<'_>, Vec<usize>> for Computer {
+    fn compute(&mut self, key: Key<'_>) -> Vec<usize> {
+        self.try_compute(key).expect("compute search")
+    }
+}
+
+/// Search key
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Key<'a> {
+    pub(crate) table: &'a dyn Searchable,
+    pub(crate) hash: u64,
+    pub(crate) query: &'a str,
+}
+
+impl Hash for Key<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+        self.query.hash(state);
+    }
+}
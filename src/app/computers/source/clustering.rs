@@ -0,0 +1,379 @@
+use crate::{
+    app::panes::source::settings::{Feature, Linkage, Settings},
+    special::column::gc_column,
+};
+use egui::{
+    emath::Float as _,
+    util::cache::{ComputerMut, FrameCache},
+};
+use lipid::prelude::*;
+use polars::prelude::*;
+use std::{
+    collections::HashMap,
+    hash::{Hash, Hasher},
+};
+
+/// Source clustering computed
+pub(crate) type Computed = FrameCache<Dendrogram, Computer>;
+
+/// Source clustering computer
+#[derive(Default)]
+pub(crate) struct Computer;
+
+impl ComputerMut<Key<'_>, Dendrogram> for Computer {
+    fn compute(&mut self, key: Key<'_>) -> Dendrogram {
+        Dendrogram::new(key.data_frame, key.settings).expect("compute source clustering")
+    }
+}
+
+/// Source clustering key
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Key<'a> {
+    pub(crate) data_frame: &'a DataFrame,
+    pub(crate) hash: u64,
+    pub(crate) settings: &'a Settings,
+}
+
+impl Hash for Key<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+        self.settings.logarithmic.hash(state);
+        self.settings.clustering.hash(state);
+    }
+}
+
+/// Dendrogram of the fatty acids
+///
+/// The leaves are the fatty acids, the merge `i` makes the cluster `n + i`.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Dendrogram {
+    pub(crate) fatty_acids: Vec<FattyAcid>,
+    pub(crate) merges: Vec<Merge>,
+    /// Cluster of each fatty acid, numbered from one in the leaf order
+    pub(crate) clusters: Vec<u32>,
+    /// Fatty acids in the leaf order
+    pub(crate) order: Vec<usize>,
+}
+
+impl Dendrogram {
+    /// Clusters the fatty acids by the feature vectors of their values in all
+    /// of the columns and modes
+    pub(crate) fn new(data_frame: &DataFrame, settings: &Settings) -> PolarsResult<Self> {
+        let (fatty_acids, features) = features(data_frame, settings)?;
+        let merges = merges(&features, settings.clustering.linkage);
+        let order = order(fatty_acids.len(), &merges);
+        let clusters = clusters(
+            fatty_acids.len(),
+            &merges,
+            &order,
+            settings.clustering.clusters,
+        );
+        Ok(Self {
+            fatty_acids,
+            merges,
+            clusters,
+            order,
+        })
+    }
+
+    /// Cluster of the fatty acid
+    pub(crate) fn cluster(&self, fatty_acid: &FattyAcid) -> Option<u32> {
+        let index = self
+            .fatty_acids
+            .iter()
+            .position(|target| target == fatty_acid)?;
+        Some(self.clusters[index])
+    }
+
+    /// Height of the cut into the clusters, between the last applied and the
+    /// first skipped merges
+    pub(crate) fn cut(&self) -> Option<f64> {
+        let count = self.clusters.iter().max().copied().unwrap_or_default() as usize;
+        let applied = (self.merges.len() + 1).checked_sub(count)?;
+        let above = self.merges.get(applied)?.distance;
+        let below = match applied.checked_sub(1) {
+            Some(index) => self.merges[index].distance,
+            None => 0.0,
+        };
+        Some((below + above) / 2.0)
+    }
+}
+
+/// Merge of two clusters
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Merge {
+    pub(crate) left: usize,
+    pub(crate) right: usize,
+    pub(crate) distance: f64,
+    pub(crate) size: usize,
+}
+
+/// Feature vectors of the fatty acids, one dimension per column and mode
+fn features(
+    data_frame: &DataFrame,
+    settings: &Settings,
+) -> PolarsResult<(Vec<FattyAcid>, Vec<Vec<Option<f64>>>)> {
+    let mut lazy_frame = data_frame.clone().lazy();
+    if data_frame.column("Column").is_err() {
+        lazy_frame = lazy_frame.with_column(gc_column::unnamed());
+    }
+    let value = match settings.clustering.feature {
+        Feature::EquivalentChainLength => col("FattyAcid")
+            .fa()
+            .ecl(
+                col("RetentionTimeMean"),
+                ChainLengthOptions::new().logarithmic(settings.logarithmic),
+            )
+            .over(["Column", "Mode"]),
+        Feature::RetentionTime => col("RetentionTimeMean"),
+    };
+    let data_frame = lazy_frame
+        .with_column(
+            col("RetentionTime")
+                .list()
+                .mean()
+                .alias("RetentionTimeMean"),
+        )
+        .select([
+            col("Column").struct_().field_by_name("Name"),
+            col("Mode").struct_().field_by_name("OnsetTemperature"),
+            col("Mode").struct_().field_by_name("TemperatureStep"),
+            col("FattyAcid"),
+            value.alias("Value"),
+        ])
+        .collect()?;
+    let names = data_frame["Name"].str()?;
+    let onset_temperatures = data_frame["OnsetTemperature"].f64()?;
+    let temperature_steps = data_frame["TemperatureStep"].f64()?;
+    let fatty_acid = data_frame["FattyAcid"].fa();
+    let values = data_frame["Value"].f64()?;
+    let mut dimensions = HashMap::new();
+    let mut fatty_acids = Vec::new();
+    let mut features = Vec::<Vec<Option<f64>>>::new();
+    for row in 0..data_frame.height() {
+        let Some(fatty_acid) = fatty_acid.get(row)? else {
+            continue;
+        };
+        let dimension = (
+            names.get(row).map(ToOwned::to_owned),
+            onset_temperatures.get(row).map(|value| value.ord()),
+            temperature_steps.get(row).map(|value| value.ord()),
+        );
+        let len = dimensions.len();
+        let dimension = *dimensions.entry(dimension).or_insert(len);
+        let index = match fatty_acids.iter().position(|target| *target == fatty_acid) {
+            Some(index) => index,
+            None => {
+                fatty_acids.push(fatty_acid);
+                features.push(Vec::new());
+                features.len() - 1
+            }
+        };
+        let feature = &mut features[index];
+        if feature.len() <= dimension {
+            feature.resize(dimension + 1, None);
+        }
+        feature[dimension] = values.get(row);
+    }
+    Ok((fatty_acids, features))
+}
+
+/// Root mean square difference in the common dimensions
+fn distance(left: &[Option<f64>], right: &[Option<f64>]) -> f64 {
+    let (sum, count) = left
+        .iter()
+        .zip(right)
+        .filter_map(|(left, right)| Some((*left)? - (*right)?))
+        .fold((0.0, 0), |(sum, count), difference| {
+            (sum + difference * difference, count + 1)
+        });
+    if count == 0 {
+        return f64::INFINITY;
+    }
+    (sum / count as f64).sqrt()
+}
+
+/// Agglomerative clustering with the Lance-Williams distance updates
+fn merges(features: &[Vec<Option<f64>>], linkage: Linkage) -> Vec<Merge> {
+    let len = features.len();
+    let mut distances = vec![vec![0.0; len]; len];
+    for left in 0..len {
+        for right in left + 1..len {
+            let distance = distance(&features[left], &features[right]);
+            distances[left][right] = distance;
+            distances[right][left] = distance;
+        }
+    }
+    // Cluster id and size of the active rows
+    let mut active = (0..len).map(|index| Some((index, 1))).collect::<Vec<_>>();
+    let mut merges = Vec::with_capacity(len.saturating_sub(1));
+    for step in 0..len.saturating_sub(1) {
+        let mut nearest = None;
+        for left in 0..len {
+            if active[left].is_none() {
+                continue;
+            }
+            for right in left + 1..len {
+                if active[right].is_none() {
+                    continue;
+                }
+                if nearest.is_none_or(|(_, _, distance)| distances[left][right] < distance) {
+                    nearest = Some((left, right, distances[left][right]));
+                }
+            }
+        }
+        let Some((left, right, distance)) = nearest else {
+            break;
+        };
+        let (Some((left_id, left_size)), Some((right_id, right_size))) =
+            (active[left], active[right])
+        else {
+            break;
+        };
+        for other in 0..len {
+            if other == left || other == right || active[other].is_none() {
+                continue;
+            }
+            let [to_left, to_right] = [distances[left][other], distances[right][other]];
+            let updated = match linkage {
+                Linkage::Single => f64::min(to_left, to_right),
+                Linkage::Complete => f64::max(to_left, to_right),
+                Linkage::Average => {
+                    (left_size as f64 * to_left + right_size as f64 * to_right)
+                        / (left_size + right_size) as f64
+                }
+            };
+            distances[left][other] = updated;
+            distances[other][left] = updated;
+        }
+        let size = left_size + right_size;
+        active[left] = Some((len + step, size));
+        active[right] = None;
+        merges.push(Merge {
+            left: left_id,
+            right: right_id,
+            distance,
+            size,
+        });
+    }
+    merges
+}
+
+/// Leaf order of the dendrogram
+fn order(len: usize, merges: &[Merge]) -> Vec<usize> {
+    let mut order = Vec::with_capacity(len);
+    let mut stack = match merges.last() {
+        Some(_) => vec![len + merges.len() - 1],
+        None => (0..len).rev().collect(),
+    };
+    while let Some(id) = stack.pop() {
+        if id < len {
+            order.push(id);
+        } else {
+            let merge = merges[id - len];
+            stack.push(merge.right);
+            stack.push(merge.left);
+        }
+    }
+    order
+}
+
+/// Cluster of each leaf, cut into the count of the clusters
+fn clusters(len: usize, merges: &[Merge], order: &[usize], count: usize) -> Vec<u32> {
+    let mut parents = (0..len + merges.len()).collect::<Vec<_>>();
+    let count = count.clamp(1, len.max(1));
+    for (index, merge) in merges.iter().take(len.saturating_sub(count)).enumerate() {
+        parents[merge.left] = len + index;
+        parents[merge.right] = len + index;
+    }
+    let root = |mut id: usize| {
+        while parents[id] != id {
+            id = parents[id];
+        }
+        id
+    };
+    let mut numbers = HashMap::new();
+    let mut clusters = vec![0; len];
+    for &leaf in order {
+        let next = numbers.len() as u32 + 1;
+        clusters[leaf] = *numbers.entry(root(leaf)).or_insert(next);
+    }
+    clusters
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Distances: 0-1 = 1, 0-2 = 5, 0-3 = 12, 1-2 = 4, 1-3 = 11, 2-3 = 7
+    fn features() -> Vec<Vec<Option<f64>>> {
+        [0.0, 1.0, 5.0, 12.0]
+            .into_iter()
+            .map(|value| vec![Some(value)])
+            .collect()
+    }
+
+    fn distances(merges: &[Merge]) -> Vec<f64> {
+        merges.iter().map(|merge| merge.distance).collect()
+    }
+
+    #[test]
+    fn distance_without_common_dimensions() {
+        assert_eq!(
+            distance(&[Some(0.0), None], &[None, Some(1.0)]),
+            f64::INFINITY
+        );
+        assert_eq!(distance(&[Some(0.0), Some(3.0)], &[Some(4.0), None]), 4.0);
+    }
+
+    #[test]
+    fn single() {
+        let merges = merges(&features(), Linkage::Single);
+        assert_eq!(distances(&merges), [1.0, 4.0, 7.0]);
+    }
+
+    #[test]
+    fn complete() {
+        let merges = merges(&features(), Linkage::Complete);
+        assert_eq!(distances(&merges), [1.0, 5.0, 12.0]);
+    }
+
+    #[test]
+    fn average() {
+        let merges = merges(&features(), Linkage::Average);
+        assert_eq!(distances(&merges), [1.0, 4.5, 10.0]);
+        let ids = merges
+            .iter()
+            .map(|merge| (merge.left, merge.right, merge.size))
+            .collect::<Vec<_>>();
+        assert_eq!(ids, [(0, 1, 2), (4, 2, 3), (5, 3, 4)]);
+    }
+
+    #[test]
+    fn order_and_clusters() {
+        let merges = merges(&features(), Linkage::Average);
+        let order = order(4, &merges);
+        assert_eq!(order, [0, 1, 2, 3]);
+        assert_eq!(clusters(4, &merges, &order, 1), [1, 1, 1, 1]);
+        assert_eq!(clusters(4, &merges, &order, 2), [1, 1, 1, 2]);
+        assert_eq!(clusters(4, &merges, &order, 3), [1, 1, 2, 3]);
+        assert_eq!(clusters(4, &merges, &order, 4), [1, 2, 3, 4]);
+        assert_eq!(clusters(4, &merges, &order, 8), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn cut() {
+        let merges = merges(&features(), Linkage::Average);
+        let order = order(4, &merges);
+        let dendrogram = |count| Dendrogram {
+            fatty_acids: Vec::new(),
+            clusters: clusters(4, &merges, &order, count),
+            merges: merges.clone(),
+            order: order.clone(),
+        };
+        assert_eq!(dendrogram(1).cut(), None);
+        assert_eq!(dendrogram(2).cut(), Some(7.25));
+        assert_eq!(dendrogram(3).cut(), Some(2.75));
+        assert_eq!(dendrogram(4).cut(), Some(0.5));
+    }
+}
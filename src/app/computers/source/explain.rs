@@ -0,0 +1,87 @@
+use super::{Saturated, derive};
+use crate::app::panes::source::settings::Settings;
+use egui::{
+    emath::Float as _,
+    util::cache::{ComputerMut, FrameCache},
+};
+use lipid::{polars::expr::FattyAcidExpr, prelude::*};
+use polars::prelude::*;
+use std::hash::{Hash, Hasher};
+
+/// Source explain computed
+pub(crate) type Computed = FrameCache<DataFrame, Computer>;
+
+/// Source explain computer
+///
+/// Evaluates the expressions of the source computer with the intermediate
+/// values: the bracketing saturated fatty acids with their mean retention
+/// times and equivalent chain lengths and the retention time of the relative
+/// fatty acid. The rows are in the order of the source.
+#[derive(Default)]
+pub(crate) struct Computer;
+
+impl Computer {
+    fn try_compute(&mut self, key: Key<'_>) -> PolarsResult<DataFrame> {
+        let saturated = |name: &str, fill: fn(FattyAcidExpr, Expr) -> Expr| {
+            as_struct(vec![
+                fill(col("FattyAcid").fa(), col("FattyAcid")).alias("FattyAcid"),
+                fill(col("FattyAcid").fa(), col("RetentionTimeMean")).alias("RetentionTime"),
+                fill(col("FattyAcid").fa(), col("EquivalentChainLength"))
+                    .alias("EquivalentChainLength"),
+            ])
+            .over(["Column", "Mode"])
+            .alias(name)
+        };
+        derive(key.data_frame, key.settings)
+            .select([
+                col("Column"),
+                col("Mode"),
+                col("FattyAcid"),
+                col("DeadTime"),
+                col("RetentionTime").list().len().alias("Count"),
+                col("RetentionTimeMean"),
+                col("RetentionTimeStandardDeviation"),
+                (col("RetentionTimeMean") / col("RelativeRetentionTime"))
+                    .alias("ReferenceRetentionTime"),
+                col("RelativeRetentionTime"),
+                col("DeltaRetentionTime"),
+                col("Temperature"),
+                col("EquivalentChainLength"),
+                col("FCL"),
+                col("Slope"),
+                // Previous saturated
+                saturated("Previous", Saturated::forward),
+                // Next saturated
+                saturated("Next", Saturated::backward),
+            ])
+            .collect()
+    }
+}
+
+impl ComputerMut<Key<'_>, DataFrame> for Computer {
+    fn compute(&mut self, key: Key<'_>) -> DataFrame {
+        self.try_compute(key).expect("compute source explain")
+    }
+}
+
+/// Source explain key
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Key<'a> {
+    pub(crate) data_frame: &'a DataFrame,
+    pub(crate) hash: u64,
+    pub(crate) settings: &'a Settings,
+}
+
+impl Hash for Key<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+        self.settings.ddof.hash(state);
+        self.settings.logarithmic.hash(state);
+        self.settings.relative.hash(state);
+        self.settings
+            .conditions
+            .maximum_temperature
+            .ord()
+            .hash(state);
+    }
+}
@@ -1,8 +1,13 @@
-use crate::app::{
-    MAX_TEMPERATURE,
-    panes::source::settings::{Filter, Order, Settings, SortBy},
+use self::clustering::Dendrogram;
+use super::filter::{filter, selection, thresholds};
+use crate::{
+    app::panes::source::settings::{Field, Order, Settings, SortBy, SortKeys},
+    special::column::{gc_column, mode::ColumnExt as _},
+};
+use egui::{
+    emath::Float as _,
+    util::cache::{ComputerMut, FrameCache},
 };
-use egui::util::cache::{ComputerMut, FrameCache};
 use lipid::{
     polars::expr::{FattyAcidExpr, fatty_acid::kind::FattyAcidExprExt},
     prelude::*,
@@ -19,105 +24,59 @@ pub(crate) struct Computer;
 
 impl Computer {
     fn try_compute(&mut self, key: Key<'_>) -> PolarsResult<DataFrame> {
-        let mut lazy_frame = key.data_frame.clone().lazy();
-        lazy_frame = lazy_frame
-            .with_columns([
-                // Retention time mean
-                col("RetentionTime")
-                    .list()
-                    .mean()
-                    .alias("RetentionTimeMean"),
-                // Retention time standard deviation
-                col("RetentionTime")
-                    .list()
-                    .std(key.settings.ddof)
-                    .alias("RetentionTimeStandardDeviation"),
+        let mut lazy_frame = derive(key.data_frame, key.settings).select([
+            col("Column"),
+            col("Mode"),
+            col("FattyAcid"),
+            // Retention time
+            as_struct(vec![
+                as_struct(vec![
+                    col("RetentionTimeMean").alias("Mean"),
+                    col("RetentionTimeStandardDeviation").alias("StandardDeviation"),
+                    col("RetentionTime").alias("Values"),
+                ])
+                .alias("Absolute"),
+                col("RelativeRetentionTime").alias("Relative"),
+                col("DeltaRetentionTime").alias("Delta"),
             ])
-            .with_columns([
-                // Relative retention time
-                relative_time(key.settings)
-                    .over(["Mode"])
-                    .alias("RelativeRetentionTime"),
-                // Delta retention time
-                col("FattyAcid")
-                    .fa()
-                    .delta(col("RetentionTimeMean"))
-                    .over(["Mode"])
-                    .alias("DeltaRetentionTime"),
-                // Temperature
-                (col("Mode").struct_().field_by_name("OnsetTemperature")
-                    + col("RetentionTimeMean")
-                        * col("Mode").struct_().field_by_name("TemperatureStep"))
-                .clip_max(lit(MAX_TEMPERATURE))
-                .alias("Temperature"),
-                // FCL
-                col("FattyAcid")
-                    .fa()
-                    .fcl(
-                        col("RetentionTimeMean"),
-                        ChainLengthOptions::new().logarithmic(key.settings.logarithmic),
-                    )
-                    .over(["Mode"])
-                    .alias("FCL"),
-                // ECL
-                col("FattyAcid")
-                    .fa()
-                    .ecl(
-                        col("RetentionTimeMean"),
-                        ChainLengthOptions::new().logarithmic(key.settings.logarithmic),
-                    )
-                    .over(["Mode"])
-                    .alias("EquivalentChainLength"),
-                // ECN
-                col("FattyAcid").fa().ecn().alias("ECN"),
+            .alias("RetentionTime"),
+            // DeadTime
+            col("DeadTime"),
+            // Temperature
+            col("Temperature"),
+            // Chain length
+            as_struct(vec![col("EquivalentChainLength"), col("FCL"), col("ECN")])
+                .alias("ChainLength"),
+            // Mass
+            as_struct(vec![
+                col("FattyAcid").fa().rco().mass(None).alias("RCO"),
+                col("FattyAcid").fa().rcoo().mass(None).alias("RCOO"),
+                col("FattyAcid").fa().rcooh().mass(None).alias("RCOOH"),
+                col("FattyAcid").fa().rcooch3().mass(None).alias("RCOOCH3"),
             ])
-            .with_columns([
-                // Slope
-                col("FattyAcid")
-                    .fa()
-                    .slope(col("EquivalentChainLength"), col("RetentionTimeMean"))
-                    .over(["Mode"])
-                    .alias("Slope"),
+            .alias("Mass"),
+            // Derivative
+            as_struct(vec![
+                col("Slope"),
+                col("Slope").arctan().degrees().alias("Angle"),
             ])
-            .select([
-                col("Mode"),
-                col("FattyAcid"),
-                // Retention time
-                as_struct(vec![
-                    as_struct(vec![
-                        col("RetentionTimeMean").alias("Mean"),
-                        col("RetentionTimeStandardDeviation").alias("StandardDeviation"),
-                        col("RetentionTime").alias("Values"),
-                    ])
-                    .alias("Absolute"),
-                    col("RelativeRetentionTime").alias("Relative"),
-                    col("DeltaRetentionTime").alias("Delta"),
-                ])
-                .alias("RetentionTime"),
-                // DeadTime
-                col("DeadTime"),
-                // Temperature
-                col("Temperature"),
-                // Chain length
-                as_struct(vec![col("EquivalentChainLength"), col("FCL"), col("ECN")])
-                    .alias("ChainLength"),
-                // Mass
-                as_struct(vec![
-                    col("FattyAcid").fa().rco().mass(None).alias("RCO"),
-                    col("FattyAcid").fa().rcoo().mass(None).alias("RCOO"),
-                    col("FattyAcid").fa().rcooh().mass(None).alias("RCOOH"),
-                    col("FattyAcid").fa().rcooch3().mass(None).alias("RCOOCH3"),
-                ])
-                .alias("Mass"),
-                // Derivative
-                as_struct(vec![
-                    col("Slope"),
-                    col("Slope").arctan().degrees().alias("Angle"),
-                ])
-                .alias("Derivative"),
-            ]);
+            .alias("Derivative"),
+        ]);
+        // Cluster
+        if let Some(dendrogram) = key.dendrogram {
+            lazy_frame = lazy_frame.with_column(cluster(dendrogram.clone()).alias("Cluster"));
+            if let Some(predicate) = selection(&key.settings.filter.clusters, |cluster| {
+                col("Cluster").eq(lit(*cluster))
+            }) {
+                lazy_frame = lazy_frame.filter(predicate);
+            }
+        }
         // Filter
-        if let Some(predicate) = filter(&key.settings.filter) {
+        if let Some(predicate) = filter(&key.settings.filter, &[col("FattyAcid")]) {
+            lazy_frame = lazy_frame.filter(predicate);
+        }
+        // Thresholds
+        if let Some(predicate) = thresholds(&key.settings.thresholds, field) {
             lazy_frame = lazy_frame.filter(predicate);
         }
         // Interpolate
@@ -126,9 +85,13 @@ impl Computer {
         if key.settings.order == Order::Descending {
             sort_options = sort_options.with_order_descending(true);
         };
-        lazy_frame = match key.settings.sort {
-            SortBy::FattyAcid => lazy_frame.sort_by_fatty_acids(sort_options),
-            SortBy::Time => lazy_frame.sort_by_time(sort_options),
+        lazy_frame = if !key.settings.sort_keys.is_empty() {
+            lazy_frame.sort_by_keys(&key.settings.sort_keys)
+        } else {
+            match key.settings.sort {
+                SortBy::FattyAcid => lazy_frame.sort_by_fatty_acids(sort_options),
+                SortBy::Time => lazy_frame.sort_by_time(sort_options),
+            }
         };
         lazy_frame.collect()
     }
@@ -144,56 +107,170 @@ impl ComputerMut<Key<'_>, DataFrame> for Computer {
 #[derive(Clone, Copy, Debug)]
 pub struct Key<'a> {
     pub(crate) data_frame: &'a DataFrame,
+    pub(crate) hash: u64,
     pub(crate) settings: &'a Settings,
+    /// Dendrogram of the source clustering computed, if the clustering is
+    /// enabled
+    pub(crate) dendrogram: Option<&'a Dendrogram>,
 }
 
 impl Hash for Key<'_> {
     fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+        self.dendrogram.is_some().hash(state);
         self.settings.view.hash(state);
         self.settings.ddof.hash(state);
         self.settings.logarithmic.hash(state);
         self.settings.relative.hash(state);
         self.settings.filter.hash(state);
+        self.settings.thresholds.hash(state);
         self.settings.sort.hash(state);
         self.settings.order.hash(state);
+        self.settings.sort_keys.hash(state);
+        self.settings.clustering.hash(state);
+        self.settings
+            .conditions
+            .maximum_temperature
+            .ord()
+            .hash(state);
     }
 }
 
-pub(super) fn filter(filter: &Filter) -> Option<Expr> {
-    let mut expr = None;
-    if !filter.onset_temperatures.is_empty() {
-        for &onset_temperature in &filter.onset_temperatures {
-            expr = Some(
-                expr.unwrap_or(lit(true)).and(
-                    col("Mode")
-                        .struct_()
-                        .field_by_name("OnsetTemperature")
-                        .neq(onset_temperature),
-                ),
-            );
-        }
+/// Retention time statistics and the values derived from them, evaluated
+/// over the columns and the modes
+pub(super) fn derive(data_frame: &DataFrame, settings: &Settings) -> LazyFrame {
+    let mut lazy_frame = data_frame.clone().lazy();
+    // Column
+    if data_frame.column("Column").is_err() {
+        lazy_frame = lazy_frame.with_column(gc_column::unnamed());
     }
-    if !filter.temperature_steps.is_empty() {
-        for &temperature_step in &filter.temperature_steps {
-            expr = Some(
-                expr.unwrap_or(lit(true)).and(
-                    col("Mode")
-                        .struct_()
-                        .field_by_name("TemperatureStep")
-                        .neq(temperature_step),
-                ),
-            );
-        }
-    }
-    if !filter.fatty_acids.is_empty() {
-        for fatty_acid in &filter.fatty_acids {
-            expr = Some(
-                expr.unwrap_or(lit(true))
-                    .and(col("FattyAcid").fa().equal(fatty_acid).not()),
-            );
+    lazy_frame
+        .with_columns([
+            // Retention time mean
+            col("RetentionTime")
+                .list()
+                .mean()
+                .alias("RetentionTimeMean"),
+            // Retention time standard deviation
+            col("RetentionTime")
+                .list()
+                .std(settings.ddof)
+                .alias("RetentionTimeStandardDeviation"),
+        ])
+        .with_columns([
+            // Relative retention time
+            relative_time(settings)
+                .over(["Column", "Mode"])
+                .alias("RelativeRetentionTime"),
+            // Delta retention time
+            col("FattyAcid")
+                .fa()
+                .delta(col("RetentionTimeMean"))
+                .over(["Column", "Mode"])
+                .alias("DeltaRetentionTime"),
+            // Temperature
+            temperature(settings.conditions.maximum_temperature).alias("Temperature"),
+            // FCL
+            col("FattyAcid")
+                .fa()
+                .fcl(
+                    col("RetentionTimeMean"),
+                    ChainLengthOptions::new().logarithmic(settings.logarithmic),
+                )
+                .over(["Column", "Mode"])
+                .alias("FCL"),
+            // ECL
+            col("FattyAcid")
+                .fa()
+                .ecl(
+                    col("RetentionTimeMean"),
+                    ChainLengthOptions::new().logarithmic(settings.logarithmic),
+                )
+                .over(["Column", "Mode"])
+                .alias("EquivalentChainLength"),
+            // ECN
+            col("FattyAcid").fa().ecn().alias("ECN"),
+        ])
+        .with_columns([
+            // Slope
+            col("FattyAcid")
+                .fa()
+                .slope(col("EquivalentChainLength"), col("RetentionTimeMean"))
+                .over(["Column", "Mode"])
+                .alias("Slope"),
+        ])
+}
+
+/// Computed field of the selected columns
+pub(crate) fn field(field: Field) -> Expr {
+    let retention_time = || col("RetentionTime").struct_();
+    let absolute = |name| {
+        retention_time()
+            .field_by_name("Absolute")
+            .struct_()
+            .field_by_name(name)
+    };
+    match field {
+        Field::RetentionTime => absolute("Mean"),
+        Field::RelativeRetentionTime => retention_time().field_by_name("Relative"),
+        Field::DeltaRetentionTime => retention_time().field_by_name("Delta"),
+        Field::StandardDeviation => absolute("StandardDeviation"),
+        Field::RelativeStandardDeviation => {
+            absolute("StandardDeviation") / absolute("Mean") * lit(100)
         }
+        Field::Temperature => col("Temperature"),
+        Field::EquivalentChainLength => col("ChainLength")
+            .struct_()
+            .field_by_name("EquivalentChainLength"),
+        Field::FractionalChainLength => col("ChainLength").struct_().field_by_name("FCL"),
+        Field::EquivalentCarbonNumber => col("ChainLength").struct_().field_by_name("ECN"),
+        Field::Mass => col("Mass").struct_().field_by_name("RCOOCH3"),
+        Field::Slope => col("Derivative").struct_().field_by_name("Slope"),
+        Field::Angle => col("Derivative").struct_().field_by_name("Angle"),
     }
-    expr
+}
+
+/// Cluster of the fatty acid in the dendrogram
+fn cluster(dendrogram: Dendrogram) -> Expr {
+    col("FattyAcid").map(
+        move |column| {
+            let fatty_acid = column.fa();
+            let cluster = (0..column.len())
+                .map(|index| {
+                    Ok(fatty_acid
+                        .get(index)?
+                        .and_then(|fatty_acid| dendrogram.cluster(&fatty_acid)))
+                })
+                .collect::<PolarsResult<UInt32Chunked>>()?;
+            Ok(Some(cluster.into_column()))
+        },
+        GetOutput::from_type(DataType::UInt32),
+    )
+}
+
+/// Elution temperature of the temperature program at the mean retention time
+fn temperature(maximum_temperature: f64) -> Expr {
+    as_struct(vec![col("Mode"), col("RetentionTimeMean")]).map(
+        move |column| {
+            let fields = column.struct_()?;
+            let mode = fields.field_by_name("Mode")?.into_column().mode();
+            let retention_time = fields.field_by_name("RetentionTimeMean")?;
+            let temperature = retention_time
+                .f64()?
+                .iter()
+                .enumerate()
+                .map(|(index, time)| {
+                    let Some(time) = time else {
+                        return Ok(None);
+                    };
+                    let program = mode.program(index, maximum_temperature)?;
+                    Ok(program.map(|program| program.temperature(time).min(maximum_temperature)))
+                })
+                .collect::<PolarsResult<Float64Chunked>>()?;
+            Ok(Some(temperature.into_column()))
+        },
+        GetOutput::from_type(DataType::Float64),
+    )
 }
 
 /// Extension methods for [`LazyFrame`]
@@ -201,29 +278,48 @@ trait LazyFrameExt {
     fn sort_by_fatty_acids(self, sort_options: SortMultipleOptions) -> LazyFrame;
 
     fn sort_by_time(self, sort_options: SortMultipleOptions) -> LazyFrame;
+
+    fn sort_by_keys(self, sort_keys: &SortKeys<Field>) -> LazyFrame;
 }
 
 impl LazyFrameExt for LazyFrame {
     fn sort_by_fatty_acids(self, sort_options: SortMultipleOptions) -> LazyFrame {
-        self.sort_by_exprs([col("Mode"), col("FattyAcid")], sort_options)
+        self.sort_by_exprs([col("Column"), col("Mode"), col("FattyAcid")], sort_options)
     }
 
     fn sort_by_time(self, sort_options: SortMultipleOptions) -> LazyFrame {
-        self.sort(["Mode"], sort_options.clone()).select([all()
-            .sort_by(
-                &[
-                    col("ChainLength")
-                        .struct_()
-                        .field_by_name("EquivalentChainLength"),
-                    col("RetentionTime")
-                        .struct_()
-                        .field_by_name("Absolute")
-                        .struct_()
-                        .field_by_name("Mean"),
-                ],
-                sort_options,
-            )
-            .over([col("Mode")])])
+        self.sort(["Column", "Mode"], sort_options.clone())
+            .select([all()
+                .sort_by(
+                    &[
+                        col("ChainLength")
+                            .struct_()
+                            .field_by_name("EquivalentChainLength"),
+                        col("RetentionTime")
+                            .struct_()
+                            .field_by_name("Absolute")
+                            .struct_()
+                            .field_by_name("Mean"),
+                    ],
+                    sort_options,
+                )
+                .over([col("Column"), col("Mode")])])
+    }
+
+    fn sort_by_keys(self, sort_keys: &SortKeys<Field>) -> LazyFrame {
+        let mut by_exprs = vec![col("Column"), col("Mode")];
+        let mut descending = vec![false, false];
+        for key in &sort_keys.keys {
+            by_exprs.push(field(key.field));
+            descending.push(key.order == Order::Descending);
+        }
+        self.sort_by_exprs(
+            by_exprs,
+            SortMultipleOptions::new()
+                .with_order_descending_multi(descending)
+                .with_nulls_last(true)
+                .with_maintain_order(true),
+        )
     }
 }
 
@@ -272,4 +368,7 @@ impl Saturated for FattyAcidExpr {
     }
 }
 
+pub(crate) mod clustering;
+pub(crate) mod explain;
+pub(crate) mod pivot;
 pub(crate) mod plot;
@@ -0,0 +1,247 @@
+use super::field;
+use crate::app::panes::source::settings::Settings;
+use egui::util::cache::{ComputerMut, FrameCache};
+use lipid::{
+    fatty_acid::display::{COMMON, DisplayWithOptions as _},
+    prelude::*,
+};
+use polars::prelude::*;
+use std::{
+    borrow::Cow,
+    fmt::Write as _,
+    hash::{Hash, Hasher},
+};
+
+/// Source pivot computed
+pub(crate) type Computed = FrameCache<Value, Computer>;
+
+/// Source pivot computer
+#[derive(Default)]
+pub(crate) struct Computer;
+
+impl Computer {
+    fn try_compute(&mut self, key: Key<'_>) -> PolarsResult<Value> {
+        let data_frame = key
+            .data_frame
+            .clone()
+            .lazy()
+            .select([
+                col("Column").struct_().field_by_name("Name"),
+                col("Mode").struct_().field_by_name("OnsetTemperature"),
+                col("Mode").struct_().field_by_name("TemperatureStep"),
+                col("FattyAcid"),
+                field(key.settings.pivot).alias("Value"),
+            ])
+            .collect()?;
+        let names = data_frame["Name"].str()?;
+        let onset_temperatures = data_frame["OnsetTemperature"].f64()?;
+        let temperature_steps = data_frame["TemperatureStep"].f64()?;
+        let fatty_acid = data_frame["FattyAcid"].fa();
+        let values = data_frame["Value"].f64()?;
+        // The source is sorted by the columns and the modes, the fatty acids
+        // keep the order of the first mode
+        let mut value = Value::default();
+        for row in 0..data_frame.height() {
+            let (Some(onset_temperature), Some(temperature_step), Some(fatty_acid)) = (
+                onset_temperatures.get(row),
+                temperature_steps.get(row),
+                fatty_acid.get(row)?,
+            ) else {
+                continue;
+            };
+            let mode = Mode {
+                column: names.get(row).map(ToOwned::to_owned),
+                onset_temperature,
+                temperature_step,
+            };
+            let column = match value.modes.iter().position(|target| *target == mode) {
+                Some(column) => column,
+                None => {
+                    value.modes.push(mode);
+                    value.modes.len() - 1
+                }
+            };
+            let index = match value
+                .fatty_acids
+                .iter()
+                .position(|target| *target == fatty_acid)
+            {
+                Some(index) => index,
+                None => {
+                    value.fatty_acids.push(fatty_acid);
+                    value.values.push(Vec::new());
+                    value.values.len() - 1
+                }
+            };
+            let cells = &mut value.values[index];
+            if cells.len() <= column {
+                cells.resize(column + 1, None);
+            }
+            cells[column] = values.get(row);
+        }
+        for cells in &mut value.values {
+            cells.resize(value.modes.len(), None);
+        }
+        Ok(value)
+    }
+}
+
+impl ComputerMut<Key<'_>, Value> for Computer {
+    fn compute(&mut self, key: Key<'_>) -> Value {
+        self.try_compute(key).expect("compute source pivot")
+    }
+}
+
+/// Source pivot key
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Key<'a> {
+    pub(crate) data_frame: &'a DataFrame,
+    pub(crate) hash: u64,
+    pub(crate) settings: &'a Settings,
+}
+
+impl Hash for Key<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+        self.settings.hash(state);
+    }
+}
+
+/// Source pivot value
+///
+/// The fatty acids in the rows and the modes in the columns.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Value {
+    pub(crate) fatty_acids: Vec<FattyAcid>,
+    pub(crate) modes: Vec<Mode>,
+    pub(crate) values: Vec<Vec<Option<f64>>>,
+}
+
+impl Value {
+    /// Ranges of the adjacent modes with the same key, for the header groups
+    pub(crate) fn groups<T: PartialEq>(&self, f: impl Fn(&Mode) -> T) -> Vec<(T, usize, usize)> {
+        let mut groups = Vec::<(T, usize, usize)>::new();
+        for (index, mode) in self.modes.iter().enumerate() {
+            let key = f(mode);
+            match groups.last_mut() {
+                Some((last, _, end)) if *last == key => *end = index + 1,
+                _ => groups.push((key, index, index + 1)),
+            }
+        }
+        groups
+    }
+
+    /// Comma separated values in the same layout as the pivot table
+    ///
+    /// The text fields with the commas, the quotes or the line breaks (the
+    /// column names and the fatty acids with several double bonds) are quoted
+    /// as in RFC 4180.
+    pub(crate) fn csv(&self) -> String {
+        let mut csv = String::new();
+        if self.modes.iter().any(|mode| mode.column.is_some()) {
+            write!(csv, "Column").ok();
+            for mode in &self.modes {
+                write!(
+                    csv,
+                    ",{}",
+                    escape(mode.column.as_deref().unwrap_or_default())
+                )
+                .ok();
+            }
+            writeln!(csv).ok();
+        }
+        write!(csv, "OnsetTemperature").ok();
+        for mode in &self.modes {
+            write!(csv, ",{}", mode.onset_temperature).ok();
+        }
+        writeln!(csv).ok();
+        write!(csv, "TemperatureStep").ok();
+        for mode in &self.modes {
+            write!(csv, ",{}", mode.temperature_step).ok();
+        }
+        writeln!(csv).ok();
+        for (fatty_acid, cells) in self.fatty_acids.iter().zip(&self.values) {
+            write!(
+                csv,
+                "{}",
+                escape(&format!("{:#}", fatty_acid.display(COMMON)))
+            )
+            .ok();
+            for cell in cells {
+                match cell {
+                    Some(value) => write!(csv, ",{value}").ok(),
+                    None => write!(csv, ",").ok(),
+                };
+            }
+            writeln!(csv).ok();
+        }
+        csv
+    }
+}
+
+/// Field of the comma separated values, quoted if needed
+fn escape(text: &str) -> Cow<'_, str> {
+    if text.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", text.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Mode of the pivot column
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Mode {
+    pub(crate) column: Option<String>,
+    pub(crate) onset_temperature: f64,
+    pub(crate) temperature_step: f64,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::special::name::Name;
+
+    fn fatty_acid(name: &str) -> FattyAcid {
+        let series = Name::to_series("FattyAcid".into(), &[Name::parse(name).unwrap()]).unwrap();
+        series.into_column().fa().get(0).unwrap().unwrap()
+    }
+
+    #[test]
+    fn escape_fields() {
+        assert_eq!(escape("18:1Δ9c"), "18:1Δ9c");
+        assert_eq!(escape("18:2Δ9c,12c"), "\"18:2Δ9c,12c\"");
+        assert_eq!(escape("SP-\"2560\""), "\"SP-\"\"2560\"\"\"");
+        assert_eq!(escape("SP\n2560"), "\"SP\n2560\"");
+    }
+
+    #[test]
+    fn csv() {
+        let linoleic = fatty_acid("18:2Δ9c,12c");
+        let value = Value {
+            fatty_acids: vec![fatty_acid("18:1Δ9c"), linoleic.clone()],
+            modes: vec![
+                Mode {
+                    column: Some("SP-2560, 100 m".to_owned()),
+                    onset_temperature: 70.0,
+                    temperature_step: 1.0,
+                },
+                Mode {
+                    column: None,
+                    onset_temperature: 80.0,
+                    temperature_step: 2.0,
+                },
+            ],
+            values: vec![vec![Some(1.5), None], vec![None, Some(2.5)]],
+        };
+        let linoleic = format!("{:#}", linoleic.display(COMMON));
+        assert!(linoleic.contains(','));
+        let csv = value.csv();
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], "Column,\"SP-2560, 100 m\",");
+        assert_eq!(lines[1], "OnsetTemperature,70,80");
+        assert_eq!(lines[2], "TemperatureStep,1,2");
+        assert!(lines[3].ends_with(",1.5,"));
+        assert_eq!(lines[4], format!("\"{linoleic}\",,2.5"));
+    }
+}
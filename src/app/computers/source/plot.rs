@@ -158,6 +158,7 @@ impl Hash for Key<'_> {
         self.settings.ddof.hash(state);
         self.settings.logarithmic.hash(state);
         self.settings.filter.hash(state);
+        self.settings.thresholds.hash(state);
         self.settings.radius_of_points.hash(state);
     }
 }
@@ -9,6 +9,40 @@ pub(crate) struct Data {
 }
 
 impl Data {
+    /// Schema of the default data in the current layout: the fatty acid is
+    /// the carbons with the unsaturated bonds and the onset temperature with
+    /// the temperature step are the mode
+    pub(crate) fn schema() -> Schema {
+        Schema::from_iter([
+            Field::new(
+                "Mode".into(),
+                DataType::Struct(vec![
+                    Field::new("OnsetTemperature".into(), DataType::Float64),
+                    Field::new("TemperatureStep".into(), DataType::Float64),
+                ]),
+            ),
+            Field::new(
+                "FattyAcid".into(),
+                DataType::Struct(vec![
+                    Field::new("Carbons".into(), DataType::UInt8),
+                    Field::new(
+                        "Unsaturated".into(),
+                        DataType::List(Box::new(DataType::Struct(vec![
+                            Field::new("Index".into(), DataType::UInt8),
+                            Field::new("Isomerism".into(), DataType::Int8),
+                            Field::new("Unsaturation".into(), DataType::UInt8),
+                        ]))),
+                    ),
+                ]),
+            ),
+            Field::new(
+                "RetentionTime".into(),
+                DataType::List(Box::new(DataType::Float64)),
+            ),
+            Field::new("DeadTime".into(), DataType::Float64),
+        ])
+    }
+
     pub(crate) fn stack(&mut self, data_frame: &DataFrame) -> Result<()> {
         // If many vstack operations are done, it is recommended to call DataFrame::align_chunks_par
         self.data_frame.vstack_mut(data_frame)?.align_chunks_par();
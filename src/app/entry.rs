@@ -0,0 +1,558 @@
+use crate::{
+    app::data::Data,
+    dead_time::Conditions,
+    presets::AGILENT,
+    special::{
+        column::mode::{Program, Ramp},
+        name::{Bond, Name},
+    },
+};
+use anyhow::{Context as _, Result, ensure};
+use egui::{
+    Button, CollapsingHeader, Color32, ComboBox, DragValue, Grid, Id, ScrollArea, TextEdit, Ui,
+};
+use egui_l20n::{ResponseExt as _, UiExt as _};
+use egui_phosphor::regular::{MINUS, PLUS};
+use metadata::MetaDataFrame;
+use polars::prelude::*;
+use std::fmt::{self, Display, Formatter};
+use tracing::error;
+
+/// Entry
+///
+/// Form of the new dataset: the modes with the temperature programs, the
+/// fatty acids and the replicate retention times of each fatty acid in each
+/// mode.
+#[derive(Clone, Debug)]
+pub(crate) struct Entry {
+    pub(crate) open: bool,
+    name: String,
+    description: String,
+    version: String,
+    modes: Vec<Mode>,
+    fatty_acids: Vec<Name>,
+    picker: Name,
+    /// Texts of the replicate retention times by the fatty acid and the mode
+    retention_times: Vec<Vec<String>>,
+}
+
+impl Entry {
+    pub(crate) fn new() -> Self {
+        Self {
+            open: false,
+            name: String::new(),
+            description: String::new(),
+            version: String::new(),
+            modes: vec![Mode::new(70.0, 1.0)],
+            fatty_acids: Vec::new(),
+            picker: Name {
+                carbons: 18,
+                bonds: Vec::new(),
+            },
+            retention_times: Vec::new(),
+        }
+    }
+
+    pub(crate) fn show(&mut self, ui: &mut Ui) {
+        Grid::new(ui.next_auto_id()).show(ui, |ui| {
+            ui.label(ui.localize("name"));
+            ui.text_edit_singleline(&mut self.name);
+            ui.end_row();
+
+            ui.label(ui.localize("description"));
+            ui.text_edit_multiline(&mut self.description);
+            ui.end_row();
+
+            ui.label(ui.localize("version"));
+            ui.add(TextEdit::singleline(&mut self.version).hint_text("0.1.0"));
+            ui.end_row();
+        });
+        ui.separator();
+        CollapsingHeader::new(ui.localize("modes"))
+            .default_open(true)
+            .show(ui, |ui| self.modes_ui(ui));
+        CollapsingHeader::new(ui.localize("fatty-acids"))
+            .default_open(true)
+            .show(ui, |ui| self.fatty_acids_ui(ui));
+        ui.separator();
+        let errors = self.retention_times_ui(ui);
+        ui.separator();
+        for error in &errors {
+            ui.colored_label(Color32::RED, format!("{error:#}"));
+        }
+        if ui
+            .add_enabled(errors.is_empty(), Button::new(ui.localize("create")))
+            .on_hover_localized("create.hover")
+            .clicked()
+        {
+            match self.frame(ui) {
+                Ok(frame) => {
+                    ui.data_mut(|data| data.insert_temp(Id::new("Source"), frame));
+                    *self = Self::new();
+                }
+                Err(error) => error!(%error),
+            }
+        }
+    }
+
+    fn modes_ui(&mut self, ui: &mut Ui) {
+        let count = self.modes.len();
+        let mut remove = None;
+        for (index, mode) in self.modes.iter_mut().enumerate() {
+            ui.push_id(index, |ui| {
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(count > 1, Button::new(MINUS))
+                        .on_hover_localized("remove-mode")
+                        .clicked()
+                    {
+                        remove = Some(index);
+                    }
+                    ui.label(format!("{} {}", ui.localize("mode"), index + 1));
+                });
+                mode.show(ui);
+            });
+            ui.separator();
+        }
+        if let Some(index) = remove {
+            self.modes.remove(index);
+            for retention_times in &mut self.retention_times {
+                retention_times.remove(index);
+            }
+        }
+        if ui.button(PLUS).on_hover_localized("add-mode").clicked() {
+            let mode = self.modes.last().map_or(Mode::new(70.0, 1.0), |mode| {
+                Mode::new(mode.onset_temperature, mode.temperature_step)
+            });
+            self.modes.push(mode);
+            for retention_times in &mut self.retention_times {
+                retention_times.push(String::new());
+            }
+        }
+    }
+
+    /// Structured picker of the fatty acid and the picked fatty acids
+    fn fatty_acids_ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label(ui.localize("carbons"));
+            ui.add(DragValue::new(&mut self.picker.carbons).range(1..=u8::MAX));
+            ui.label(ui.localize("double-bonds"));
+            let mut count = self.picker.bonds.len();
+            if ui.add(DragValue::new(&mut count).range(0..=16)).changed() {
+                while self.picker.bonds.len() < count {
+                    let index = self.picker.bonds.last().map_or(9, |bond| bond.index + 3);
+                    self.picker.bonds.push(Bond {
+                        index,
+                        isomerism: 1,
+                        unsaturation: 1,
+                    });
+                }
+                self.picker.bonds.truncate(count);
+            }
+        });
+        for (index, bond) in self.picker.bonds.iter_mut().enumerate() {
+            ui.push_id(index, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Δ");
+                    ui.add(DragValue::new(&mut bond.index).range(1..=u8::MAX));
+                    ComboBox::from_id_salt("Isomerism")
+                        .selected_text(ui.localize(isomerism(bond.isomerism)))
+                        .show_ui(ui, |ui| {
+                            for value in [1, -1, 0] {
+                                ui.selectable_value(
+                                    &mut bond.isomerism,
+                                    value,
+                                    ui.localize(isomerism(value)),
+                                );
+                            }
+                        });
+                });
+            });
+        }
+        let picked = Name::parse(&self.picker.to_string());
+        ui.horizontal(|ui| {
+            ui.label(self.picker.to_string());
+            let name = picked
+                .as_ref()
+                .ok()
+                .filter(|name| !self.fatty_acids.contains(name));
+            if ui
+                .add_enabled(name.is_some(), Button::new(PLUS))
+                .on_hover_localized("add-fatty-acid")
+                .clicked()
+            {
+                self.fatty_acids.extend(name.cloned());
+                self.retention_times
+                    .push(vec![String::new(); self.modes.len()]);
+            }
+        });
+        if let Err(error) = picked {
+            ui.colored_label(Color32::RED, format!("{error:#}"));
+        }
+    }
+
+    /// Grid of the replicate retention times, returns the errors
+    fn retention_times_ui(&mut self, ui: &mut Ui) -> Vec<anyhow::Error> {
+        let mut errors = Vec::new();
+        let mut remove = None;
+        ScrollArea::both()
+            .id_salt("RetentionTimes")
+            .max_height(ui.available_height() / 2.0)
+            .show(ui, |ui| {
+                Grid::new(ui.next_auto_id()).striped(true).show(ui, |ui| {
+                    ui.label("");
+                    ui.label(ui.localize("fatty-acid"));
+                    for index in 0..self.modes.len() {
+                        ui.label(format!("{} {}", ui.localize("mode"), index + 1))
+                            .on_hover_text(self.modes[index].to_string());
+                    }
+                    ui.end_row();
+                    for (row, name) in self.fatty_acids.iter().enumerate() {
+                        if ui
+                            .button(MINUS)
+                            .on_hover_localized("remove-fatty-acid")
+                            .clicked()
+                        {
+                            remove = Some(row);
+                        }
+                        ui.label(name.to_string());
+                        for (column, text) in self.retention_times[row].iter_mut().enumerate() {
+                            let result = parse(text).with_context(|| {
+                                format!("{name}, {} {}", ui.localize("mode"), column + 1)
+                            });
+                            let mut text_edit = TextEdit::singleline(text)
+                                .hint_text(ui.localize("replicates.hint"))
+                                .desired_width(ui.spacing().text_edit_width / 2.0);
+                            if result.is_err() {
+                                text_edit = text_edit.text_color(Color32::RED);
+                            }
+                            ui.add(text_edit).on_hover_localized("replicates.hover");
+                            if let Err(error) = result {
+                                errors.push(error);
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+        if let Some(row) = remove {
+            self.fatty_acids.remove(row);
+            self.retention_times.remove(row);
+        }
+        if self
+            .retention_times
+            .iter()
+            .flatten()
+            .all(|text| text.trim().is_empty())
+        {
+            errors.push(anyhow::anyhow!(ui.localize("no-retention-times")));
+        }
+        errors
+    }
+
+    /// Frame of the entered data, a row for each fatty acid with the retention
+    /// times in each mode
+    fn frame(&self, ui: &Ui) -> Result<MetaDataFrame> {
+        let mut modes = Vec::new();
+        let mut names = Vec::new();
+        let mut retention_times = Vec::new();
+        for (name, texts) in self.fatty_acids.iter().zip(&self.retention_times) {
+            for (mode, text) in self.modes.iter().zip(texts) {
+                let values = parse(text)?;
+                if !values.is_empty() {
+                    modes.push(mode);
+                    names.push(name.clone());
+                    retention_times.push(Some(Series::from_iter(values)));
+                }
+            }
+        }
+        let length = names.len();
+        // Mode
+        let mut fields = vec![
+            Series::from_iter(modes.iter().map(|mode| mode.onset_temperature))
+                .with_name("OnsetTemperature".into()),
+            Series::from_iter(modes.iter().map(|mode| mode.temperature_step))
+                .with_name("TemperatureStep".into()),
+        ];
+        if modes
+            .iter()
+            .any(|mode| mode.hold > 0.0 || !mode.ramps.is_empty())
+        {
+            fields.push(
+                Series::from_iter(modes.iter().map(|mode| mode.hold)).with_name("Hold".into()),
+            );
+            fields.push(
+                modes
+                    .iter()
+                    .map(|mode| mode.ramps())
+                    .collect::<PolarsResult<ListChunked>>()?
+                    .with_name("Ramps".into())
+                    .into_series(),
+            );
+        }
+        if modes.iter().any(|mode| mode.flow.is_some()) {
+            fields.push(Series::new(
+                "Flow".into(),
+                modes.iter().map(|mode| mode.flow).collect::<Vec<_>>(),
+            ));
+        }
+        if modes.iter().any(|mode| mode.pressure.is_some()) {
+            fields.push(Series::new(
+                "Pressure".into(),
+                modes.iter().map(|mode| mode.pressure).collect::<Vec<_>>(),
+            ));
+        }
+        let mode = StructChunked::from_series("Mode".into(), length, fields.iter())?.into_series();
+        // Fatty acid
+        let fatty_acid = Name::to_series("FattyAcid".into(), &names)?;
+        // Retention time
+        let retention_time = retention_times
+            .into_iter()
+            .collect::<ListChunked>()
+            .with_name("RetentionTime".into())
+            .into_series();
+        // Dead time
+        let dead_time =
+            Series::from_iter(modes.iter().map(|mode| mode.dead_time)).with_name("DeadTime".into());
+        let schema = Data::schema();
+        let cast = |series: Series| match schema.get(series.name()) {
+            Some(data_type) if series.name() != "Mode" => series.cast(data_type),
+            _ => Ok(series),
+        };
+        let data_frame = DataFrame::new(vec![
+            cast(mode)?.into_column(),
+            cast(fatty_acid)?.into_column(),
+            cast(retention_time)?.into_column(),
+            cast(dead_time)?.into_column(),
+        ])?;
+        let mut meta = AGILENT.meta.clone();
+        meta.name = match self.name.trim() {
+            "" => ui.localize("untitled"),
+            name => name.to_owned(),
+        };
+        meta.description = self.description.trim().to_owned();
+        meta.version = match self.version.trim() {
+            "" => None,
+            version => Some(
+                version
+                    .parse()
+                    .with_context(|| format!("invalid version {version:?}"))?,
+            ),
+        };
+        meta.date = None;
+        Ok(MetaDataFrame::new(meta, data_frame))
+    }
+}
+
+impl Default for Entry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Mode of the form
+#[derive(Clone, Debug)]
+struct Mode {
+    onset_temperature: f64,
+    temperature_step: f64,
+    /// Initial hold (min)
+    hold: f64,
+    /// Ramps, the first one has the temperature step rate
+    ramps: Vec<Ramp>,
+    flow: Option<f64>,
+    pressure: Option<f64>,
+    dead_time: f64,
+}
+
+impl Mode {
+    fn new(onset_temperature: f64, temperature_step: f64) -> Self {
+        Self {
+            onset_temperature,
+            temperature_step,
+            hold: 0.0,
+            ramps: Vec::new(),
+            flow: None,
+            pressure: None,
+            dead_time: Conditions::new().dead_time(onset_temperature),
+        }
+    }
+
+    fn show(&mut self, ui: &mut Ui) {
+        Grid::new(ui.next_auto_id()).show(ui, |ui| {
+            ui.label(ui.localize("onset-temperature"));
+            ui.add(
+                DragValue::new(&mut self.onset_temperature)
+                    .range(0.0..=500.0)
+                    .suffix(" °C"),
+            );
+            ui.end_row();
+
+            ui.label(ui.localize("temperature-step"));
+            ui.add(
+                DragValue::new(&mut self.temperature_step)
+                    .range(0.1..=100.0)
+                    .speed(0.1)
+                    .suffix(" °C/min"),
+            );
+            ui.end_row();
+
+            ui.label(ui.localize("hold"));
+            ui.add(
+                DragValue::new(&mut self.hold)
+                    .range(0.0..=1000.0)
+                    .speed(0.1)
+                    .suffix(" min"),
+            );
+            ui.end_row();
+
+            ui.label(ui.localize("dead-time"));
+            ui.horizontal(|ui| {
+                ui.add(
+                    DragValue::new(&mut self.dead_time)
+                        .range(0.0..=100.0)
+                        .speed(0.01)
+                        .suffix(" min"),
+                );
+                if ui
+                    .button(ui.localize("calculate"))
+                    .on_hover_localized("dead-time-calculator")
+                    .clicked()
+                {
+                    self.dead_time = Conditions::new().dead_time(self.onset_temperature);
+                }
+            });
+            ui.end_row();
+
+            optional(ui, "flow", &mut self.flow, " mL/min");
+            optional(ui, "inlet-pressure", &mut self.pressure, " kPa");
+        });
+        ui.label(ui.localize("temperature-program"));
+        let mut remove = None;
+        for (index, ramp) in self.ramps.iter_mut().enumerate() {
+            ui.push_id(index, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button(MINUS).clicked() {
+                        remove = Some(index);
+                    }
+                    ui.add_enabled(
+                        index > 0,
+                        DragValue::new(&mut ramp.rate)
+                            .range(0.1..=100.0)
+                            .speed(0.1)
+                            .suffix(" °C/min"),
+                    );
+                    ui.label("→");
+                    ui.add(
+                        DragValue::new(&mut ramp.temperature)
+                            .range(0.0..=500.0)
+                            .suffix(" °C"),
+                    );
+                    ui.add(
+                        DragValue::new(&mut ramp.hold)
+                            .range(0.0..=1000.0)
+                            .speed(0.1)
+                            .suffix(" min"),
+                    );
+                });
+            });
+        }
+        if let Some(index) = remove {
+            self.ramps.remove(index);
+        }
+        if ui.button(PLUS).on_hover_localized("add-ramp").clicked() {
+            let ramp = self.ramps.last().copied().unwrap_or(Ramp {
+                rate: self.temperature_step,
+                temperature: 250.0,
+                hold: 0.0,
+            });
+            self.ramps.push(ramp);
+        }
+        ui.label(self.to_string());
+    }
+
+    /// Temperature program, the first ramp has the temperature step rate
+    fn program(&self) -> Program {
+        let mut ramps = self.ramps.clone();
+        if let Some(ramp) = ramps.first_mut() {
+            ramp.rate = self.temperature_step;
+        }
+        Program {
+            onset_temperature: self.onset_temperature,
+            hold: self.hold,
+            ramps,
+            flow: self.flow,
+            pressure: self.pressure,
+        }
+    }
+
+    /// Ramps of the program as the list item
+    fn ramps(&self) -> PolarsResult<Option<Series>> {
+        let ramps = self.program().ramps;
+        Ok(Some(
+            StructChunked::from_series(
+                PlSmallStr::EMPTY,
+                ramps.len(),
+                [
+                    Series::from_iter(ramps.iter().map(|ramp| ramp.rate)).with_name("Rate".into()),
+                    Series::from_iter(ramps.iter().map(|ramp| ramp.temperature))
+                        .with_name("Temperature".into()),
+                    Series::from_iter(ramps.iter().map(|ramp| ramp.hold)).with_name("Hold".into()),
+                ]
+                .iter(),
+            )?
+            .into_series(),
+        ))
+    }
+}
+
+impl Display for Mode {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.ramps.is_empty() {
+            write!(
+                f,
+                "{} °C → {} °C/min",
+                self.onset_temperature, self.temperature_step
+            )
+        } else {
+            Display::fmt(&self.program(), f)
+        }
+    }
+}
+
+/// Optional value with the checkbox
+fn optional(ui: &mut Ui, text: &str, value: &mut Option<f64>, suffix: &str) {
+    let mut checked = value.is_some();
+    if ui.checkbox(&mut checked, ui.localize(text)).changed() {
+        *value = checked.then_some(0.0);
+    }
+    if let Some(value) = value {
+        ui.add(DragValue::new(value).range(0.0..=1000.0).suffix(suffix));
+    }
+    ui.end_row();
+}
+
+fn isomerism(value: i8) -> &'static str {
+    match value.signum() {
+        1 => "cis",
+        -1 => "trans",
+        _ => "unknown-isomerism",
+    }
+}
+
+/// Replicate retention times separated by the whitespaces or the semicolons
+fn parse(text: &str) -> Result<Vec<f64>> {
+    text.split(|char: char| char.is_whitespace() || char == ';')
+        .filter(|value| !value.is_empty())
+        .map(|value| {
+            let retention_time = value
+                .replace(',', ".")
+                .parse::<f64>()
+                .with_context(|| format!("invalid retention time {value:?}"))?;
+            ensure!(
+                retention_time.is_finite() && retention_time > 0.0,
+                "invalid retention time {value:?}"
+            );
+            Ok(retention_time)
+        })
+        .collect()
+}
@@ -1,32 +1,39 @@
-use self::panes::{Pane, behavior::Behavior};
-use crate::{localization::ContextExt as _, presets::AGILENT};
+use self::{
+    entry::Entry,
+    panes::{Pane, behavior::Behavior, properties},
+    paste::Paste,
+    project::Project,
+};
+use crate::{
+    localization::ContextExt as _,
+    presets::AGILENT,
+    utils::{ipc, save_ron},
+};
 use anyhow::Result;
 use data::Data;
 use eframe::{APP_KEY, get_value, set_value};
 use egui::{
-    Align, Align2, CentralPanel, Color32, Context, FontDefinitions, Frame, Grid, Id, Label,
-    LayerId, Layout, Order, RichText, ScrollArea, TextStyle, TopBottomPanel, menu::bar,
+    Align, Align2, CentralPanel, Color32, Context, Event, FontDefinitions, Frame, Id, LayerId,
+    Layout, Order, RichText, ScrollArea, TextStyle, TopBottomPanel, Ui, Window, menu::bar,
     warn_if_debug_build,
 };
 use egui_ext::{DroppedFileExt, HoveredFileExt, LightDarkButton};
-use egui_l20n::{ResponseExt as _, ui::locale_button::LocaleButton};
+use egui_l20n::{ResponseExt as _, UiExt as _, ui::locale_button::LocaleButton};
 use egui_phosphor::{
     Variant, add_to_fonts,
     regular::{
-        ARROWS_CLOCKWISE, DATABASE, GRID_FOUR, ROCKET, SQUARE_SPLIT_HORIZONTAL,
-        SQUARE_SPLIT_VERTICAL, TABS, TRASH,
+        ARROWS_CLOCKWISE, CLIPBOARD_TEXT, DATABASE, EXPORT, GRID_FOUR, NOTE_PENCIL, ROCKET,
+        SQUARE_SPLIT_HORIZONTAL, SQUARE_SPLIT_VERTICAL, TABS, TRASH,
     },
 };
 use egui_tiles::{ContainerKind, Tile, Tree};
 use egui_tiles_ext::{TreeExt as _, VERTICAL};
-use metadata::MetaDataFrame;
 use serde::{Deserialize, Serialize};
-use std::{fmt::Write, io::Cursor, str, time::Duration};
+use std::{fmt::Write, path::Path, str, time::Duration};
 use tracing::{error, info, trace};
 
 /// IEEE 754-2008
 const MAX_PRECISION: usize = 16;
-const MAX_TEMPERATURE: f64 = 250.0;
 const _NOTIFICATIONS_DURATION: Duration = Duration::from_secs(15);
 const ICON_SIZE: f32 = 32.0;
 
@@ -40,6 +47,14 @@ pub struct App {
     // Panes
     tree: Tree<Pane>,
     behavior: Behavior,
+    // Windows
+    #[serde(skip)]
+    paste: Paste,
+    #[serde(skip)]
+    entry: Entry,
+    /// Dropped project waiting for the confirmation
+    #[serde(skip)]
+    project: Option<Project>,
 }
 
 impl Default for App {
@@ -50,6 +65,9 @@ impl Default for App {
             left_panel: true,
             tree: Tree::empty("tree"),
             behavior: Default::default(),
+            paste: Paste::new(),
+            entry: Entry::new(),
+            project: None,
         }
     }
 }
@@ -102,7 +120,27 @@ impl App {
             info!(?dropped_files);
             for dropped_file in dropped_files {
                 if let Err(error) = || -> Result<()> {
-                    let frame = MetaDataFrame::read(Cursor::new(dropped_file.bytes()?))?;
+                    let path = dropped_file
+                        .path
+                        .as_deref()
+                        .unwrap_or(Path::new(&dropped_file.name));
+                    if path
+                        .extension()
+                        .is_some_and(|extension| extension == Project::EXTENSION)
+                    {
+                        let project: Project = ron::de::from_bytes(&dropped_file.bytes()?)?;
+                        trace!(?project.presets);
+                        if self.tree.tiles.is_empty() {
+                            project.merge_presets(&mut self.behavior.presets);
+                            self.tree = project.tree;
+                        } else {
+                            // Asked in the import window
+                            self.project = Some(project);
+                        }
+                        ctx.request_repaint();
+                        return Ok(());
+                    }
+                    let frame = ipc::read(&dropped_file.bytes()?)?;
                     trace!(?frame);
                     self.data.stack(&frame.data)?;
                     ctx.request_repaint();
@@ -193,6 +231,19 @@ impl App {
                 if let Some(id) = self.behavior.close.take() {
                     self.tree.tiles.remove(id);
                 }
+                let mut open = self.paste.open;
+                Window::new(format!("{CLIPBOARD_TEXT} {}", ui.localize("paste")))
+                    .id(Id::new("Paste"))
+                    .open(&mut open)
+                    .show(ui.ctx(), |ui| self.paste.show(ui));
+                self.paste.open &= open;
+                let mut open = self.entry.open;
+                Window::new(format!("{NOTE_PENCIL} {}", ui.localize("entry")))
+                    .id(Id::new("Entry"))
+                    .open(&mut open)
+                    .show(ui.ctx(), |ui| self.entry.show(ui));
+                self.entry.open &= open;
+                self.project_window(ui);
             });
     }
 
@@ -281,37 +332,7 @@ impl App {
                     ui.menu_button(RichText::new(DATABASE).size(ICON_SIZE), |ui| {
                         let mut response = ui
                             .button(RichText::new(format!("{DATABASE} IPPRAS/Agilent")).heading());
-                        response = response.on_hover_ui(|ui| {
-                            let meta = &AGILENT.meta;
-                            Grid::new(ui.next_auto_id()).show(ui, |ui| {
-                                ui.label("Name");
-                                ui.label(&meta.name);
-                                ui.end_row();
-
-                                if !meta.description.is_empty() {
-                                    ui.label("Description");
-                                    ui.add(Label::new(&meta.description).truncate());
-                                    ui.end_row();
-                                }
-
-                                // TODO
-                                // ui.label("Authors");
-                                // ui.label(meta.authors.join(", "));
-                                // ui.end_row();
-
-                                if let Some(version) = &meta.version {
-                                    ui.label("Version");
-                                    ui.label(version.to_string());
-                                    ui.end_row();
-                                }
-
-                                if let Some(date) = &meta.date {
-                                    ui.label("Date");
-                                    ui.label(date.to_string());
-                                    ui.end_row();
-                                }
-                            });
-                        });
+                        response = response.on_hover_ui(|ui| properties::view(ui, &AGILENT.meta));
                         if response.clicked() {
                             self.tree
                                 .insert_pane::<VERTICAL>(Pane::source(AGILENT.clone()));
@@ -320,6 +341,37 @@ impl App {
                     })
                     .response
                     .on_hover_localized("database");
+                    // Paste
+                    ui.toggle_value(
+                        &mut self.paste.open,
+                        RichText::new(CLIPBOARD_TEXT).size(ICON_SIZE),
+                    )
+                    .on_hover_localized("paste")
+                    .on_hover_localized("paste.hover");
+                    // Entry
+                    ui.toggle_value(
+                        &mut self.entry.open,
+                        RichText::new(NOTE_PENCIL).size(ICON_SIZE),
+                    )
+                    .on_hover_localized("entry")
+                    .on_hover_localized("entry.hover");
+                    ui.separator();
+                    // Export project
+                    let name = format!("{}.{}", env!("CARGO_PKG_NAME"), Project::EXTENSION);
+                    if ui
+                        .button(RichText::new(EXPORT).size(ICON_SIZE))
+                        .on_hover_localized("export-project.hover")
+                        .on_hover_text(&name)
+                        .clicked()
+                    {
+                        let project = Project {
+                            tree: self.tree.clone(),
+                            presets: self.behavior.presets.clone(),
+                        };
+                        if let Err(error) = save_ron(&name, &project) {
+                            error!(%error);
+                        }
+                    }
                     ui.separator();
                     // Locale
                     ui.add(LocaleButton::new().size(ICON_SIZE))
@@ -332,11 +384,82 @@ impl App {
 }
 
 impl App {
+    /// Asks whether the dropped project replaces the open panes or is merged
+    /// into them
+    fn project_window(&mut self, ui: &mut Ui) {
+        let Some(project) = self.project.take() else {
+            return;
+        };
+        let mut open = true;
+        Window::new(ui.localize("import-project"))
+            .id(Id::new("Project"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ui.ctx(), |ui| {
+                ui.label(ui.localize("import-project.hover"));
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(ui.localize("replace-panes"))
+                        .on_hover_localized("replace-panes.hover")
+                        .clicked()
+                    {
+                        project.merge_presets(&mut self.behavior.presets);
+                        self.tree = project.tree.clone();
+                        open = false;
+                    }
+                    if ui
+                        .button(ui.localize("merge-panes"))
+                        .on_hover_localized("merge-panes.hover")
+                        .clicked()
+                    {
+                        project.merge_presets(&mut self.behavior.presets);
+                        project.merge_panes(&mut self.tree);
+                        open = false;
+                    }
+                    if ui.button(ui.localize("cancel")).clicked() {
+                        open = false;
+                    }
+                });
+            });
+        if open {
+            self.project = Some(project);
+        }
+    }
+
+    /// Opens the paste window with the tabular data pasted outside of the
+    /// text edits
+    fn paste(&mut self, ctx: &Context) {
+        if ctx.memory(|memory| memory.focused().is_none()) {
+            if let Some(text) = ctx.input(|input| {
+                input.events.iter().find_map(|event| match event {
+                    Event::Paste(text) if text.contains('\t') => Some(text.clone()),
+                    _ => None,
+                })
+            }) {
+                self.paste.text = text;
+                self.paste.open = true;
+            }
+        }
+    }
+
+    fn source(&mut self, ctx: &Context) {
+        if let Some(frame) = ctx.data_mut(|data| data.remove_temp(Id::new("Source"))) {
+            self.tree.insert_pane::<VERTICAL>(Pane::source(frame));
+        }
+    }
+
     fn distance(&mut self, ctx: &Context) {
         if let Some(frame) = ctx.data_mut(|data| data.remove_temp(Id::new("Distance"))) {
             self.tree.insert_pane::<VERTICAL>(Pane::distance(frame));
         }
     }
+
+    fn comparison(&mut self, ctx: &Context) {
+        if let Some(frame) = ctx.data_mut(|data| data.remove_temp(Id::new("Comparison"))) {
+            self.tree.insert_pane::<VERTICAL>(Pane::comparison(frame));
+        }
+    }
 }
 
 impl eframe::App for App {
@@ -347,7 +470,10 @@ impl eframe::App for App {
 
     /// Called each time the UI needs repainting, which may be many times per second.
     fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
+        self.paste(ctx);
+        self.source(ctx);
         self.distance(ctx);
+        self.comparison(ctx);
         self.panels(ctx);
         self.drag_and_drop(ctx);
         if self.reactive {
@@ -358,4 +484,7 @@ impl eframe::App for App {
 
 mod computers;
 mod data;
+mod entry;
 mod panes;
+mod paste;
+mod project;
@@ -1,4 +1,4 @@
-use super::{MARGIN, Pane};
+use super::{MARGIN, Pane, preset::Preset};
 use egui::{
     CentralPanel, RichText, ScrollArea, Sides, TextStyle, TopBottomPanel, Ui, WidgetText, menu::bar,
 };
@@ -11,6 +11,7 @@ use serde::{Deserialize, Serialize};
 pub(crate) struct Behavior {
     pub(crate) close: Option<TileId>,
     pub(crate) click: Option<TileId>,
+    pub(crate) presets: Vec<Preset>,
 }
 
 impl egui_tiles::Behavior<Pane> for Behavior {
@@ -36,6 +37,7 @@ impl egui_tiles::Behavior<Pane> for Behavior {
     // }
 
     fn pane_ui(&mut self, ui: &mut Ui, tile_id: TileId, pane: &mut Pane) -> UiResponse {
+        let Self { close, presets, .. } = self;
         let response = TopBottomPanel::top(ui.auto_id_with("TopPanel"))
             .show_inside(ui, |ui| {
                 bar(ui, |ui| {
@@ -46,11 +48,11 @@ impl egui_tiles::Behavior<Pane> for Behavior {
                                 .height(ui.text_style_height(&TextStyle::Heading) + 4.0 * MARGIN.y)
                                 .show(
                                     ui,
-                                    |ui| pane.header(ui),
+                                    |ui| pane.header(ui, presets),
                                     |ui| {
                                         ui.visuals_mut().button_frame = false;
                                         if ui.button(RichText::new(X).heading()).clicked() {
-                                            self.close = Some(tile_id);
+                                            *close = Some(tile_id);
                                         }
                                     },
                                 )
@@ -0,0 +1,192 @@
+use self::{plot::PlotView, settings::Settings, state::State, table::TableView};
+use super::{source::settings::View, widgets::ViewWidget};
+use crate::{
+    app::computers::{
+        ComparisonComputed, ComparisonKey, ComparisonPlotComputed, ComparisonPlotKey,
+    },
+    special::data_frame::DataFrameExt as _,
+    utils::save,
+};
+use egui::{CursorIcon, Response, RichText, Ui, Window, util::hash};
+use egui_l20n::{ResponseExt as _, UiExt as _};
+use egui_phosphor::regular::{
+    ARROWS_CLOCKWISE, ARROWS_HORIZONTAL, FLOPPY_DISK, GEAR, INFO, SCALES,
+};
+use metadata::MetaDataFrame;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+const ID_SOURCE: &str = "Comparison";
+
+/// Comparison pane
+///
+/// Equivalent chain lengths of the fatty acids on two columns at the matched
+/// modes.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub(crate) struct Pane {
+    source: Source,
+    target: DataFrame,
+    settings: Settings,
+    state: State,
+}
+
+impl Pane {
+    pub(crate) fn new(frame: MetaDataFrame) -> Self {
+        let hash = hash(&frame);
+        let mut settings = Settings::new();
+        if let Some(gc_column) = frame.data.gc_column() {
+            match gc_column.names() {
+                Ok(names) => {
+                    let mut names = names.into_iter();
+                    settings.columns = [names.next(), names.next()];
+                }
+                Err(error) => error!(%error),
+            }
+        }
+        Self {
+            source: Source { frame, hash },
+            target: DataFrame::empty(),
+            settings,
+            state: State::new(),
+        }
+    }
+
+    pub(crate) const fn icon() -> &'static str {
+        SCALES
+    }
+
+    pub(crate) fn title(&self) -> String {
+        self.source.frame.meta.title()
+    }
+
+    pub(super) fn header(&mut self, ui: &mut Ui) -> Response {
+        ui.visuals_mut().button_frame = false;
+        let mut response = ui
+            .heading(Self::icon())
+            .on_hover_text(ui.localize("comparison"));
+        response |= ui.heading(self.title());
+        response = response
+            .on_hover_text(format!("{:x}", self.source.hash))
+            .on_hover_cursor(CursorIcon::Grab);
+        ui.separator();
+        // Reset
+        if ui
+            .button(RichText::new(ARROWS_CLOCKWISE).heading())
+            .clicked()
+        {
+            self.state.reset_table_state = true;
+        }
+        ui.separator();
+        // Resize
+        ui.toggle_value(
+            &mut self.settings.resizable,
+            RichText::new(ARROWS_HORIZONTAL).heading(),
+        )
+        .on_hover_text(ui.localize("resize"));
+        ui.separator();
+        // Settings
+        ui.toggle_value(
+            &mut self.state.open_settings_window,
+            RichText::new(GEAR).heading(),
+        );
+        ui.separator();
+        // View
+        ui.add(ViewWidget::new(&mut self.settings.view));
+        ui.separator();
+        // Metadata
+        ui.toggle_value(
+            &mut self.state.open_metadata_window,
+            RichText::new(INFO).heading(),
+        )
+        .on_hover_localized("metadata");
+        ui.separator();
+        // Save
+        let name = format!("{}.comparison.ipc", self.source.frame.meta.title());
+        if ui
+            .button(RichText::new(FLOPPY_DISK).heading())
+            .on_hover_text(&name)
+            .clicked()
+        {
+            if let Err(error) = save(
+                &name,
+                MetaDataFrame::new(&self.source.frame.meta, &mut self.target),
+            ) {
+                error!(%error);
+            }
+        }
+        ui.separator();
+        response
+    }
+
+    pub(super) fn body(&mut self, ui: &mut Ui) {
+        self.window(ui);
+        self.metadata_window(ui);
+        self.target = ui.memory_mut(|memory| {
+            memory
+                .caches
+                .cache::<ComparisonComputed>()
+                .get(ComparisonKey {
+                    data_frame: &self.source.frame.data,
+                    hash: self.source.hash,
+                    settings: &self.settings,
+                })
+        });
+        match self.settings.view {
+            View::Plot => {
+                let value = ui.memory_mut(|memory| {
+                    memory
+                        .caches
+                        .cache::<ComparisonPlotComputed>()
+                        .get(ComparisonPlotKey {
+                            data_frame: &self.target,
+                            hash: self.source.hash,
+                            settings: &self.settings,
+                        })
+                });
+                PlotView::new(value, &self.settings).show(ui)
+            }
+            View::Heatmap | View::Pivot | View::Table => {
+                TableView::new(&self.target, &self.settings, &mut self.state).show(ui)
+            }
+        };
+    }
+
+    fn window(&mut self, ui: &mut Ui) {
+        Window::new(ui.localize("comparison-settings"))
+            .id(ui.auto_id_with(ID_SOURCE))
+            .open(&mut self.state.open_settings_window)
+            .show(ui.ctx(), |ui| {
+                if let Err(error) = self.settings.show(ui, &self.source.frame.data) {
+                    error!(%error);
+                }
+            });
+    }
+
+    fn metadata_window(&mut self, ui: &mut Ui) {
+        Window::new(ui.localize("metadata"))
+            .id(ui.auto_id_with(ID_SOURCE).with("Metadata"))
+            .open(&mut self.state.open_metadata_window)
+            .show(ui.ctx(), |ui| {
+                if self
+                    .state
+                    .form
+                    .show(ui, &mut self.source.frame.meta, self.source.hash)
+                {
+                    self.source.hash = hash(&self.source.frame);
+                }
+            });
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+struct Source {
+    frame: MetaDataFrame,
+    hash: u64,
+}
+
+pub(crate) mod settings;
+
+mod plot;
+mod state;
+mod table;
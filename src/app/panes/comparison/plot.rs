@@ -0,0 +1,93 @@
+use super::Settings;
+use crate::app::computers::ComparisonPlotValue;
+use egui::{Grid, Ui};
+use egui_ext::color;
+use egui_l20n::UiExt as _;
+use egui_plot::{Legend, Line, LineStyle, MarkerShape, Plot, PlotPoint, Points};
+use lipid::fatty_acid::{
+    FattyAcidExt as _,
+    display::{COMMON, DisplayWithOptions as _},
+};
+use std::fmt::Write;
+
+/// Plot view
+#[derive(Clone)]
+pub(super) struct PlotView<'a> {
+    data: ComparisonPlotValue,
+    settings: &'a Settings,
+}
+
+impl<'a> PlotView<'a> {
+    pub(super) fn new(data: ComparisonPlotValue, settings: &'a Settings) -> Self {
+        Self { data, settings }
+    }
+}
+
+impl PlotView<'_> {
+    pub(super) fn show(self, ui: &mut Ui) {
+        // Candidates
+        ui.collapsing(ui.localize("candidates"), |ui| {
+            Grid::new(ui.next_auto_id()).striped(true).show(ui, |ui| {
+                for candidate in &self.data.candidates {
+                    ui.label(format!("{:#}", (&candidate.fatty_acid).display(COMMON)));
+                    ui.label(format!(
+                        "{:.*}",
+                        self.settings.precision, candidate.distance
+                    ));
+                    ui.end_row();
+                }
+            });
+        });
+        let [a, b] = self.settings.columns.clone().map(Option::unwrap_or_default);
+        let equivalent_chain_length = ui.localize("equivalent-chain-length.abbreviation");
+        let distance = ui.localize("distance");
+        let precision = self.settings.precision;
+        let peak = self.settings.peak;
+        let peak_name = ui.localize("peak");
+        let mut plot = Plot::new("plot")
+            .data_aspect(1.0)
+            .x_axis_label(format!("{equivalent_chain_length} {a}"))
+            .y_axis_label(format!("{equivalent_chain_length} {b}"))
+            .label_formatter(move |name, &PlotPoint { x, y }| {
+                let mut label = String::new();
+                if !name.is_empty() {
+                    writeln!(&mut label, "{name}").ok();
+                }
+                writeln!(&mut label, "{a} = {x:.precision$}").ok();
+                writeln!(&mut label, "{b} = {y:.precision$}").ok();
+                let d = ((x - peak.a).powi(2) + (y - peak.b).powi(2)).sqrt();
+                writeln!(&mut label, "{distance} = {d:.precision$}").ok();
+                label
+            });
+        if self.settings.legend {
+            plot = plot.legend(Legend::default().follow_insertion_order(true));
+        }
+        plot.show(ui, |ui| {
+            // Fatty acids
+            for (index, point) in self.data.points.iter().enumerate() {
+                let mut points = Points::new(point.coordinates)
+                    .name(format!("{:#}", (&point.fatty_acid).display(COMMON)))
+                    .color(color(index as _))
+                    .radius(self.settings.radius_of_points);
+                if point.fatty_acid.is_saturated() {
+                    points = points.shape(MarkerShape::Square);
+                }
+                ui.points(points);
+            }
+            // Peak
+            let peak = [self.settings.peak.a, self.settings.peak.b];
+            for candidate in &self.data.candidates {
+                ui.line(
+                    Line::new(vec![peak, candidate.coordinates])
+                        .style(LineStyle::Dotted { spacing: 8.0 }),
+                );
+            }
+            ui.points(
+                Points::new(peak)
+                    .name(peak_name)
+                    .shape(MarkerShape::Diamond)
+                    .radius(self.settings.radius_of_points as f32 * 2.0),
+            );
+        });
+    }
+}
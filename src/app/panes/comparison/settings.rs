@@ -0,0 +1,253 @@
+use super::table::LEN;
+use crate::{
+    app::{
+        MAX_PRECISION,
+        panes::source::settings::{Filter, View},
+    },
+    special::data_frame::DataFrameExt as _,
+};
+use egui::{ComboBox, DragValue, Grid, RichText, Slider, Ui, emath::Float as _};
+use egui_ext::LabeledSeparator;
+use egui_l20n::{ResponseExt as _, UiExt as _};
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher},
+};
+
+/// Settings
+#[derive(Clone, Debug, Deserialize, Hash, PartialEq, Serialize)]
+pub(crate) struct Settings {
+    pub(crate) precision: usize,
+    pub(crate) resizable: bool,
+    pub(crate) sticky: usize,
+    pub(crate) truncate: bool,
+
+    /// Names of the compared columns A and B
+    pub(crate) columns: [Option<String>; 2],
+    pub(crate) filter: Filter,
+
+    pub(crate) view: View,
+    pub(crate) legend: bool,
+    pub(crate) radius_of_points: u8,
+    /// Mode of the plot, the first one if none
+    pub(crate) mode: Option<Mode>,
+    pub(crate) peak: Peak,
+    /// Number of the nearest candidates of the peak
+    pub(crate) candidates: usize,
+}
+
+impl Settings {
+    pub(crate) fn new() -> Self {
+        Self {
+            precision: 2,
+            resizable: false,
+            sticky: 0,
+            truncate: false,
+            columns: [None, None],
+            filter: Filter::new(),
+
+            view: View::Table,
+            legend: true,
+            radius_of_points: 4,
+            mode: None,
+            peak: Peak::new(),
+            candidates: 5,
+        }
+    }
+
+    pub(crate) fn show(&mut self, ui: &mut Ui, data_frame: &DataFrame) -> PolarsResult<()> {
+        Grid::new("Comparison")
+            .show(ui, |ui| {
+                // Precision floats
+                ui.label(ui.localize("precision"));
+                ui.add(Slider::new(&mut self.precision, 0..=MAX_PRECISION));
+                ui.end_row();
+
+                // Sticky columns
+                ui.label(ui.localize("sticky"));
+                ui.add(Slider::new(&mut self.sticky, 0..=LEN));
+                ui.end_row();
+
+                // Truncate titles
+                ui.label(ui.localize("truncate"));
+                ui.checkbox(&mut self.truncate, "");
+                ui.end_row();
+
+                // Columns
+                ui.separator();
+                ui.labeled_separator(RichText::new(ui.localize("columns")).heading());
+                ui.end_row();
+
+                let names = match data_frame.gc_column() {
+                    Some(gc_column) => gc_column.names()?,
+                    None => Vec::new(),
+                };
+                for (column, text) in self.columns.iter_mut().zip(["column-a", "column-b"]) {
+                    ui.label(ui.localize(text))
+                        .on_hover_localized(&format!("{text}.hover"));
+                    ComboBox::from_id_salt(ui.next_auto_id())
+                        .selected_text(column.clone().unwrap_or_default())
+                        .show_ui(ui, |ui| {
+                            for name in &names {
+                                ui.selectable_value(column, Some(name.clone()), name);
+                            }
+                        });
+                    ui.end_row();
+                }
+
+                // Filter
+                ui.separator();
+                ui.labeled_separator(RichText::new(ui.localize("filter")).heading());
+                ui.end_row();
+
+                self.filter.show_within_column(ui, data_frame)?;
+                ui.end_row();
+
+                if let View::Plot = self.view {
+                    // Plot
+                    ui.separator();
+                    ui.labeled_separator(RichText::new("Plot").heading());
+                    ui.end_row();
+
+                    // Legend
+                    ui.label(ui.localize("legend"));
+                    ui.checkbox(&mut self.legend, "");
+                    ui.end_row();
+
+                    // Radius of points
+                    ui.label(ui.localize("radius-of-points"))
+                        .on_hover_localized("radius-of-points.hover");
+                    ui.add(Slider::new(&mut self.radius_of_points, 0..=u8::MAX).logarithmic(true));
+                    ui.end_row();
+
+                    // Mode
+                    ui.label(ui.localize("mode"))
+                        .on_hover_localized("mode.hover");
+                    let modes = modes(data_frame)?;
+                    let selected_text = self.mode.or(modes.first().copied());
+                    ComboBox::from_id_salt(ui.next_auto_id())
+                        .selected_text(
+                            selected_text
+                                .map(|mode| mode.to_string())
+                                .unwrap_or_default(),
+                        )
+                        .show_ui(ui, |ui| {
+                            for mode in modes {
+                                ui.selectable_value(&mut self.mode, Some(mode), mode.to_string());
+                            }
+                        });
+                    ui.end_row();
+
+                    // Identification
+                    ui.separator();
+                    ui.labeled_separator(RichText::new(ui.localize("identification")).heading());
+                    ui.end_row();
+
+                    // Peak
+                    ui.label(ui.localize("peak"))
+                        .on_hover_localized("peak.hover");
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            DragValue::new(&mut self.peak.a)
+                                .range(0.0..=f64::MAX)
+                                .speed(0.01)
+                                .prefix("A: "),
+                        );
+                        ui.add(
+                            DragValue::new(&mut self.peak.b)
+                                .range(0.0..=f64::MAX)
+                                .speed(0.01)
+                                .prefix("B: "),
+                        );
+                    });
+                    ui.end_row();
+
+                    // Candidates
+                    ui.label(ui.localize("candidates"))
+                        .on_hover_localized("candidates.hover");
+                    ui.add(Slider::new(&mut self.candidates, 0..=20));
+                    ui.end_row();
+                }
+                Ok(())
+            })
+            .inner
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Mode of the plot
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub(crate) struct Mode {
+    pub(crate) onset_temperature: f64,
+    pub(crate) temperature_step: f64,
+}
+
+impl Display for Mode {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} °C, {} °C/min",
+            self.onset_temperature, self.temperature_step
+        )
+    }
+}
+
+impl Hash for Mode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.onset_temperature.ord().hash(state);
+        self.temperature_step.ord().hash(state);
+    }
+}
+
+/// Peak equivalent chain lengths on the columns A and B
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub(crate) struct Peak {
+    pub(crate) a: f64,
+    pub(crate) b: f64,
+}
+
+impl Peak {
+    pub(crate) fn new() -> Self {
+        Self { a: 18.0, b: 18.0 }
+    }
+}
+
+impl Hash for Peak {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.a.ord().hash(state);
+        self.b.ord().hash(state);
+    }
+}
+
+/// Unique sorted modes
+pub(crate) fn modes(data_frame: &DataFrame) -> PolarsResult<Vec<Mode>> {
+    let data_frame = data_frame
+        .clone()
+        .lazy()
+        .select([
+            col("Mode").struct_().field_by_name("OnsetTemperature"),
+            col("Mode").struct_().field_by_name("TemperatureStep"),
+        ])
+        .unique(None, UniqueKeepStrategy::First)
+        .sort_by_exprs(
+            [col("OnsetTemperature"), col("TemperatureStep")],
+            Default::default(),
+        )
+        .collect()?;
+    Ok(data_frame["OnsetTemperature"]
+        .f64()?
+        .into_no_null_iter()
+        .zip(data_frame["TemperatureStep"].f64()?.into_no_null_iter())
+        .map(|(onset_temperature, temperature_step)| Mode {
+            onset_temperature,
+            temperature_step,
+        })
+        .collect())
+}
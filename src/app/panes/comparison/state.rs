@@ -0,0 +1,21 @@
+use crate::app::panes::properties::Form;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub(crate) struct State {
+    pub(crate) form: Form,
+    pub(crate) open_metadata_window: bool,
+    pub(crate) open_settings_window: bool,
+    pub(crate) reset_table_state: bool,
+}
+
+impl State {
+    pub(crate) fn new() -> Self {
+        Self {
+            form: Form::new(),
+            open_metadata_window: false,
+            open_settings_window: false,
+            reset_table_state: false,
+        }
+    }
+}
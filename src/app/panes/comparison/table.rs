@@ -0,0 +1,214 @@
+use super::{ID_SOURCE, Settings, State};
+use crate::app::panes::{MARGIN, widgets::float::FloatValue};
+use egui::{Frame, Id, Margin, TextStyle, TextWrapMode, Ui};
+use egui_l20n::{ResponseExt as _, UiExt as _};
+use egui_phosphor::regular::HASH;
+use egui_table::{
+    AutoSizeMode, CellInfo, Column, HeaderCellInfo, HeaderRow, Table, TableDelegate, TableState,
+};
+use lipid::{
+    fatty_acid::display::{COMMON, DisplayWithOptions as _},
+    prelude::*,
+};
+use polars::prelude::*;
+use std::ops::Range;
+
+const INDEX: Range<usize> = 0..1;
+const MODE: Range<usize> = INDEX.end..INDEX.end + 2;
+const FATTY_ACID: Range<usize> = MODE.end..MODE.end + 1;
+const ECL: Range<usize> = FATTY_ACID.end..FATTY_ACID.end + 3;
+pub(super) const LEN: usize = ECL.end;
+
+const TOP: &[Range<usize>] = &[INDEX, MODE, FATTY_ACID, ECL];
+
+/// Table view
+#[derive(Debug)]
+pub(super) struct TableView<'a> {
+    data_frame: &'a DataFrame,
+    settings: &'a Settings,
+    state: &'a mut State,
+}
+
+impl<'a> TableView<'a> {
+    pub(super) const fn new(
+        data_frame: &'a DataFrame,
+        settings: &'a Settings,
+        state: &'a mut State,
+    ) -> Self {
+        Self {
+            data_frame,
+            settings,
+            state,
+        }
+    }
+}
+
+impl TableView<'_> {
+    pub(super) fn show(&mut self, ui: &mut Ui) {
+        let id_salt = Id::new(ID_SOURCE).with("Table");
+        if self.state.reset_table_state {
+            let id = TableState::id(ui, Id::new(id_salt));
+            TableState::reset(ui.ctx(), id);
+            self.state.reset_table_state = false;
+        }
+        let height = ui.text_style_height(&TextStyle::Heading) + 2.0 * MARGIN.y;
+        let num_rows = self.data_frame.height() as _;
+        let num_columns = LEN;
+        Table::new()
+            .id_salt(id_salt)
+            .num_rows(num_rows)
+            .columns(vec![
+                Column::default().resizable(self.settings.resizable);
+                num_columns
+            ])
+            .num_sticky_cols(self.settings.sticky)
+            .headers([
+                HeaderRow {
+                    height,
+                    groups: TOP.to_vec(),
+                },
+                HeaderRow::new(height),
+            ])
+            .auto_size_mode(AutoSizeMode::OnParentResize)
+            .show(ui, self);
+    }
+
+    fn header_cell_content_ui(&mut self, ui: &mut Ui, row: usize, column: Range<usize>) {
+        if self.settings.truncate {
+            ui.style_mut().wrap_mode = Some(TextWrapMode::Truncate);
+        }
+        match (row, column) {
+            // Top
+            (0, INDEX) => {
+                ui.heading(HASH).on_hover_localized("index");
+            }
+            (0, MODE) => {
+                ui.heading(ui.localize("mode"))
+                    .on_hover_localized("mode.hover");
+            }
+            (0, FATTY_ACID) => {
+                ui.heading(ui.localize("fatty-acid"))
+                    .on_hover_localized("fatty-acid.abbreviation");
+            }
+            (0, ECL) => {
+                ui.heading(ui.localize("equivalent-chain-length.abbreviation"))
+                    .on_hover_localized("equivalent-chain-length");
+            }
+            // Bottom
+            (1, mode::ONSET) => {
+                ui.heading(ui.localize("onset-temperature.abbreviation"))
+                    .on_hover_localized("onset-temperature");
+            }
+            (1, mode::STEP) => {
+                ui.heading(ui.localize("temperature-step.abbreviation"))
+                    .on_hover_localized("temperature-step")
+                    .on_hover_localized("temperature-step.hover");
+            }
+            (1, ecl::A) => {
+                let text = self.settings.columns[0].clone().unwrap_or_default();
+                ui.heading(&text).on_hover_localized("column-a");
+            }
+            (1, ecl::B) => {
+                let text = self.settings.columns[1].clone().unwrap_or_default();
+                ui.heading(&text).on_hover_localized("column-b");
+            }
+            (1, ecl::DELTA) => {
+                ui.heading(ui.localize("equivalent-chain-length-difference.abbreviation"))
+                    .on_hover_localized("equivalent-chain-length-difference")
+                    .on_hover_localized("equivalent-chain-length-difference.hover");
+            }
+            _ => {}
+        }
+    }
+
+    fn body_cell_content_ui(
+        &mut self,
+        ui: &mut Ui,
+        row: usize,
+        column: Range<usize>,
+    ) -> PolarsResult<()> {
+        match (row, column) {
+            (row, INDEX) => {
+                ui.label(row.to_string());
+            }
+            (row, mode::ONSET) => {
+                let mode = self.data_frame["Mode"].struct_()?;
+                let onset_temperature = mode.field_by_name("OnsetTemperature")?;
+                let response = ui.label(onset_temperature.str_value(row)?);
+                // Temperature program
+                if let Some(program) = mode.field_by_name("Program")?.str()?.get(row) {
+                    if !program.is_empty() {
+                        response.on_hover_text(program);
+                    }
+                }
+            }
+            (row, mode::STEP) => {
+                let mode = self.data_frame["Mode"].struct_()?;
+                let temperature_step = mode.field_by_name("TemperatureStep")?;
+                ui.label(temperature_step.str_value(row)?);
+            }
+            (row, FATTY_ACID) => {
+                let fatty_acids = self.data_frame["FattyAcid"].fa();
+                let Some(fatty_acid) = fatty_acids.get(row)? else {
+                    polars_bail!(NoData: "FattyAcid[{row}]");
+                };
+                let text = format!("{:#}", fatty_acid.display(COMMON));
+                ui.label(&text).on_hover_text(&text);
+            }
+            (row, ecl::A) => self.ecl_cell_content_ui(ui, row, "A")?,
+            (row, ecl::B) => self.ecl_cell_content_ui(ui, row, "B")?,
+            (row, ecl::DELTA) => self.ecl_cell_content_ui(ui, row, "Delta")?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn ecl_cell_content_ui(&mut self, ui: &mut Ui, row: usize, name: &str) -> PolarsResult<()> {
+        let ecl = self.data_frame["EquivalentChainLength"].struct_()?;
+        let value = ecl.field_by_name(name)?;
+        ui.add(
+            FloatValue::new(value.f64()?.get(row))
+                .precision(Some(self.settings.precision))
+                .hover(),
+        );
+        Ok(())
+    }
+}
+
+impl TableDelegate for TableView<'_> {
+    fn header_cell_ui(&mut self, ui: &mut Ui, cell: &HeaderCellInfo) {
+        Frame::new()
+            .inner_margin(Margin::from(MARGIN))
+            .show(ui, |ui| {
+                self.header_cell_content_ui(ui, cell.row_nr, cell.col_range.clone())
+            });
+    }
+
+    fn cell_ui(&mut self, ui: &mut Ui, cell: &CellInfo) {
+        if cell.row_nr % 2 == 0 {
+            ui.painter()
+                .rect_filled(ui.max_rect(), 0.0, ui.visuals().faint_bg_color);
+        }
+        Frame::new()
+            .inner_margin(Margin::from(MARGIN))
+            .show(ui, |ui| {
+                self.body_cell_content_ui(ui, cell.row_nr as _, cell.col_nr..cell.col_nr + 1)
+                    .unwrap()
+            });
+    }
+}
+
+mod mode {
+    use super::*;
+
+    pub(super) const ONSET: Range<usize> = MODE.start..MODE.start + 1;
+    pub(super) const STEP: Range<usize> = ONSET.end..ONSET.end + 1;
+}
+
+mod ecl {
+    use super::*;
+
+    pub(super) const A: Range<usize> = ECL.start..ECL.start + 1;
+    pub(super) const B: Range<usize> = A.end..A.end + 1;
+    pub(super) const DELTA: Range<usize> = B.end..B.end + 1;
+}
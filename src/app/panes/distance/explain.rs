@@ -0,0 +1,76 @@
+use crate::app::panes::explain::{Explanation, Key, find, value};
+use egui::Ui;
+use lipid::{
+    fatty_acid::display::{COMMON, DisplayWithOptions as _},
+    prelude::*,
+};
+use polars::prelude::*;
+
+/// Explanation of the alpha in the row with the key
+pub(super) fn show(
+    ui: &mut Ui,
+    data_frame: &DataFrame,
+    key: &Key,
+    precision: usize,
+) -> PolarsResult<()> {
+    if let Some(row) = find(data_frame, key)? {
+        explanation(data_frame, row, precision)?.show(ui);
+    }
+    Ok(())
+}
+
+fn explanation(data_frame: &DataFrame, row: usize, precision: usize) -> PolarsResult<Explanation> {
+    let float = |series: &Series| -> PolarsResult<Option<f64>> {
+        Ok(series.cast(&DataType::Float64)?.f64()?.get(row))
+    };
+    let field = |name: &str, field: &str| -> PolarsResult<Series> {
+        data_frame[name].struct_()?.field_by_name(field)
+    };
+    let fatty_acid = |name: &str| -> PolarsResult<String> {
+        Ok(match field("FattyAcid", name)?.fa().get(row)? {
+            Some(fatty_acid) => format!("{:#}", fatty_acid.display(COMMON)),
+            None => "—".to_owned(),
+        })
+    };
+    let dead_time = float(data_frame["DeadTime"].as_materialized_series())?;
+    let from = float(&field("RetentionTime", "From")?)?;
+    let to = float(&field("RetentionTime", "To")?)?;
+    let adjusted = |time: Option<f64>| Some(time? - dead_time?);
+    let mut explanation = Explanation::new(Some("α = (t₁ − t₀) / (t₂ − t₀)"));
+    explanation
+        .entry("column", "", data_frame["Column"].str_value(row)?)
+        .entry(
+            "onset-temperature",
+            "T₀",
+            value(float(&field("Mode", "OnsetTemperature")?)?, precision),
+        )
+        .entry(
+            "temperature-step",
+            "β",
+            value(float(&field("Mode", "TemperatureStep")?)?, precision),
+        )
+        .entry("from", "", fatty_acid("From")?)
+        .entry("to", "", fatty_acid("To")?)
+        .entry("dead-time", "t₀", value(dead_time, precision))
+        .entry("retention-time", "t₁", value(from, precision))
+        .entry("retention-time", "t₂", value(to, precision))
+        .entry(
+            "adjusted-retention-time",
+            "t₁ − t₀",
+            value(adjusted(from), precision),
+        )
+        .entry(
+            "adjusted-retention-time",
+            "t₂ − t₀",
+            value(adjusted(to), precision),
+        )
+        .entry(
+            "alpha",
+            "α",
+            value(
+                float(data_frame["Alpha"].as_materialized_series())?,
+                precision,
+            ),
+        );
+    Ok(explanation)
+}
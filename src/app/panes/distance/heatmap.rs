@@ -0,0 +1,134 @@
+use super::settings::Heatmap;
+use crate::{
+    app::computers::{DistanceHeatmapValue, distance::heatmap::intensity},
+    localization::Text as _,
+};
+use egui::{
+    Align2, Color32, Grid, Pos2, Rect, ScrollArea, Sense, Stroke, StrokeKind, TextStyle, Ui, Vec2,
+    epaint::TextShape, vec2,
+};
+use egui_l20n::UiExt as _;
+use lipid::fatty_acid::display::{COMMON, DisplayWithOptions as _};
+use std::f32::consts::FRAC_PI_2;
+
+/// Heatmap view
+pub(crate) struct HeatmapView<'a> {
+    value: DistanceHeatmapValue,
+    settings: &'a Heatmap,
+}
+
+impl<'a> HeatmapView<'a> {
+    pub(crate) fn new(value: DistanceHeatmapValue, settings: &'a Heatmap) -> Self {
+        Self { value, settings }
+    }
+}
+
+impl HeatmapView<'_> {
+    pub(super) fn show(self, ui: &mut Ui) {
+        let names = self
+            .value
+            .fatty_acids
+            .iter()
+            .map(|fatty_acid| format!("{:#}", fatty_acid.display(COMMON)))
+            .collect::<Vec<_>>();
+        let len = names.len();
+        let font_id = TextStyle::Small.resolve(ui.style());
+        let margin = names
+            .iter()
+            .map(|name| {
+                ui.painter()
+                    .layout_no_wrap(name.clone(), font_id.clone(), Color32::PLACEHOLDER)
+                    .size()
+                    .x
+            })
+            .fold(0.0, f32::max)
+            + ui.spacing().item_spacing.x;
+        let cell = self.settings.cell_size as f32;
+        ScrollArea::both().show(ui, |ui| {
+            let size = Vec2::splat(margin + len as f32 * cell);
+            let (rect, response) = ui.allocate_exact_size(size, Sense::hover());
+            let painter = ui.painter_at(rect);
+            let origin = rect.min + Vec2::splat(margin);
+            let text_color = ui.visuals().text_color();
+            // Labels
+            for (index, name) in names.iter().enumerate() {
+                let center = index as f32 * cell + cell / 2.0;
+                painter.text(
+                    Pos2::new(
+                        origin.x - ui.spacing().item_spacing.x / 2.0,
+                        origin.y + center,
+                    ),
+                    Align2::RIGHT_CENTER,
+                    name,
+                    font_id.clone(),
+                    text_color,
+                );
+                let galley = painter.layout_no_wrap(name.clone(), font_id.clone(), text_color);
+                let position = Pos2::new(
+                    origin.x + center - galley.size().y / 2.0,
+                    origin.y - ui.spacing().item_spacing.x / 2.0,
+                );
+                painter.add(TextShape::new(position, galley, text_color).with_angle(-FRAC_PI_2));
+            }
+            // Cells
+            let range = self.value.range.unwrap_or([0.0, 1.0]);
+            for row in 0..len {
+                for column in 0..len {
+                    let Some(value) = self.value.cells[row * len + column] else {
+                        continue;
+                    };
+                    let cell_rect = Rect::from_min_size(
+                        origin + vec2(column as f32 * cell, row as f32 * cell),
+                        Vec2::splat(cell),
+                    );
+                    painter.rect_filled(cell_rect, 0.0, self.color(value, range));
+                }
+            }
+            painter.rect_stroke(
+                Rect::from_min_size(origin, Vec2::splat(len as f32 * cell)),
+                0.0,
+                Stroke::new(1.0, ui.visuals().weak_text_color()),
+                StrokeKind::Outside,
+            );
+            // Hover
+            if let Some(pointer) = response.hover_pos() {
+                let position = (pointer - origin) / cell;
+                if position.x >= 0.0 && position.y >= 0.0 {
+                    let [row, column] = [position.y as usize, position.x as usize];
+                    if row < len && column < len {
+                        response.on_hover_ui_at_pointer(|ui| {
+                            Grid::new(ui.next_auto_id()).show(ui, |ui| {
+                                ui.label(ui.localize("from"));
+                                ui.label(&names[row]);
+                                ui.end_row();
+
+                                ui.label(ui.localize("to"));
+                                ui.label(&names[column]);
+                                ui.end_row();
+
+                                ui.label(ui.localize(self.settings.field.text()));
+                                match self.value.cells[row * len + column] {
+                                    Some(value) => ui.label(value.to_string()),
+                                    None => ui.label("-"),
+                                };
+                            });
+                        });
+                    }
+                }
+            }
+        });
+    }
+
+    fn color(&self, value: f64, [min, max]: [f64; 2]) -> Color32 {
+        let intensity = intensity(self.settings.field, value);
+        let mut normalized = if max > min {
+            (intensity - min) / (max - min)
+        } else {
+            0.0
+        };
+        if self.settings.reverse {
+            normalized = 1.0 - normalized;
+        }
+        self.settings.color_scale.color(normalized)
+    }
+}
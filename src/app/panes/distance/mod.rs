@@ -1,15 +1,30 @@
-use self::{plot::PlotView, settings::Settings, state::State, table::TableView};
-use super::{source::settings::View, widgets::ViewWidget};
+use self::{
+    heatmap::HeatmapView,
+    plot::PlotView,
+    settings::{Field, Settings},
+    state::State,
+    table::{Cells, TableView},
+};
+use super::{
+    formatting::Styles,
+    preset::Preset,
+    source::settings::View,
+    widgets::{PresetsWidget, ViewWidget},
+};
 use crate::{
     app::computers::{
-        DistanceComputed, DistanceFilteredComputed, DistanceFilteredKey, DistanceKey,
-        DistancePlotComputed, DistancePlotKey,
+        DistanceComputed, DistanceFilteredComputed, DistanceFilteredKey, DistanceHeatmapComputed,
+        DistanceHeatmapKey, DistanceKey, DistancePlotComputed, DistancePlotKey, FormattingComputed,
+        FormattingKey, GroupingComputed, GroupingKey, distance::filtered::field, formatting,
+        grouping,
     },
     utils::save,
 };
 use egui::{CursorIcon, Response, RichText, Ui, Window, util::hash};
-use egui_l20n::UiExt as _;
-use egui_phosphor::regular::{ARROWS_CLOCKWISE, ARROWS_HORIZONTAL, EXCLUDE, FLOPPY_DISK, GEAR};
+use egui_l20n::{ResponseExt as _, UiExt as _};
+use egui_phosphor::regular::{
+    ARROWS_CLOCKWISE, ARROWS_HORIZONTAL, EXCLUDE, FLOPPY_DISK, GEAR, INFO,
+};
 use metadata::MetaDataFrame;
 use polars::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -45,7 +60,7 @@ impl Pane {
         self.source.frame.meta.title()
     }
 
-    pub(super) fn header(&mut self, ui: &mut Ui) -> Response {
+    pub(super) fn header(&mut self, ui: &mut Ui, presets: &mut Vec<Preset>) -> Response {
         ui.visuals_mut().button_frame = false;
         let mut response = ui
             .heading(Self::icon())
@@ -75,9 +90,27 @@ impl Pane {
             &mut self.state.open_settings_window,
             RichText::new(GEAR).heading(),
         );
+        // Presets
+        ui.add(PresetsWidget::new(presets, &mut self.settings));
         ui.separator();
         // View
-        ui.add(ViewWidget::new(&mut self.settings.view));
+        ui.add(ViewWidget::new(&mut self.settings.view).views(&[
+            View::Table,
+            View::Plot,
+            View::Heatmap,
+        ]));
+        ui.separator();
+        // Search
+        if matches!(self.settings.view, View::Pivot | View::Table) {
+            self.state.search.show(ui);
+            ui.separator();
+        }
+        // Metadata
+        ui.toggle_value(
+            &mut self.state.open_metadata_window,
+            RichText::new(INFO).heading(),
+        )
+        .on_hover_localized("metadata");
         ui.separator();
         // Save
         let name = format!("{}.distance.ipc", self.source.frame.meta.title());
@@ -99,10 +132,12 @@ impl Pane {
 
     pub(super) fn body(&mut self, ui: &mut Ui) {
         self.window(ui);
+        self.metadata_window(ui);
         self.target = ui.memory_mut(|memory| {
             memory.caches.cache::<DistanceComputed>().get(DistanceKey {
                 data_frame: &self.source.frame.data,
                 hash: self.source.hash,
+                pairing: self.settings.pairing,
             })
         });
         // Filtered
@@ -112,10 +147,24 @@ impl Pane {
                 .cache::<DistanceFilteredComputed>()
                 .get(DistanceFilteredKey {
                     data_frame: &self.target,
+                    hash: self.source.hash,
                     settings: &self.settings,
                 })
         });
         match self.settings.view {
+            View::Heatmap => {
+                let value = ui.memory_mut(|memory| {
+                    memory
+                        .caches
+                        .cache::<DistanceHeatmapComputed>()
+                        .get(DistanceHeatmapKey {
+                            data_frame: &data_frame,
+                            hash: self.source.hash,
+                            settings: &self.settings,
+                        })
+                });
+                HeatmapView::new(value, &self.settings.heatmap).show(ui)
+            }
             View::Plot => {
                 let points = ui.memory_mut(|memory| {
                     memory
@@ -123,12 +172,70 @@ impl Pane {
                         .cache::<DistancePlotComputed>()
                         .get(DistancePlotKey {
                             data_frame: &data_frame,
+                            hash: self.source.hash,
                             settings: &self.settings,
                         })
                 });
                 PlotView::new(points, &self.settings.plot).show(ui)
             }
-            View::Table => TableView::new(&data_frame, &self.settings, &mut self.state).show(ui),
+            View::Pivot | View::Table => {
+                let cells = Cells {
+                    data_frame: &data_frame,
+                    precision: self.settings.precision,
+                };
+                let data_frame = match self.state.search.apply(
+                    ui,
+                    &data_frame,
+                    &cells,
+                    hash((self.source.hash, &self.settings)),
+                ) {
+                    Ok(data_frame) => data_frame,
+                    Err(error) => {
+                        error!(%error);
+                        data_frame.clone()
+                    }
+                };
+                let styles = ui.memory_mut(|memory| {
+                    memory
+                        .caches
+                        .cache::<FormattingComputed<Field>>()
+                        .get(FormattingKey {
+                            data_frame: &data_frame,
+                            hash: hash((
+                                self.source.hash,
+                                &self.settings,
+                                self.state.search.filtered(),
+                            )),
+                            formatting: &self.settings.formatting,
+                            field,
+                            saturated: formatting::pair,
+                        })
+                });
+                let table = hash((
+                    self.source.hash,
+                    &self.settings,
+                    self.state.search.filtered(),
+                ));
+                let groups = ui.memory_mut(|memory| {
+                    memory.caches.cache::<GroupingComputed>().get(GroupingKey {
+                        data_frame: &data_frame,
+                        hash: table,
+                        grouping: self.settings.grouping,
+                        summary: grouping::pair,
+                    })
+                });
+                let rules = self.settings.formatting.rules.clone();
+                TableView::new(
+                    &data_frame,
+                    &mut self.settings,
+                    &mut self.state,
+                    Styles::new(&styles, &rules),
+                    &groups,
+                    table,
+                )
+                .show(ui);
+                self.explain_window(ui, &data_frame);
+            }
         };
     }
 
@@ -140,6 +247,43 @@ impl Pane {
                 self.settings.show(ui, &self.source.frame.data);
             });
     }
+
+    fn metadata_window(&mut self, ui: &mut Ui) {
+        Window::new(ui.localize("metadata"))
+            .id(ui.auto_id_with(ID_SOURCE).with("Metadata"))
+            .open(&mut self.state.open_metadata_window)
+            .show(ui.ctx(), |ui| {
+                if self
+                    .state
+                    .form
+                    .show(ui, &mut self.source.frame.meta, self.source.hash)
+                {
+                    self.source.hash = hash(&self.source.frame);
+                }
+            });
+    }
+
+    fn explain_window(&mut self, ui: &mut Ui, data_frame: &DataFrame) {
+        let Some(key) = &self.state.explain else {
+            return;
+        };
+        let mut open = true;
+        Window::new(format!(
+            "{} {}",
+            ui.localize("explain"),
+            ui.localize("alpha")
+        ))
+        .id(ui.auto_id_with(ID_SOURCE).with("Explain"))
+        .open(&mut open)
+        .show(ui.ctx(), |ui| {
+            if let Err(error) = explain::show(ui, data_frame, key, self.settings.precision) {
+                error!(%error);
+            }
+        });
+        if !open {
+            self.state.explain = None;
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -150,6 +294,8 @@ struct Source {
 
 pub(crate) mod settings;
 
+mod explain;
+mod heatmap;
 mod plot;
 mod state;
 mod table;
@@ -2,15 +2,24 @@ use super::table::LEN;
 use crate::{
     app::{
         MAX_PRECISION,
-        panes::source::settings::{Axis, Filter, Order, PlotSettings, View},
+        panes::{
+            comparison::settings::{Mode, modes},
+            source::settings::{
+                Axis, Filter, Formatting, Grouping, Order, PlotSettings, SortKeys, Target,
+                Threshold, View,
+            },
+            widgets::{FormattingWidget, ThresholdsWidget},
+        },
     },
     localization::Text,
+    special::data_frame::DataFrameExt as _,
 };
-use egui::{ComboBox, Grid, RichText, Slider, Ui};
+use egui::{Color32, ComboBox, DragValue, Grid, RichText, Slider, Ui, lerp};
 use egui_ext::LabeledSeparator;
 use egui_l20n::{ResponseExt, UiExt as _};
 use polars::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::mem::discriminant;
 
 /// Settings
 #[derive(Clone, Debug, Deserialize, Hash, PartialEq, Serialize)]
@@ -19,12 +28,20 @@ pub(crate) struct Settings {
     pub(crate) resizable: bool,
     pub(crate) sticky: usize,
     pub(crate) truncate: bool,
+    pub(crate) grouping: Grouping,
 
+    /// Number of the theoretical plates of the column, used by the resolution
+    pub(crate) plates: u32,
+    pub(crate) pairing: Pairing,
     pub(crate) sort: Sort,
+    pub(crate) sort_keys: SortKeys<Field>,
     pub(crate) filter: Filter,
+    pub(crate) thresholds: Vec<Threshold<Field>>,
+    pub(crate) formatting: Formatting<Field>,
 
     pub(crate) view: View,
     pub(crate) plot: PlotSettings,
+    pub(crate) heatmap: Heatmap,
 }
 
 impl Settings {
@@ -34,10 +51,17 @@ impl Settings {
             resizable: false,
             sticky: 0,
             truncate: false,
+            grouping: Grouping::None,
+            plates: 100_000,
+            pairing: Pairing::All,
             sort: Sort::new(),
+            sort_keys: SortKeys::new(),
             filter: Filter::new(),
+            thresholds: Vec::new(),
+            formatting: Formatting::new(),
             view: View::Table,
             plot: PlotSettings::new(),
+            heatmap: Heatmap::new(),
         }
     }
 
@@ -58,6 +82,29 @@ impl Settings {
             ui.checkbox(&mut self.truncate, "");
             ui.end_row();
 
+            // Grouping
+            ui.label(ui.localize("grouping"))
+                .on_hover_localized("grouping.hover");
+            self.grouping
+                .show(ui, &[Grouping::None, Grouping::Mode, Grouping::Pair]);
+            ui.end_row();
+
+            // Pairing
+            ui.label(ui.localize("pairing"))
+                .on_hover_localized("pairing.hover");
+            self.pairing.show(ui);
+            ui.end_row();
+
+            // Theoretical plates
+            ui.label(ui.localize("theoretical-plates"))
+                .on_hover_localized("theoretical-plates.hover");
+            ui.add(
+                DragValue::new(&mut self.plates)
+                    .range(1..=u32::MAX)
+                    .speed(1000),
+            );
+            ui.end_row();
+
             // Filter
             ui.separator();
             ui.labeled_separator(RichText::new(ui.localize("filter")).heading());
@@ -66,14 +113,69 @@ impl Settings {
             self.filter.show(ui, data_frame)?;
             ui.end_row();
 
+            // Structure target
+            ui.label(ui.localize("structure-target"))
+                .on_hover_localized("structure-target.hover");
+            ComboBox::from_id_salt(ui.next_auto_id())
+                .selected_text(ui.localize(self.filter.structure.target.text()))
+                .show_ui(ui, |ui| {
+                    for target in Target::VARIANTS {
+                        ui.selectable_value(
+                            &mut self.filter.structure.target,
+                            target,
+                            ui.localize(target.text()),
+                        )
+                        .on_hover_localized(target.hover_text());
+                    }
+                })
+                .response
+                .on_hover_localized(self.filter.structure.target.hover_text());
+            ui.end_row();
+
+            // Thresholds
+            ui.label(ui.localize("thresholds"))
+                .on_hover_localized("thresholds.hover");
+            ui.add(ThresholdsWidget::new(
+                &mut self.thresholds,
+                &Field::VARIANTS,
+            ));
+            ui.end_row();
+
+            // Formatting
+            ui.separator();
+            ui.labeled_separator(RichText::new(ui.localize("formatting")).heading());
+            ui.end_row();
+
+            ui.label(ui.localize("formatting"))
+                .on_hover_localized("formatting.hover");
+            ui.add(FormattingWidget::new(
+                &mut self.formatting,
+                &Field::VARIANTS,
+            ));
+            ui.end_row();
+
             // Sort
             ui.separator();
             ui.labeled_separator(RichText::new(ui.localize("sort-by-distance")).heading());
             ui.end_row();
 
-            self.sort.show(ui);
+            self.sort.show(ui, data_frame)?;
             ui.end_row();
 
+            // Sort keys
+            ui.label(ui.localize("sort-keys"))
+                .on_hover_localized("sort-keys.hover");
+            self.sort_keys.show(ui);
+            ui.end_row();
+
+            if let View::Heatmap = self.view {
+                // Heatmap
+                ui.separator();
+                ui.labeled_separator(RichText::new(ui.localize("heatmap-view")).heading());
+                ui.end_row();
+
+                self.heatmap.show(ui, data_frame)?;
+            }
             if let View::Plot = self.view {
                 // Plot
                 ui.separator();
@@ -134,11 +236,279 @@ impl Default for Settings {
     }
 }
 
+/// Heatmap settings
+#[derive(Clone, Debug, Deserialize, Hash, PartialEq, Serialize)]
+pub(crate) struct Heatmap {
+    pub(crate) field: Field,
+    /// Column of the heatmap, the first one if none
+    pub(crate) column: Option<String>,
+    /// Mode of the heatmap, the first one if none
+    pub(crate) mode: Option<Mode>,
+    pub(crate) color_scale: ColorScale,
+    pub(crate) reverse: bool,
+    /// Size of the cells in points
+    pub(crate) cell_size: u8,
+}
+
+impl Heatmap {
+    fn new() -> Self {
+        Self {
+            field: Field::Alpha,
+            column: None,
+            mode: None,
+            color_scale: ColorScale::Viridis,
+            reverse: false,
+            cell_size: 16,
+        }
+    }
+
+    fn show(&mut self, ui: &mut Ui, data_frame: &DataFrame) -> PolarsResult<()> {
+        // Field
+        ui.label(ui.localize("heatmap-field"))
+            .on_hover_localized("heatmap-field.hover");
+        ComboBox::from_id_salt(ui.next_auto_id())
+            .selected_text(ui.localize(self.field.text()))
+            .show_ui(ui, |ui| {
+                for field in Field::VARIANTS {
+                    ui.selectable_value(&mut self.field, field, ui.localize(field.text()))
+                        .on_hover_localized(field.hover_text());
+                }
+            })
+            .response
+            .on_hover_localized(self.field.hover_text());
+        ui.end_row();
+
+        // Column
+        if let Some(gc_column) = data_frame.gc_column() {
+            ui.label(ui.localize("column"))
+                .on_hover_localized("column.hover");
+            let names = gc_column.names()?;
+            let selected_text = self.column.as_ref().or(names.first());
+            ComboBox::from_id_salt(ui.next_auto_id())
+                .selected_text(selected_text.cloned().unwrap_or_default())
+                .show_ui(ui, |ui| {
+                    for name in &names {
+                        ui.selectable_value(&mut self.column, Some(name.clone()), name);
+                    }
+                });
+            ui.end_row();
+        }
+
+        // Mode
+        ui.label(ui.localize("mode"))
+            .on_hover_localized("mode.hover");
+        let modes = modes(data_frame)?;
+        let selected_text = self.mode.or(modes.first().copied());
+        ComboBox::from_id_salt(ui.next_auto_id())
+            .selected_text(
+                selected_text
+                    .map(|mode| mode.to_string())
+                    .unwrap_or_default(),
+            )
+            .show_ui(ui, |ui| {
+                for mode in modes {
+                    ui.selectable_value(&mut self.mode, Some(mode), mode.to_string());
+                }
+            });
+        ui.end_row();
+
+        // Color scale
+        ui.label(ui.localize("color-scale"))
+            .on_hover_localized("color-scale.hover");
+        ui.horizontal(|ui| {
+            ComboBox::from_id_salt(ui.next_auto_id())
+                .selected_text(ui.localize(self.color_scale.text()))
+                .show_ui(ui, |ui| {
+                    for color_scale in ColorScale::VARIANTS {
+                        ui.selectable_value(
+                            &mut self.color_scale,
+                            color_scale,
+                            ui.localize(color_scale.text()),
+                        );
+                    }
+                });
+            ui.checkbox(&mut self.reverse, ui.localize("reverse"))
+                .on_hover_localized("reverse.hover");
+        });
+        ui.end_row();
+
+        // Cell size
+        ui.label(ui.localize("cell-size"));
+        ui.add(Slider::new(&mut self.cell_size, 4..=64));
+        ui.end_row();
+        Ok(())
+    }
+}
+
+/// Color scale
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub(crate) enum ColorScale {
+    Grayscale,
+    Magma,
+    #[default]
+    Viridis,
+}
+
+impl ColorScale {
+    pub(crate) const VARIANTS: [Self; 3] = [Self::Viridis, Self::Magma, Self::Grayscale];
+
+    /// Color of the value in 0..=1
+    pub(crate) fn color(&self, value: f64) -> Color32 {
+        const GRAYSCALE: [[u8; 3]; 2] = [[0, 0, 0], [255, 255, 255]];
+        const MAGMA: [[u8; 3]; 5] = [
+            [0, 0, 4],
+            [81, 18, 124],
+            [183, 55, 121],
+            [252, 137, 97],
+            [252, 253, 191],
+        ];
+        const VIRIDIS: [[u8; 3]; 5] = [
+            [68, 1, 84],
+            [59, 82, 139],
+            [33, 145, 140],
+            [94, 201, 98],
+            [253, 231, 37],
+        ];
+        let stops: &[[u8; 3]] = match self {
+            Self::Grayscale => &GRAYSCALE,
+            Self::Magma => &MAGMA,
+            Self::Viridis => &VIRIDIS,
+        };
+        let position = value.clamp(0.0, 1.0) * (stops.len() - 1) as f64;
+        let index = (position.floor() as usize).min(stops.len() - 2);
+        let t = (position - index as f64) as f32;
+        let [from, to] = [stops[index], stops[index + 1]];
+        let channel =
+            |channel: usize| lerp(from[channel] as f32..=to[channel] as f32, t).round() as u8;
+        Color32::from_rgb(channel(0), channel(1), channel(2))
+    }
+}
+
+impl Text for ColorScale {
+    fn text(&self) -> &'static str {
+        match self {
+            Self::Grayscale => "grayscale",
+            Self::Magma => "magma",
+            Self::Viridis => "viridis",
+        }
+    }
+
+    fn hover_text(&self) -> &'static str {
+        self.text()
+    }
+}
+
+/// Pair selection strategy
+#[derive(Clone, Copy, Debug, Default, Deserialize, Hash, PartialEq, Serialize)]
+pub(crate) enum Pairing {
+    /// All pairs within the column and mode
+    #[default]
+    All,
+    /// Neighbours in the elution order
+    Adjacent,
+    /// The nearest pairs of each fatty acid by the metric
+    Nearest { count: usize, metric: Metric },
+}
+
+impl Pairing {
+    fn show(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ComboBox::from_id_salt(ui.next_auto_id())
+                .selected_text(ui.localize(self.text()))
+                .show_ui(ui, |ui| {
+                    for pairing in [
+                        Self::All,
+                        Self::Adjacent,
+                        Self::Nearest {
+                            count: 3,
+                            metric: Metric::RetentionTime,
+                        },
+                    ] {
+                        let selected = discriminant(self) == discriminant(&pairing);
+                        if ui
+                            .selectable_label(selected, ui.localize(pairing.text()))
+                            .on_hover_localized(pairing.hover_text())
+                            .clicked()
+                            && !selected
+                        {
+                            *self = pairing;
+                        }
+                    }
+                })
+                .response
+                .on_hover_localized(self.hover_text());
+            if let Self::Nearest { count, metric } = self {
+                ui.add(DragValue::new(count).range(1..=usize::MAX))
+                    .on_hover_localized("nearest-count.hover");
+                ComboBox::from_id_salt(ui.next_auto_id())
+                    .selected_text(ui.localize(metric.text()))
+                    .show_ui(ui, |ui| {
+                        for variant in Metric::VARIANTS {
+                            ui.selectable_value(metric, variant, ui.localize(variant.text()))
+                                .on_hover_localized(variant.hover_text());
+                        }
+                    })
+                    .response
+                    .on_hover_localized(metric.hover_text());
+            }
+        });
+    }
+}
+
+impl Text for Pairing {
+    fn text(&self) -> &'static str {
+        match self {
+            Self::All => "pairing-all",
+            Self::Adjacent => "pairing-adjacent",
+            Self::Nearest { .. } => "pairing-nearest",
+        }
+    }
+
+    fn hover_text(&self) -> &'static str {
+        match self {
+            Self::All => "pairing-all.hover",
+            Self::Adjacent => "pairing-adjacent.hover",
+            Self::Nearest { .. } => "pairing-nearest.hover",
+        }
+    }
+}
+
+/// Pairing metric
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub(crate) enum Metric {
+    #[default]
+    RetentionTime,
+    EquivalentChainLength,
+}
+
+impl Metric {
+    pub(crate) const VARIANTS: [Self; 2] = [Self::RetentionTime, Self::EquivalentChainLength];
+}
+
+impl Text for Metric {
+    fn text(&self) -> &'static str {
+        match self {
+            Self::RetentionTime => "retention-time",
+            Self::EquivalentChainLength => "equivalent-chain-length",
+        }
+    }
+
+    fn hover_text(&self) -> &'static str {
+        match self {
+            Self::RetentionTime => "retention-time.abbreviation",
+            Self::EquivalentChainLength => "equivalent-chain-length.abbreviation",
+        }
+    }
+}
+
 /// Sort
 #[derive(Clone, Copy, Debug, Deserialize, Hash, PartialEq, Serialize)]
 pub(crate) struct Sort {
     pub(crate) aggregation: Aggregation,
     pub(crate) by: SortBy,
+    pub(crate) field: Field,
+    /// Mode of the value to sort, the first one if none
+    pub(crate) mode: Option<Mode>,
     pub(crate) order: Order,
 }
 
@@ -147,11 +517,13 @@ impl Sort {
         Self {
             aggregation: Aggregation::Maximum,
             by: SortBy::Value,
+            field: Field::Alpha,
+            mode: None,
             order: Order::Descending,
         }
     }
 
-    fn show(&mut self, ui: &mut Ui) {
+    fn show(&mut self, ui: &mut Ui, data_frame: &DataFrame) -> PolarsResult<()> {
         ui.label(ui.localize("sort-by-distance"))
             .on_hover_localized("sort-by-distance.hover");
         ComboBox::from_id_salt(ui.next_auto_id())
@@ -170,32 +542,41 @@ impl Sort {
             .on_hover_localized(self.by.hover_text());
         ui.end_row();
 
+        let enabled = self.by == SortBy::Value;
+        // Field
+        ui.label(ui.localize("sort-by-field"))
+            .on_hover_localized("sort-by-field.hover");
+        ui.add_enabled_ui(enabled, |ui| {
+            ComboBox::from_id_salt(ui.next_auto_id())
+                .selected_text(ui.localize(self.field.text()))
+                .show_ui(ui, |ui| {
+                    for field in Field::VARIANTS {
+                        ui.selectable_value(&mut self.field, field, ui.localize(field.text()))
+                            .on_hover_localized(field.hover_text());
+                    }
+                })
+                .response
+                .on_hover_localized(self.field.hover_text());
+        })
+        .response
+        .on_disabled_hover_text("Used only for sort by value");
+        ui.end_row();
+
         // Aggregation
         ui.label(ui.localize("sort-by-aggregation"))
             .on_hover_localized("sort-by-aggregation.hover");
-        let enabled = self.by == SortBy::Value;
         ui.add_enabled_ui(enabled, |ui| {
             ComboBox::from_id_salt(ui.next_auto_id())
                 .selected_text(ui.localize(self.aggregation.text()))
                 .show_ui(ui, |ui| {
-                    ui.selectable_value(
-                        &mut self.aggregation,
-                        Aggregation::Maximum,
-                        ui.localize(Aggregation::Maximum.text()),
-                    )
-                    .on_hover_localized(Aggregation::Maximum.hover_text());
-                    ui.selectable_value(
-                        &mut self.aggregation,
-                        Aggregation::Median,
-                        ui.localize(Aggregation::Median.text()),
-                    )
-                    .on_hover_localized(Aggregation::Median.hover_text());
-                    ui.selectable_value(
-                        &mut self.aggregation,
-                        Aggregation::Minimum,
-                        ui.localize(Aggregation::Minimum.text()),
-                    )
-                    .on_hover_localized(Aggregation::Minimum.hover_text());
+                    for aggregation in Aggregation::VARIANTS {
+                        ui.selectable_value(
+                            &mut self.aggregation,
+                            aggregation,
+                            ui.localize(aggregation.text()),
+                        )
+                        .on_hover_localized(aggregation.hover_text());
+                    }
                 })
                 .response
                 .on_hover_localized(self.aggregation.hover_text());
@@ -204,6 +585,28 @@ impl Sort {
         .on_disabled_hover_text("Used only for sort by value");
         ui.end_row();
 
+        // Mode
+        if self.aggregation == Aggregation::Mode {
+            ui.label(ui.localize("mode"))
+                .on_hover_localized("mode.hover");
+            let modes = modes(data_frame)?;
+            let selected_text = self.mode.or(modes.first().copied());
+            ui.add_enabled_ui(enabled, |ui| {
+                ComboBox::from_id_salt(ui.next_auto_id())
+                    .selected_text(
+                        selected_text
+                            .map(|mode| mode.to_string())
+                            .unwrap_or_default(),
+                    )
+                    .show_ui(ui, |ui| {
+                        for mode in modes {
+                            ui.selectable_value(&mut self.mode, Some(mode), mode.to_string());
+                        }
+                    });
+            });
+            ui.end_row();
+        }
+
         // Order
         ui.label(ui.localize("order"));
         ComboBox::from_id_salt(ui.next_auto_id())
@@ -224,6 +627,7 @@ impl Sort {
             })
             .response
             .on_hover_localized(self.order.hover_text());
+        Ok(())
     }
 }
 
@@ -255,24 +659,83 @@ impl Text for SortBy {
 pub(crate) enum Aggregation {
     #[default]
     Maximum,
+    Mean,
     Median,
     Minimum,
+    Mode,
+}
+
+impl Aggregation {
+    pub(crate) const VARIANTS: [Self; 5] = [
+        Self::Maximum,
+        Self::Mean,
+        Self::Median,
+        Self::Minimum,
+        Self::Mode,
+    ];
 }
 
 impl Text for Aggregation {
     fn text(&self) -> &'static str {
         match self {
             Self::Maximum => "sort-by-maximum",
+            Self::Mean => "sort-by-mean",
             Self::Median => "sort-by-median",
             Self::Minimum => "sort-by-minimum",
+            Self::Mode => "sort-by-mode",
         }
     }
 
     fn hover_text(&self) -> &'static str {
         match self {
             Self::Maximum => "sort-by-maximum.hover",
+            Self::Mean => "sort-by-mean.hover",
             Self::Median => "sort-by-median.hover",
             Self::Minimum => "sort-by-minimum.hover",
+            Self::Mode => "sort-by-mode.hover",
+        }
+    }
+}
+
+/// Distance computed field
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub(crate) enum Field {
+    #[default]
+    Alpha,
+    RetentionTime,
+    EquivalentChainLength,
+    EuclideanDistance,
+    Resolution,
+}
+
+impl Field {
+    pub(crate) const VARIANTS: [Self; 5] = [
+        Self::Alpha,
+        Self::RetentionTime,
+        Self::EquivalentChainLength,
+        Self::EuclideanDistance,
+        Self::Resolution,
+    ];
+}
+
+impl Text for Field {
+    fn text(&self) -> &'static str {
+        match self {
+            Self::Alpha => "alpha",
+            Self::RetentionTime => "retention-time-distance",
+            Self::EquivalentChainLength => "equivalent-chain-length-distance",
+            Self::EuclideanDistance => "euclidean-distance",
+            Self::Resolution => "resolution",
+        }
+    }
+
+    fn hover_text(&self) -> &'static str {
+        match self {
+            Self::Alpha => "alpha.hover",
+            Self::RetentionTime => "retention-time-distance.hover",
+            Self::EquivalentChainLength => "equivalent-chain-length-distance.hover",
+            Self::EuclideanDistance => "euclidean-distance.hover",
+            Self::Resolution => "resolution.hover",
         }
     }
 }
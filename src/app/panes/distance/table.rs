@@ -1,8 +1,29 @@
-use super::{ID_SOURCE, Settings, State};
-use crate::app::panes::{MARGIN, widgets::float::FloatValue};
-use egui::{Frame, Id, Margin, TextStyle, TextWrapMode, Ui};
+use super::{
+    ID_SOURCE, State,
+    settings::{Field, Settings, SortBy},
+};
+use crate::{
+    app::{
+        computers::grouping::Group,
+        panes::{
+            MARGIN,
+            explain::key,
+            formatting::Styles,
+            grouping::{self, Row, Rows},
+            search::{Query, Searchable},
+            selection::Selection,
+            source::settings::Grouping,
+            widgets::float::FloatValue,
+        },
+    },
+    special::data_frame::DataFrameExt as _,
+};
+use egui::{
+    Align, CursorIcon, Frame, Id, Margin, Sense, StrokeKind, TextStyle, TextWrapMode, Ui,
+    util::hash,
+};
 use egui_l20n::{ResponseExt as _, UiExt as _};
-use egui_phosphor::regular::HASH;
+use egui_phosphor::regular::{CARET_DOWN, CARET_RIGHT, HASH};
 use egui_table::{
     AutoSizeMode, CellInfo, Column, HeaderCellInfo, HeaderRow, Table, TableDelegate, TableState,
 };
@@ -12,33 +33,75 @@ use lipid::{
 };
 use polars::prelude::*;
 use std::ops::Range;
+use tracing::error;
 
 const INDEX: Range<usize> = 0..1;
-const MODE: Range<usize> = INDEX.end..INDEX.end + 2;
+const COLUMN: Range<usize> = INDEX.end..INDEX.end + 1;
+const MODE: Range<usize> = COLUMN.end..COLUMN.end + 2;
 const FA: Range<usize> = MODE.end..MODE.end + 2;
-const DISTANCE: Range<usize> = FA.end..FA.end + 4;
-pub(super) const LEN: usize = DISTANCE.end;
+const DISTANCE: Range<usize> = FA.end..FA.end + 5;
+const RANK: Range<usize> = DISTANCE.end..DISTANCE.end + 1;
+pub(super) const LEN: usize = RANK.end;
 
-const TOP: &[Range<usize>] = &[INDEX, MODE, FA, DISTANCE];
+const TOP: &[Range<usize>] = &[INDEX, COLUMN, MODE, FA, DISTANCE, RANK];
+
+/// Headers of the columns
+const HEADERS: [&str; LEN] = [
+    "index",
+    "column",
+    "onset-temperature.abbreviation",
+    "temperature-step.abbreviation",
+    "from",
+    "to",
+    "retention-time-distance.abbreviation",
+    "equivalent-chain-length-distance.abbreviation",
+    "euclidean-distance.abbreviation",
+    "alpha.abbreviation",
+    "resolution.abbreviation",
+    "rank",
+];
 
 /// Table view
 #[derive(Debug)]
 pub(crate) struct TableView<'a> {
     pub(crate) data_frame: &'a DataFrame,
-    pub(crate) settings: &'a Settings,
+    pub(crate) settings: &'a mut Settings,
     state: &'a mut State,
+    styles: Styles<'a, Field>,
+    headers: Vec<String>,
+    query: Option<Query>,
+    rows: Rows<'a>,
 }
 
 impl<'a> TableView<'a> {
-    pub(crate) const fn new(
+    pub(crate) fn new(
         data_frame: &'a DataFrame,
-        settings: &'a Settings,
+        settings: &'a mut Settings,
         state: &'a mut State,
+        styles: Styles<'a, Field>,
+        groups: &'a [Group],
+        table: u64,
     ) -> Self {
+        if let Some(row) = state.search.scroll {
+            grouping::expand(groups, &mut state.collapsed, row);
+        }
+        let mut collapsed = state.collapsed.iter().copied().collect::<Vec<_>>();
+        collapsed.sort_unstable();
+        Selection::reset(
+            &mut state.selection,
+            &mut state.selection_hash,
+            hash((table, collapsed)),
+        );
+        let rows = Rows::new(groups, &state.collapsed, data_frame.height());
+        let query = state.search.query();
         Self {
             data_frame,
             settings,
             state,
+            styles,
+            headers: Vec::new(),
+            query,
+            rows,
         }
     }
 }
@@ -52,9 +115,10 @@ impl TableView<'_> {
             self.state.reset_table_state = false;
         }
         let height = ui.text_style_height(&TextStyle::Heading) + 2.0 * MARGIN.y;
-        let num_rows = self.data_frame.height() as _;
+        let num_rows = self.rows.len() as _;
         let num_columns = LEN;
-        Table::new()
+        self.headers = HEADERS.map(|header| ui.localize(header)).to_vec();
+        let mut table = Table::new()
             .id_salt(id_salt)
             .num_rows(num_rows)
             .columns(vec![
@@ -69,8 +133,111 @@ impl TableView<'_> {
                 },
                 HeaderRow::new(height),
             ])
-            .auto_size_mode(AutoSizeMode::OnParentResize)
-            .show(ui, self);
+            .auto_size_mode(AutoSizeMode::OnParentResize);
+        if let Some(row) = self
+            .state
+            .search
+            .scroll
+            .take()
+            .and_then(|row| self.rows.position(row))
+        {
+            table = table.scroll_to_row(row as _, Some(Align::Center));
+        }
+        table.show(ui, self);
+        if let Some(selection) = self.state.selection {
+            selection.copy_ui(
+                ui,
+                None,
+                [self.rows.len(), LEN],
+                |column| self.headers[column].clone(),
+                |row, column| self.table_cell_text(row, column..column + 1),
+            );
+        }
+    }
+
+    /// Header of the group, toggled on the click
+    fn group_cell_ui(&mut self, ui: &mut Ui, index: usize, column: Range<usize>) {
+        let groups = self.rows.groups;
+        let group = &groups[index];
+        ui.painter()
+            .rect_filled(ui.max_rect(), 0.0, ui.visuals().widgets.inactive.bg_fill);
+        let response = ui.interact(ui.max_rect(), ui.id().with("Group"), Sense::click());
+        grouping::toggle(&response, group, &mut self.state.collapsed);
+        let precision = self.settings.precision;
+        response.on_hover_ui(|ui| group.summary.show(ui, precision));
+        Frame::new()
+            .inner_margin(Margin::from(MARGIN))
+            .show(ui, |ui| -> PolarsResult<()> {
+                if column == INDEX {
+                    let collapsed = self.state.collapsed.contains(&group.key);
+                    ui.label(if collapsed { CARET_RIGHT } else { CARET_DOWN });
+                } else if self.is_grouped(&column) {
+                    self.body_cell_content_ui(ui, group.rows[0], column)?;
+                } else if column.start == self.summary_column() {
+                    ui.label(group.summary.text(precision));
+                }
+                Ok(())
+            })
+            .inner
+            .unwrap();
+    }
+
+    /// Text of the cell of the table row
+    fn table_cell_text(&self, row: usize, column: Range<usize>) -> PolarsResult<String> {
+        match self.rows.get(row) {
+            Row::Group(index) => self.group_cell_text(index, column),
+            Row::Data(row) => self.cell_text(row, column),
+        }
+    }
+
+    /// Text of the cell of the group header
+    fn group_cell_text(&self, index: usize, column: Range<usize>) -> PolarsResult<String> {
+        let group = &self.rows.groups[index];
+        Ok(if self.is_grouped(&column) {
+            self.cell_text(group.rows[0], column)?
+        } else if column.start == self.summary_column() {
+            group.summary.text(self.settings.precision)
+        } else {
+            String::new()
+        })
+    }
+
+    /// Whether the column is of the grouped values
+    fn is_grouped(&self, column: &Range<usize>) -> bool {
+        match self.settings.grouping {
+            Grouping::None => false,
+            Grouping::Mode => COLUMN.contains(&column.start) || MODE.contains(&column.start),
+            Grouping::Pair => FA.contains(&column.start),
+        }
+    }
+
+    /// Column of the group summary, the first one after the grouped columns
+    fn summary_column(&self) -> usize {
+        match self.settings.grouping {
+            Grouping::Pair => DISTANCE.start,
+            _ => FA.start,
+        }
+    }
+
+    /// Whether the cell matches the search query
+    fn matches(&self, row: usize, column: Range<usize>) -> PolarsResult<bool> {
+        match &self.query {
+            Some(query) => self.cells().matches(query, row, column.start),
+            None => Ok(false),
+        }
+    }
+
+    /// Text of the cell as it is displayed
+    fn cell_text(&self, row: usize, column: Range<usize>) -> PolarsResult<String> {
+        self.cells().text(row, column)
+    }
+
+    /// Displayed cells of the table frame
+    fn cells(&self) -> Cells<'_> {
+        Cells {
+            data_frame: self.data_frame,
+            precision: self.settings.precision,
+        }
     }
 
     fn header_cell_content_ui(&mut self, ui: &mut Ui, row: usize, column: Range<usize>) {
@@ -82,17 +249,35 @@ impl TableView<'_> {
             (0, INDEX) => {
                 ui.heading(HASH).on_hover_localized("index");
             }
+            (0, COLUMN) => {
+                ui.heading(ui.localize("column"))
+                    .on_hover_localized("column.hover");
+            }
             (0, MODE) => {
                 ui.heading(ui.localize("mode"))
                     .on_hover_localized("mode.hover");
             }
             (0, FA) => {
-                ui.heading(ui.localize("fatty-acid"))
+                let response = ui
+                    .heading(ui.localize("fatty-acid"))
+                    .interact(Sense::click())
+                    .on_hover_cursor(CursorIcon::PointingHand)
                     .on_hover_localized("fatty-acid.abbreviation");
+                if response.clicked() {
+                    if self.settings.sort.by == SortBy::Key && self.settings.sort_keys.is_empty() {
+                        self.settings.sort.order = !self.settings.sort.order;
+                    }
+                    self.settings.sort.by = SortBy::Key;
+                    self.settings.sort_keys.clear();
+                }
             }
             (0, DISTANCE) => {
                 ui.heading(ui.localize("distance"));
             }
+            (0, RANK) => {
+                ui.heading(ui.localize("rank"))
+                    .on_hover_localized("rank.hover");
+            }
             // Bottom
             (1, mode::ONSET) => {
                 ui.heading(ui.localize("onset-temperature.abbreviation"))
@@ -110,25 +295,45 @@ impl TableView<'_> {
                 ui.heading(ui.localize("to"));
             }
             (1, distance::RETENTION_TIME) => {
-                ui.heading(ui.localize("retention-time-distance.abbreviation"))
+                let text = ui.localize("retention-time-distance.abbreviation");
+                self.settings
+                    .sort_keys
+                    .header(ui, text, Field::RetentionTime)
                     .on_hover_localized("retention-time-distance")
                     .on_hover_localized("retention-time-distance.hover");
             }
             (1, distance::ECL) => {
-                ui.heading(ui.localize("equivalent-chain-length-distance.abbreviation"))
+                let text = ui.localize("equivalent-chain-length-distance.abbreviation");
+                self.settings
+                    .sort_keys
+                    .header(ui, text, Field::EquivalentChainLength)
                     .on_hover_localized("equivalent-chain-length-distance")
                     .on_hover_localized("equivalent-chain-length-distance.hover");
             }
             (1, distance::EUCLIDEAN) => {
-                ui.heading(ui.localize("euclidean-distance.abbreviation"))
+                let text = ui.localize("euclidean-distance.abbreviation");
+                self.settings
+                    .sort_keys
+                    .header(ui, text, Field::EuclideanDistance)
                     .on_hover_localized("euclidean-distance")
                     .on_hover_localized("euclidean-distance.hover");
             }
             (1, distance::ALPHA) => {
-                ui.heading(ui.localize("alpha.abbreviation"))
+                let text = ui.localize("alpha.abbreviation");
+                self.settings
+                    .sort_keys
+                    .header(ui, text, Field::Alpha)
                     .on_hover_localized("alpha")
                     .on_hover_localized("alpha.hover");
             }
+            (1, distance::RESOLUTION) => {
+                let text = ui.localize("resolution.abbreviation");
+                self.settings
+                    .sort_keys
+                    .header(ui, text, Field::Resolution)
+                    .on_hover_localized("resolution")
+                    .on_hover_localized("resolution.hover");
+            }
             _ => {}
         }
     }
@@ -143,6 +348,16 @@ impl TableView<'_> {
             (row, INDEX) => {
                 ui.label(row.to_string());
             }
+            (row, COLUMN) => {
+                let Some(gc_column) = self.data_frame.gc_column() else {
+                    polars_bail!(ColumnNotFound: "Column");
+                };
+                let description = gc_column.description(row)?;
+                let response = ui.label(gc_column.name.str()?.get(row).unwrap_or_default());
+                if !description.is_empty() {
+                    response.on_hover_text(description);
+                }
+            }
             (row, mode::ONSET) => {
                 let mode = self.data_frame["Mode"].struct_()?;
                 let onset_temperature = mode.field_by_name("OnsetTemperature")?;
@@ -252,12 +467,140 @@ impl TableView<'_> {
                     .unwrap()
                 });
             }
+            (row, distance::RESOLUTION) => {
+                let resolution = self.data_frame["Resolution"].f64()?;
+                ui.add(
+                    FloatValue::new(resolution.get(row))
+                        .precision(Some(self.settings.precision))
+                        .hover(),
+                );
+            }
+            (row, RANK) => {
+                let rank = self.data_frame["Rank"].idx()?;
+                if let Some(rank) = rank.get(row) {
+                    ui.label(rank.to_string());
+                }
+            }
             _ => {}
         }
         Ok(())
     }
 }
 
+/// Displayed cells of the table
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Cells<'a> {
+    pub(super) data_frame: &'a DataFrame,
+    pub(super) precision: usize,
+}
+
+impl Cells<'_> {
+    /// Text of the cell as it is displayed
+    fn text(&self, row: usize, column: Range<usize>) -> PolarsResult<String> {
+        let precision = self.precision;
+        let float = |value: Option<f64>| {
+            value
+                .map(|value| format!("{value:.precision$}"))
+                .unwrap_or_default()
+        };
+        let display = |name| -> PolarsResult<String> {
+            let fatty_acid = self.data_frame["FattyAcid"]
+                .struct_()?
+                .field_by_name(name)?
+                .fa();
+            Ok(match fatty_acid.get(row)? {
+                Some(fatty_acid) => format!("{:#}", fatty_acid.display(COMMON)),
+                None => String::new(),
+            })
+        };
+        Ok(match column {
+            INDEX => row.to_string(),
+            COLUMN => match self.data_frame.gc_column() {
+                Some(gc_column) => gc_column
+                    .name
+                    .str()?
+                    .get(row)
+                    .unwrap_or_default()
+                    .to_owned(),
+                None => String::new(),
+            },
+            mode::ONSET => self.data_frame["Mode"]
+                .struct_()?
+                .field_by_name("OnsetTemperature")?
+                .str_value(row)?
+                .into_owned(),
+            mode::STEP => self.data_frame["Mode"]
+                .struct_()?
+                .field_by_name("TemperatureStep")?
+                .str_value(row)?
+                .into_owned(),
+            fatty_acid::FROM => display("From")?,
+            fatty_acid::TO => display("To")?,
+            distance::RETENTION_TIME => {
+                let retention_time = self.data_frame["RetentionTime"].struct_()?;
+                float(retention_time.field_by_name("Delta")?.f64()?.get(row))
+            }
+            distance::ECL => {
+                let ecl = self.data_frame["EquivalentChainLength"].struct_()?;
+                float(ecl.field_by_name("Delta")?.f64()?.get(row))
+            }
+            distance::EUCLIDEAN => float(self.data_frame["EuclideanDistance"].f64()?.get(row)),
+            distance::ALPHA => float(self.data_frame["Alpha"].f64()?.get(row)),
+            distance::RESOLUTION => float(self.data_frame["Resolution"].f64()?.get(row)),
+            RANK => self.data_frame["Rank"]
+                .idx()?
+                .get(row)
+                .map(|rank| rank.to_string())
+                .unwrap_or_default(),
+            _ => String::new(),
+        })
+    }
+}
+
+impl Searchable for Cells<'_> {
+    fn height(&self) -> usize {
+        self.data_frame.height()
+    }
+
+    fn width(&self) -> usize {
+        LEN
+    }
+
+    fn fatty_acids(&self, column: usize) -> PolarsResult<Option<Series>> {
+        let fatty_acid = |name| -> PolarsResult<Option<Series>> {
+            Ok(Some(
+                self.data_frame["FattyAcid"]
+                    .struct_()?
+                    .field_by_name(name)?,
+            ))
+        };
+        match column..column + 1 {
+            fatty_acid::FROM => fatty_acid("From"),
+            fatty_acid::TO => fatty_acid("To"),
+            _ => Ok(None),
+        }
+    }
+
+    fn matches(&self, query: &Query, row: usize, column: usize) -> PolarsResult<bool> {
+        let fatty_acid_matches = |name| -> PolarsResult<bool> {
+            let fatty_acid = self.data_frame["FattyAcid"]
+                .struct_()?
+                .field_by_name(name)?;
+            query.fatty_acid(&fatty_acid, row)
+        };
+        let column = column..column + 1;
+        Ok(match column {
+            INDEX => false,
+            mode::ONSET | mode::STEP => {
+                query.mode(self.data_frame, row)? || query.text(&self.text(row, column)?)
+            }
+            fatty_acid::FROM => fatty_acid_matches("From")?,
+            fatty_acid::TO => fatty_acid_matches("To")?,
+            _ => query.text(&self.text(row, column)?),
+        })
+    }
+}
+
 impl TableDelegate for TableView<'_> {
     fn header_cell_ui(&mut self, ui: &mut Ui, cell: &HeaderCellInfo) {
         Frame::new()
@@ -268,16 +611,68 @@ impl TableDelegate for TableView<'_> {
     }
 
     fn cell_ui(&mut self, ui: &mut Ui, cell: &CellInfo) {
+        let column = cell.col_nr..cell.col_nr + 1;
+        let row = match self.rows.get(cell.row_nr as _) {
+            Row::Group(index) => return self.group_cell_ui(ui, index, column),
+            Row::Data(row) => row,
+        };
         if cell.row_nr % 2 == 0 {
             ui.painter()
                 .rect_filled(ui.max_rect(), 0.0, ui.visuals().faint_bg_color);
         }
+        if let Some(color) = self
+            .styles
+            .cell(row, |field| distance::field(field) == column)
+            .or_else(|| self.styles.row(row))
+        {
+            ui.painter().rect_filled(ui.max_rect(), 0.0, color);
+        }
+        match self.matches(row, column.clone()) {
+            Ok(true) => {
+                let color = ui.visuals().warn_fg_color.gamma_multiply(0.25);
+                ui.painter().rect_filled(ui.max_rect(), 0.0, color);
+            }
+            Ok(false) => {}
+            Err(error) => error!(%error),
+        }
+        if self.state.search.row() == Some(row) {
+            ui.painter().rect_stroke(
+                ui.max_rect(),
+                0.0,
+                ui.visuals().selection.stroke,
+                StrokeKind::Inside,
+            );
+        }
+        let response = ui.interact(ui.max_rect(), ui.id().with("Cell"), Sense::click());
+        Selection::click(
+            &mut self.state.selection,
+            &response,
+            [cell.row_nr as _, cell.col_nr],
+            LEN,
+            column == INDEX,
+        );
+        if response.double_clicked() && column == distance::ALPHA {
+            match key(self.data_frame, row) {
+                Ok(key) => self.state.explain = Some(key),
+                Err(error) => error!(%error),
+            }
+        }
+        if let Some(selection) = self.state.selection {
+            if selection.contains(cell.row_nr as _, cell.col_nr) {
+                let color = ui.visuals().selection.bg_fill.gamma_multiply(0.5);
+                ui.painter().rect_filled(ui.max_rect(), 0.0, color);
+            }
+            selection.copy_ui(
+                ui,
+                Some(&response),
+                [self.rows.len(), LEN],
+                |column| self.headers[column].clone(),
+                |row, column| self.table_cell_text(row, column..column + 1),
+            );
+        }
         Frame::new()
             .inner_margin(Margin::from(MARGIN))
-            .show(ui, |ui| {
-                self.body_cell_content_ui(ui, cell.row_nr as _, cell.col_nr..cell.col_nr + 1)
-                    .unwrap()
-            });
+            .show(ui, |ui| self.body_cell_content_ui(ui, row, column).unwrap());
     }
 }
 
@@ -302,4 +697,16 @@ mod distance {
     pub(super) const ECL: Range<usize> = RETENTION_TIME.end..RETENTION_TIME.end + 1;
     pub(super) const EUCLIDEAN: Range<usize> = ECL.end..ECL.end + 1;
     pub(super) const ALPHA: Range<usize> = EUCLIDEAN.end..EUCLIDEAN.end + 1;
+    pub(super) const RESOLUTION: Range<usize> = ALPHA.end..ALPHA.end + 1;
+
+    /// Table column of the field
+    pub(super) const fn field(field: Field) -> Range<usize> {
+        match field {
+            Field::Alpha => ALPHA,
+            Field::RetentionTime => RETENTION_TIME,
+            Field::EquivalentChainLength => ECL,
+            Field::EuclideanDistance => EUCLIDEAN,
+            Field::Resolution => RESOLUTION,
+        }
+    }
 }
@@ -0,0 +1,103 @@
+use egui::{Grid, RichText, Ui};
+use egui_l20n::UiExt as _;
+use polars::prelude::*;
+
+const KEYS: [&str; 3] = ["Column", "Mode", "FattyAcid"];
+
+/// Explanation of the computed value
+///
+/// The formula with the values it is evaluated on, the last entry is the
+/// computed value.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Explanation {
+    pub(crate) formula: Option<&'static str>,
+    pub(crate) entries: Vec<Entry>,
+}
+
+impl Explanation {
+    pub(crate) fn new(formula: Option<&'static str>) -> Self {
+        Self {
+            formula,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Adds the named value with the symbol of the formula
+    pub(crate) fn entry(
+        &mut self,
+        name: &'static str,
+        symbol: &'static str,
+        value: impl Into<String>,
+    ) -> &mut Self {
+        self.entries.push(Entry {
+            name,
+            symbol,
+            value: value.into(),
+        });
+        self
+    }
+
+    pub(crate) fn show(&self, ui: &mut Ui) {
+        if let Some(formula) = self.formula {
+            ui.label(RichText::new(formula).monospace().heading());
+            ui.separator();
+        }
+        Grid::new(ui.next_auto_id()).striped(true).show(ui, |ui| {
+            for (index, entry) in self.entries.iter().enumerate() {
+                ui.label(ui.localize(entry.name));
+                ui.label(RichText::new(entry.symbol).monospace());
+                let value = RichText::new(&entry.value).monospace();
+                ui.label(if index + 1 == self.entries.len() {
+                    value.strong()
+                } else {
+                    value
+                });
+                ui.end_row();
+            }
+        });
+    }
+}
+
+/// Entry of the explanation
+#[derive(Clone, Debug)]
+pub(crate) struct Entry {
+    pub(crate) name: &'static str,
+    pub(crate) symbol: &'static str,
+    pub(crate) value: String,
+}
+
+/// Formatted value, the missing one is a dash
+pub(crate) fn value(value: Option<f64>, precision: usize) -> String {
+    match value {
+        Some(value) => format!("{value:.precision$}"),
+        None => "—".to_owned(),
+    }
+}
+
+/// Key of the explained row, the column, the mode and the fatty acid values
+///
+/// The key is kept instead of the row index, so the explanation follows the
+/// row after the sort, the filter and the search.
+pub(crate) type Key = [String; 3];
+
+/// Key of the row of the data frame
+pub(crate) fn key(data_frame: &DataFrame, row: usize) -> PolarsResult<Key> {
+    Ok([
+        data_frame[KEYS[0]].str_value(row)?.into_owned(),
+        data_frame[KEYS[1]].str_value(row)?.into_owned(),
+        data_frame[KEYS[2]].str_value(row)?.into_owned(),
+    ])
+}
+
+/// Row of the data frame with the key
+pub(crate) fn find(data_frame: &DataFrame, key: &Key) -> PolarsResult<Option<usize>> {
+    'rows: for row in 0..data_frame.height() {
+        for (name, target) in KEYS.iter().zip(key) {
+            if data_frame[*name].str_value(row)? != target.as_str() {
+                continue 'rows;
+            }
+        }
+        return Ok(Some(row));
+    }
+    Ok(None)
+}
@@ -0,0 +1,74 @@
+use super::source::settings::Rule;
+use egui::{Color32, lerp};
+use polars::prelude::*;
+
+/// Saturated rows tint
+const SATURATED: Color32 = Color32::from_rgba_premultiplied(0, 32, 64, 32);
+/// Unsaturated rows tint
+const UNSATURATED: Color32 = Color32::from_rgba_premultiplied(64, 32, 0, 32);
+
+/// Styles of the table cells
+///
+/// The evaluated formatting rules, one column per rule.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Styles<'a, F> {
+    data_frame: &'a DataFrame,
+    rules: &'a [Rule<F>],
+}
+
+impl<'a, F: Copy> Styles<'a, F> {
+    pub(crate) const fn new(data_frame: &'a DataFrame, rules: &'a [Rule<F>]) -> Self {
+        Self { data_frame, rules }
+    }
+
+    /// Background of the cell, the first satisfied rule of the field wins
+    pub(crate) fn cell(&self, row: usize, f: impl Fn(F) -> bool) -> Option<Color32> {
+        for (index, rule) in self.rules.iter().enumerate() {
+            if !f(rule.field()) {
+                continue;
+            }
+            let Ok(column) = self.data_frame.column(&index.to_string()) else {
+                continue;
+            };
+            match *rule {
+                Rule::Scale { .. } => {
+                    if let Some(value) = column.f64().ok().and_then(|value| value.get(row)) {
+                        if value.is_finite() {
+                            return Some(scale(value));
+                        }
+                    }
+                }
+                Rule::Highlight {
+                    color: [red, green, blue],
+                    ..
+                } => {
+                    if let Some(true) = column.bool().ok().and_then(|value| value.get(row)) {
+                        return Some(Color32::from_rgb(red, green, blue).gamma_multiply(0.5));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Background of the row by the saturation of the fatty acids
+    pub(crate) fn row(&self, row: usize) -> Option<Color32> {
+        let saturated = self.data_frame.column("Saturated").ok()?.bool().ok()?;
+        Some(if saturated.get(row)? {
+            SATURATED
+        } else {
+            UNSATURATED
+        })
+    }
+}
+
+/// Red to green color of the value in 0..=1
+fn scale(value: f64) -> Color32 {
+    const STOPS: [[u8; 3]; 3] = [[215, 48, 39], [254, 224, 139], [26, 152, 80]];
+    let position = value.clamp(0.0, 1.0) * (STOPS.len() - 1) as f64;
+    let index = (position.floor() as usize).min(STOPS.len() - 2);
+    let t = (position - index as f64) as f32;
+    let [from, to] = [STOPS[index], STOPS[index + 1]];
+    let channel = |channel: usize| lerp(from[channel] as f32..=to[channel] as f32, t).round() as u8;
+    Color32::from_rgb(channel(0), channel(1), channel(2)).gamma_multiply(0.5)
+}
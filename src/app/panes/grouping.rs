@@ -0,0 +1,123 @@
+use crate::app::computers::grouping::{Group, Summary};
+use egui::{Grid, Response, Ui};
+use egui_l20n::UiExt as _;
+use std::collections::HashSet;
+
+/// Row of the table
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Row {
+    /// Header of the group
+    Group(usize),
+    /// Row of the data frame
+    Data(usize),
+}
+
+/// Rows of the table
+///
+/// The group headers are followed by the rows of the expanded groups. The
+/// rows are the rows of the data frame without the groups.
+#[derive(Debug)]
+pub(crate) struct Rows<'a> {
+    pub(crate) groups: &'a [Group],
+    rows: Option<Vec<Row>>,
+    height: usize,
+}
+
+impl<'a> Rows<'a> {
+    pub(crate) fn new(groups: &'a [Group], collapsed: &HashSet<u64>, height: usize) -> Self {
+        let rows = (!groups.is_empty()).then(|| {
+            let mut rows = Vec::with_capacity(groups.len() + height);
+            for (index, group) in groups.iter().enumerate() {
+                rows.push(Row::Group(index));
+                if !collapsed.contains(&group.key) {
+                    rows.extend(group.rows.iter().map(|&row| Row::Data(row)));
+                }
+            }
+            rows
+        });
+        Self {
+            groups,
+            rows,
+            height,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        match &self.rows {
+            Some(rows) => rows.len(),
+            None => self.height,
+        }
+    }
+
+    pub(crate) fn get(&self, row: usize) -> Row {
+        match &self.rows {
+            Some(rows) => rows[row],
+            None => Row::Data(row),
+        }
+    }
+
+    /// Row of the table with the row of the data frame
+    pub(crate) fn position(&self, row: usize) -> Option<usize> {
+        match &self.rows {
+            Some(rows) => rows.iter().position(|&target| target == Row::Data(row)),
+            None => Some(row),
+        }
+    }
+}
+
+/// Expands the group of the row of the data frame
+pub(crate) fn expand(groups: &[Group], collapsed: &mut HashSet<u64>, row: usize) {
+    if let Some(group) = groups.iter().find(|group| group.rows.contains(&row)) {
+        collapsed.remove(&group.key);
+    }
+}
+
+/// Toggles the group on the click
+pub(crate) fn toggle(response: &Response, group: &Group, collapsed: &mut HashSet<u64>) {
+    if response.clicked() && !collapsed.remove(&group.key) {
+        collapsed.insert(group.key);
+    }
+}
+
+impl Summary {
+    /// Summary line
+    pub(crate) fn text(&self, precision: usize) -> String {
+        let mut text = self.count.to_string();
+        if let Some([min, max]) = self.retention_time {
+            text += &format!(" · {min:.precision$}–{max:.precision$}");
+        }
+        if let Some(alpha) = self.alpha {
+            text += &format!(" · α {alpha:.precision$}");
+        }
+        if let Some(relative_standard_deviation) = self.relative_standard_deviation {
+            text += &format!(" · {relative_standard_deviation:.precision$}%");
+        }
+        text
+    }
+
+    pub(crate) fn show(&self, ui: &mut Ui, precision: usize) {
+        Grid::new(ui.next_auto_id()).show(ui, |ui| {
+            ui.label(ui.localize("count"));
+            ui.label(self.count.to_string());
+            ui.end_row();
+
+            if let Some([min, max]) = self.retention_time {
+                ui.label(ui.localize("retention-time-span"));
+                ui.label(format!("{min:.precision$}–{max:.precision$}"));
+                ui.end_row();
+            }
+
+            if let Some(alpha) = self.alpha {
+                ui.label(ui.localize("minimum-alpha"));
+                ui.label(format!("{alpha:.precision$}"));
+                ui.end_row();
+            }
+
+            if let Some(relative_standard_deviation) = self.relative_standard_deviation {
+                ui.label(ui.localize("mean-relative-standard-deviation"));
+                ui.label(format!("{relative_standard_deviation:.precision$}%"));
+                ui.end_row();
+            }
+        });
+    }
+}
@@ -1,5 +1,8 @@
-pub(crate) use self::{distance::Pane as DistancePane, source::Pane as SourcePane};
+pub(crate) use self::{
+    comparison::Pane as ComparisonPane, distance::Pane as DistancePane, source::Pane as SourcePane,
+};
 
+use self::preset::Preset;
 use egui::{Response, Ui, Vec2, vec2};
 use metadata::MetaDataFrame;
 use serde::{Deserialize, Serialize};
@@ -11,6 +14,7 @@ const MARGIN: Vec2 = vec2(4.0, 2.0);
 pub(crate) enum Pane {
     Source(SourcePane),
     Distance(DistancePane),
+    Comparison(ComparisonPane),
 }
 
 impl Pane {
@@ -22,19 +26,25 @@ impl Pane {
         Self::Distance(DistancePane::new(frame))
     }
 
+    pub(crate) fn comparison(frame: MetaDataFrame) -> Self {
+        Self::Comparison(ComparisonPane::new(frame))
+    }
+
     pub(crate) const fn title(&self) -> &'static str {
         match self {
             Self::Source(_) => "Source",
             Self::Distance(_) => "Distance",
+            Self::Comparison(_) => "Comparison",
         }
     }
 }
 
 impl Pane {
-    fn header(&mut self, ui: &mut Ui) -> Response {
+    fn header(&mut self, ui: &mut Ui, presets: &mut Vec<Preset>) -> Response {
         match self {
-            Self::Source(pane) => pane.header(ui),
-            Self::Distance(pane) => pane.header(ui),
+            Self::Source(pane) => pane.header(ui, presets),
+            Self::Distance(pane) => pane.header(ui, presets),
+            Self::Comparison(pane) => pane.header(ui),
         }
     }
 
@@ -42,11 +52,20 @@ impl Pane {
         match self {
             Self::Source(pane) => pane.body(ui),
             Self::Distance(pane) => pane.body(ui),
+            Self::Comparison(pane) => pane.body(ui),
         }
     }
 }
 
 pub(crate) mod behavior;
+pub(crate) mod comparison;
 pub(crate) mod distance;
+pub(crate) mod explain;
+pub(crate) mod formatting;
+pub(crate) mod grouping;
+pub(crate) mod preset;
+pub(crate) mod properties;
+pub(crate) mod search;
+pub(crate) mod selection;
 pub(crate) mod source;
 pub(crate) mod widgets;
@@ -0,0 +1,104 @@
+use super::{
+    distance::settings::{
+        Field as DistanceField, Settings as DistanceSettings, Sort, SortBy as DistanceSortBy,
+    },
+    source::settings::{
+        Field as SourceField, Filter, Order, Settings as SourceSettings, SortBy, SortKeys, Target,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+/// Named filter and sort preset
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub(crate) struct Preset {
+    pub(crate) name: String,
+    pub(crate) filter: Filter,
+    pub(crate) sort: PresetSort,
+}
+
+/// Sort of the pane the preset was saved from
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub(crate) enum PresetSort {
+    Source {
+        by: SortBy,
+        order: Order,
+        keys: SortKeys<SourceField>,
+    },
+    Distance {
+        sort: Sort,
+        keys: SortKeys<DistanceField>,
+    },
+}
+
+/// Settings which can be saved to and restored from a preset
+pub(crate) trait Presettable {
+    fn preset(&self, name: String) -> Preset;
+
+    fn apply(&mut self, preset: &Preset);
+}
+
+impl Presettable for SourceSettings {
+    fn preset(&self, name: String) -> Preset {
+        Preset {
+            name,
+            filter: self.filter.clone(),
+            sort: PresetSort::Source {
+                by: self.sort.clone(),
+                order: self.order,
+                keys: self.sort_keys.clone(),
+            },
+        }
+    }
+
+    fn apply(&mut self, preset: &Preset) {
+        self.filter = preset.filter.clone();
+        match &preset.sort {
+            PresetSort::Source { by, order, keys } => {
+                self.sort = by.clone();
+                self.order = *order;
+                self.sort_keys = keys.clone();
+            }
+            PresetSort::Distance { sort, .. } => {
+                self.sort_keys.clear();
+                self.sort = match sort.by {
+                    DistanceSortBy::Key => SortBy::FattyAcid,
+                    DistanceSortBy::Value => SortBy::Time,
+                };
+                self.order = sort.order;
+            }
+        }
+    }
+}
+
+impl Presettable for DistanceSettings {
+    fn preset(&self, name: String) -> Preset {
+        Preset {
+            name,
+            filter: self.filter.clone(),
+            sort: PresetSort::Distance {
+                sort: self.sort,
+                keys: self.sort_keys.clone(),
+            },
+        }
+    }
+
+    fn apply(&mut self, preset: &Preset) {
+        self.filter = preset.filter.clone();
+        match &preset.sort {
+            PresetSort::Source { by, order, .. } => {
+                self.sort_keys.clear();
+                // The fatty acid selection applies to both of the pair, so
+                // should the structure conditions
+                self.filter.structure.target = Target::Both;
+                if let SortBy::FattyAcid = by {
+                    self.sort.by = DistanceSortBy::Key;
+                }
+                self.sort.order = *order;
+            }
+            PresetSort::Distance { sort, keys } => {
+                self.sort = *sort;
+                self.sort_keys = keys.clone();
+            }
+        }
+    }
+}
@@ -0,0 +1,556 @@
+use anyhow::{Context as _, Result, bail, ensure};
+use egui::{Button, Color32, Grid, Label, TextEdit, Ui};
+use egui_l20n::{ResponseExt as _, UiExt as _};
+use egui_phosphor::regular::{MINUS, PLUS};
+use metadata::Metadata;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    fmt::{self, Display, Formatter},
+};
+
+const SEPARATOR: &str = "--- properties ---";
+const AUTHORS: &str = "Authors";
+const INSTRUMENT: &str = "Instrument";
+const COLUMN: &str = "Column";
+const METHOD: &str = "Method";
+const KEYS: [&str; 4] = [AUTHORS, INSTRUMENT, COLUMN, METHOD];
+/// Keys of the schema metadata written by the metadata
+pub(crate) const METADATA_KEYS: [&str; 5] = ["name", "description", "authors", "version", "date"];
+
+/// Properties of the metadata
+///
+/// The properties are the entries of the IPC schema metadata next to the
+/// name, the version and the date (see [`crate::utils::ipc`]). The metadata
+/// has the name, the description, the version and the date only, so in the
+/// memory the authors, the instrument, the column and the method notes and
+/// the free key-value pairs are kept at the end of the description:
+///
+/// ```text
+/// Description
+///
+/// --- properties ---
+/// Authors: A, B
+/// Instrument: Agilent 7890A
+/// Key: value
+/// ```
+///
+/// The block is after the last `--- properties ---` line, each line of it is
+/// `Key: value`, the authors are comma separated and the line breaks of the
+/// values are replaced by the spaces. The description is kept as is if the
+/// block is malformed, so the plain `---` lines of the description are not
+/// taken for the properties.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Properties {
+    pub(crate) description: String,
+    pub(crate) authors: Vec<String>,
+    pub(crate) instrument: String,
+    pub(crate) column: String,
+    pub(crate) method: String,
+    pub(crate) pairs: Vec<(String, String)>,
+}
+
+impl Properties {
+    /// Parses the description, the description without the properties is
+    /// kept as is
+    pub(crate) fn parse(description: &str) -> Self {
+        let mut properties = Self {
+            description: description.to_owned(),
+            ..Default::default()
+        };
+        // Last separator line
+        let Some(index) = description
+            .match_indices(SEPARATOR)
+            .map(|(index, _)| index)
+            .filter(|&index| {
+                let end = index + SEPARATOR.len();
+                (index == 0 || description[..index].ends_with('\n'))
+                    && (end == description.len() || description[end..].starts_with('\n'))
+            })
+            .last()
+        else {
+            return properties;
+        };
+        let text = &description[..index];
+        let section = &description[index + SEPARATOR.len()..];
+        let Some(lines) = section
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.split_once(": "))
+            .collect::<Option<Vec<_>>>()
+        else {
+            return properties;
+        };
+        properties.description = text.trim_end().to_owned();
+        for (key, value) in lines {
+            properties.insert(key, value);
+        }
+        properties
+    }
+
+    /// Properties of the description and the entries of the schema metadata
+    pub(crate) fn new<'a>(
+        description: &str,
+        entries: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Self {
+        let mut properties = Self {
+            description: description.to_owned(),
+            ..Default::default()
+        };
+        for (key, value) in entries {
+            properties.insert(key, value);
+        }
+        properties
+    }
+
+    fn insert(&mut self, key: &str, value: &str) {
+        let value = value.trim().to_owned();
+        match key.trim() {
+            AUTHORS => {
+                self.authors = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|author| !author.is_empty())
+                    .map(ToOwned::to_owned)
+                    .collect();
+            }
+            INSTRUMENT => self.instrument = value,
+            COLUMN => self.column = value,
+            METHOD => self.method = value,
+            key => self.pairs.push((key.to_owned(), value)),
+        }
+    }
+
+    /// Sets the value of the free key, the previous value is replaced
+    pub(crate) fn set(&mut self, key: &str, value: String) {
+        match self.pairs.iter_mut().find(|(target, _)| target == key) {
+            Some((_, target)) => *target = value,
+            None => self.pairs.push((key.to_owned(), value)),
+        }
+    }
+
+    /// Whether there are no properties besides the description
+    pub(crate) fn is_empty(&self) -> bool {
+        self.authors.is_empty()
+            && self.instrument.is_empty()
+            && self.column.is_empty()
+            && self.method.is_empty()
+            && self.pairs.is_empty()
+    }
+
+    /// Non-empty entries of the schema metadata
+    pub(crate) fn entries(&self) -> impl Iterator<Item = (&str, String)> {
+        [
+            (AUTHORS, self.authors.join(", ")),
+            (INSTRUMENT, self.instrument.clone()),
+            (COLUMN, self.column.clone()),
+            (METHOD, self.method.clone()),
+        ]
+        .into_iter()
+        .chain(
+            self.pairs
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.clone())),
+        )
+        .filter(|(_, value)| !value.is_empty())
+    }
+}
+
+/// Description with the properties
+///
+/// The empty block is written after the description with the separator line,
+/// so it is parsed back as is.
+impl Display for Properties {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.description)?;
+        if !self.is_empty() || self.description.lines().any(|line| line == SEPARATOR) {
+            if !self.description.is_empty() {
+                f.write_str("\n\n")?;
+            }
+            f.write_str(SEPARATOR)?;
+            for (key, value) in self.entries() {
+                write!(f, "\n{key}: {}", value.replace('\n', " "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Form of the metadata
+///
+/// The draft is taken from the metadata on the first show, after the apply or
+/// the reset and after the source hash is changed.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub(crate) struct Form {
+    #[serde(skip)]
+    drafts: Option<Drafts>,
+}
+
+impl Form {
+    pub(crate) fn new() -> Self {
+        Self { drafts: None }
+    }
+
+    /// Shows the form, returns whether the metadata is changed
+    pub(crate) fn show(&mut self, ui: &mut Ui, meta: &mut Metadata, hash: u64) -> bool {
+        if self
+            .drafts
+            .as_ref()
+            .is_some_and(|drafts| drafts.hash != hash)
+        {
+            self.drafts = None;
+        }
+        let drafts = self.drafts.get_or_insert_with(|| Drafts::new(meta, hash));
+        drafts.draft.show(ui);
+        ui.separator();
+        let result = drafts.draft.metadata(meta);
+        let modified = drafts.draft != drafts.original;
+        if let Err(error) = &result {
+            ui.colored_label(Color32::RED, format!("{error:#}"));
+        }
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(
+                    modified && result.is_ok(),
+                    Button::new(ui.localize("apply")),
+                )
+                .on_hover_localized("apply.hover")
+                .clicked()
+            {
+                if let Ok(value) = result {
+                    *meta = value;
+                    changed = true;
+                }
+                self.drafts = None;
+            }
+            if ui.button(ui.localize("reset")).clicked() {
+                self.drafts = None;
+            }
+        });
+        changed
+    }
+}
+
+/// Draft and the original of the metadata of the source hash
+#[derive(Clone, Debug)]
+struct Drafts {
+    hash: u64,
+    original: Draft,
+    draft: Draft,
+}
+
+impl Drafts {
+    fn new(meta: &Metadata, hash: u64) -> Self {
+        let original = Draft::new(meta);
+        Self {
+            hash,
+            draft: original.clone(),
+            original,
+        }
+    }
+}
+
+/// Draft of the metadata
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Draft {
+    name: String,
+    description: String,
+    authors: String,
+    version: String,
+    date: String,
+    instrument: String,
+    column: String,
+    method: String,
+    pairs: Vec<(String, String)>,
+}
+
+impl Draft {
+    fn new(meta: &Metadata) -> Self {
+        let properties = Properties::parse(&meta.description);
+        Self {
+            name: meta.name.clone(),
+            description: properties.description,
+            authors: properties.authors.join(", "),
+            version: meta
+                .version
+                .as_ref()
+                .map_or_else(String::new, ToString::to_string),
+            date: meta
+                .date
+                .as_ref()
+                .map_or_else(String::new, ToString::to_string),
+            instrument: properties.instrument,
+            column: properties.column,
+            method: properties.method,
+            pairs: properties.pairs,
+        }
+    }
+
+    fn show(&mut self, ui: &mut Ui) {
+        Grid::new(ui.next_auto_id()).num_columns(2).show(ui, |ui| {
+            ui.label(ui.localize("name"));
+            ui.text_edit_singleline(&mut self.name);
+            ui.end_row();
+
+            ui.label(ui.localize("description"));
+            ui.text_edit_multiline(&mut self.description);
+            ui.end_row();
+
+            ui.label(ui.localize("authors"));
+            ui.add(TextEdit::singleline(&mut self.authors).hint_text(ui.localize("authors.hint")));
+            ui.end_row();
+
+            ui.label(ui.localize("version"));
+            ui.add(TextEdit::singleline(&mut self.version).hint_text("0.1.0"));
+            ui.end_row();
+
+            ui.label(ui.localize("date"));
+            ui.add(TextEdit::singleline(&mut self.date).hint_text("2025-01-31"));
+            ui.end_row();
+
+            ui.label(ui.localize("instrument"));
+            ui.text_edit_singleline(&mut self.instrument);
+            ui.end_row();
+
+            ui.label(ui.localize("column"));
+            ui.text_edit_singleline(&mut self.column);
+            ui.end_row();
+
+            ui.label(ui.localize("method"));
+            ui.text_edit_singleline(&mut self.method);
+            ui.end_row();
+        });
+        ui.separator();
+        ui.label(ui.localize("properties"));
+        let mut remove = None;
+        Grid::new(ui.next_auto_id()).show(ui, |ui| {
+            for (index, (key, value)) in self.pairs.iter_mut().enumerate() {
+                ui.add(
+                    TextEdit::singleline(key)
+                        .hint_text(ui.localize("key"))
+                        .desired_width(ui.spacing().text_edit_width / 2.0),
+                );
+                ui.add(TextEdit::singleline(value).hint_text(ui.localize("value")));
+                if ui
+                    .button(MINUS)
+                    .on_hover_localized("remove-property")
+                    .clicked()
+                {
+                    remove = Some(index);
+                }
+                ui.end_row();
+            }
+        });
+        if let Some(index) = remove {
+            self.pairs.remove(index);
+        }
+        if ui.button(PLUS).on_hover_localized("add-property").clicked() {
+            self.pairs.push(Default::default());
+        }
+    }
+
+    /// Metadata of the draft
+    fn metadata(&self, meta: &Metadata) -> Result<Metadata> {
+        let mut keys = HashSet::new();
+        for (key, _) in &self.pairs {
+            let key = key.trim();
+            ensure!(!key.is_empty(), "empty property key");
+            ensure!(
+                !key.contains(':') && !key.contains('\n'),
+                "invalid property key {key:?}"
+            );
+            if KEYS.contains(&key) || METADATA_KEYS.contains(&key) {
+                bail!("reserved property key {key:?}");
+            }
+            ensure!(keys.insert(key), "duplicate property key {key:?}");
+        }
+        let properties = Properties {
+            description: self.description.trim().to_owned(),
+            authors: self
+                .authors
+                .split(',')
+                .map(str::trim)
+                .filter(|author| !author.is_empty())
+                .map(ToOwned::to_owned)
+                .collect(),
+            instrument: self.instrument.trim().to_owned(),
+            column: self.column.trim().to_owned(),
+            method: self.method.trim().to_owned(),
+            pairs: self
+                .pairs
+                .iter()
+                .map(|(key, value)| (key.trim().to_owned(), value.trim().to_owned()))
+                .collect(),
+        };
+        let mut meta = meta.clone();
+        meta.name = self.name.trim().to_owned();
+        meta.description = properties.to_string();
+        meta.version = match self.version.trim() {
+            "" => None,
+            version => Some(
+                version
+                    .parse()
+                    .with_context(|| format!("invalid version {version:?}"))?,
+            ),
+        };
+        meta.date = match self.date.trim() {
+            "" => None,
+            date => Some(
+                date.parse()
+                    .with_context(|| format!("invalid date {date:?}"))?,
+            ),
+        };
+        Ok(meta)
+    }
+}
+
+/// Viewer of the metadata
+pub(crate) fn view(ui: &mut Ui, meta: &Metadata) {
+    let properties = Properties::parse(&meta.description);
+    Grid::new(ui.next_auto_id()).show(ui, |ui| {
+        ui.label(ui.localize("name"));
+        ui.label(&meta.name);
+        ui.end_row();
+
+        if !properties.description.is_empty() {
+            ui.label(ui.localize("description"));
+            ui.add(Label::new(&properties.description).truncate());
+            ui.end_row();
+        }
+
+        if !properties.authors.is_empty() {
+            ui.label(ui.localize("authors"));
+            ui.label(properties.authors.join(", "));
+            ui.end_row();
+        }
+
+        if let Some(version) = &meta.version {
+            ui.label(ui.localize("version"));
+            ui.label(version.to_string());
+            ui.end_row();
+        }
+
+        if let Some(date) = &meta.date {
+            ui.label(ui.localize("date"));
+            ui.label(date.to_string());
+            ui.end_row();
+        }
+
+        for (key, value) in [
+            ("instrument", &properties.instrument),
+            ("column", &properties.column),
+            ("method", &properties.method),
+        ] {
+            if !value.is_empty() {
+                ui.label(ui.localize(key));
+                ui.label(value);
+                ui.end_row();
+            }
+        }
+
+        for (key, value) in &properties.pairs {
+            ui.label(key);
+            ui.label(value);
+            ui.end_row();
+        }
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn properties() -> Properties {
+        Properties {
+            description: "Fatty acid methyl esters\n\nSecond paragraph".to_owned(),
+            authors: vec!["A".to_owned(), "B".to_owned()],
+            instrument: "Agilent 7890A".to_owned(),
+            column: "SP-2560".to_owned(),
+            method: String::new(),
+            pairs: vec![("Sample".to_owned(), "Seed oil".to_owned())],
+        }
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            properties().to_string(),
+            "Fatty acid methyl esters\n\nSecond paragraph\n\n--- properties ---\nAuthors: A, B\nInstrument: Agilent 7890A\nColumn: SP-2560\nSample: Seed oil",
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let properties = properties();
+        assert_eq!(Properties::parse(&properties.to_string()), properties);
+        let properties = Properties {
+            description: String::new(),
+            ..properties
+        };
+        assert_eq!(Properties::parse(&properties.to_string()), properties);
+        let properties = Properties {
+            description: "Fatty acid methyl esters".to_owned(),
+            ..Default::default()
+        };
+        assert_eq!(properties.to_string(), "Fatty acid methyl esters");
+        assert_eq!(Properties::parse(&properties.to_string()), properties);
+    }
+
+    #[test]
+    fn entries() {
+        let properties = properties();
+        let entries = properties.entries().collect::<Vec<_>>();
+        let entries = entries.iter().map(|(key, value)| (*key, value.as_str()));
+        assert_eq!(
+            Properties::new(&properties.description, entries),
+            properties
+        );
+    }
+
+    #[test]
+    fn plain_separator() {
+        let description = "Notes\n---\nColumn: SP-2560\nOven: 70 °C";
+        let properties = Properties::parse(description);
+        assert!(properties.is_empty());
+        assert_eq!(properties.description, description);
+    }
+
+    #[test]
+    fn separator_in_description() {
+        let properties = Properties {
+            description: "Notes\n--- properties ---\nColumn: SP-2560".to_owned(),
+            ..Default::default()
+        };
+        assert_eq!(Properties::parse(&properties.to_string()), properties);
+        let properties = Properties {
+            method: "70 °C".to_owned(),
+            ..properties
+        };
+        assert_eq!(Properties::parse(&properties.to_string()), properties);
+    }
+
+    #[test]
+    fn malformed() {
+        let description = "Notes\n\n--- properties ---\nColumn: SP-2560\nOven";
+        let properties = Properties::parse(description);
+        assert!(properties.is_empty());
+        assert_eq!(properties.description, description);
+    }
+
+    #[test]
+    fn set() {
+        let mut properties = properties();
+        properties.set("Changes", "0 FattyAcid: 18:1 → 18:2".to_owned());
+        properties.set("Changes", "1 DeadTime: 1 → 2".to_owned());
+        assert_eq!(
+            properties.pairs,
+            [
+                ("Sample".to_owned(), "Seed oil".to_owned()),
+                ("Changes".to_owned(), "1 DeadTime: 1 → 2".to_owned()),
+            ]
+        );
+        let parsed = Properties::parse(&properties.to_string());
+        assert_eq!(parsed, properties);
+    }
+}
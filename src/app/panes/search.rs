@@ -0,0 +1,319 @@
+use crate::{
+    app::computers::{SearchComputed, SearchKey},
+    special::name::Name,
+};
+use egui::{Button, Key, RichText, TextEdit, Ui};
+use egui_l20n::{ResponseExt as _, UiExt as _};
+use egui_phosphor::regular::{CARET_DOWN, CARET_UP, FUNNEL, MAGNIFYING_GLASS};
+use lipid::{
+    fatty_acid::display::{COMMON, DisplayWithOptions as _},
+    prelude::*,
+};
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+/// Incremental search of the table rows
+///
+/// The matched rows are either filtered or jumped to with the next and the
+/// previous buttons.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub(crate) struct Search {
+    pub(crate) query: String,
+    pub(crate) filter: bool,
+    pub(crate) current: usize,
+    /// Matched rows of the data frame
+    #[serde(skip)]
+    pub(crate) matches: Vec<usize>,
+    /// Row of the table to scroll to
+    #[serde(skip)]
+    pub(crate) scroll: Option<usize>,
+    #[serde(skip)]
+    changed: bool,
+}
+
+impl Search {
+    pub(crate) fn new() -> Self {
+        Self {
+            query: String::new(),
+            filter: false,
+            current: 0,
+            matches: Vec::new(),
+            scroll: None,
+            changed: false,
+        }
+    }
+
+    /// Query of the non empty search
+    pub(crate) fn query(&self) -> Option<Query> {
+        Query::new(&self.query)
+    }
+
+    /// Whether the table shows the matched rows only
+    pub(crate) fn is_filter(&self) -> bool {
+        self.filter && !self.query.trim().is_empty()
+    }
+
+    /// Query of the filter mode
+    pub(crate) fn filtered(&self) -> Option<&str> {
+        self.is_filter().then_some(&self.query)
+    }
+
+    /// Search box with the navigation over the matched rows
+    pub(crate) fn show(&mut self, ui: &mut Ui) {
+        ui.label(RichText::new(MAGNIFYING_GLASS).heading());
+        let response = ui
+            .add(
+                TextEdit::singleline(&mut self.query)
+                    .hint_text(ui.localize("search"))
+                    .desired_width(ui.spacing().text_edit_width / 2.0),
+            )
+            .on_hover_localized("search.hover");
+        if response.changed() {
+            self.current = 0;
+            self.changed = true;
+        }
+        let count = self.matches.len();
+        self.current = self.current.min(count.saturating_sub(1));
+        if !self.query.trim().is_empty() {
+            ui.label(match count {
+                0 => "0/0".to_owned(),
+                _ => format!("{}/{count}", self.current + 1),
+            });
+        }
+        let enter = response.lost_focus() && ui.input(|input| input.key_pressed(Key::Enter));
+        let shift = ui.input(|input| input.modifiers.shift);
+        let mut step = None;
+        if ui
+            .add_enabled(count > 0, Button::new(RichText::new(CARET_UP).heading()))
+            .on_hover_localized("previous-match")
+            .clicked()
+            || enter && shift
+        {
+            step = Some(count.saturating_sub(1));
+        }
+        if ui
+            .add_enabled(count > 0, Button::new(RichText::new(CARET_DOWN).heading()))
+            .on_hover_localized("next-match")
+            .clicked()
+            || enter && !shift
+        {
+            step = Some(1);
+        }
+        if let Some(step) = step.filter(|_| count > 0) {
+            self.current = (self.current + step) % count;
+            self.scroll = self.row();
+            if enter {
+                response.request_focus();
+            }
+        }
+        if ui
+            .toggle_value(&mut self.filter, RichText::new(FUNNEL).heading())
+            .on_hover_localized("filter-matches")
+            .changed()
+        {
+            self.changed = true;
+        }
+    }
+
+    /// Searches the displayed cells of the data frame, only the matched rows
+    /// are kept in the filter mode
+    pub(crate) fn apply(
+        &mut self,
+        ui: &Ui,
+        data_frame: &DataFrame,
+        table: &dyn Searchable,
+        hash: u64,
+    ) -> PolarsResult<DataFrame> {
+        let matches = ui.memory_mut(|memory| {
+            memory.caches.cache::<SearchComputed>().get(SearchKey {
+                table,
+                hash,
+                query: &self.query,
+            })
+        });
+        let data_frame = if self.is_filter() {
+            let indices = matches.iter().map(|&row| row as IdxSize).collect();
+            data_frame.take(&IdxCa::from_vec(PlSmallStr::EMPTY, indices))?
+        } else {
+            data_frame.clone()
+        };
+        self.update(matches);
+        Ok(data_frame)
+    }
+
+    /// Updates the matched rows, scrolls to the first one after the query
+    /// changed
+    fn update(&mut self, matches: Vec<usize>) {
+        self.matches = matches;
+        if self.changed {
+            self.changed = false;
+            self.scroll = self.row();
+        }
+    }
+
+    /// Row of the table with the current match
+    pub(crate) fn row(&self) -> Option<usize> {
+        if self.is_filter() {
+            (self.current < self.matches.len()).then_some(self.current)
+        } else {
+            self.matches.get(self.current).copied()
+        }
+    }
+}
+
+/// Table searched by the displayed cells
+///
+/// The cells are of the visible columns, so the search agrees with the
+/// highlighted cells.
+pub(crate) trait Searchable: Debug {
+    /// Number of the rows
+    fn height(&self) -> usize;
+
+    /// Number of the columns
+    fn width(&self) -> usize;
+
+    /// Fatty acids of the column, `None` for the other columns
+    fn fatty_acids(&self, column: usize) -> PolarsResult<Option<Series>>;
+
+    /// Whether the cell matches the search query
+    fn matches(&self, query: &Query, row: usize, column: usize) -> PolarsResult<bool>;
+}
+
+/// Search query
+///
+/// A fatty acid name in any notation (`18:1Δ9c`, `18:1n-9`), a mode as the
+/// onset temperature and the temperature step (`70/1`), a number or a text.
+/// The number matches the values with the same leading digits.
+#[derive(Clone, Debug)]
+pub(crate) enum Query {
+    FattyAcid(Name),
+    Mode {
+        onset_temperature: Number,
+        temperature_step: Number,
+    },
+    Number(Number),
+    Text(String),
+}
+
+impl Query {
+    pub(crate) fn new(text: &str) -> Option<Self> {
+        let text = text.trim();
+        if text.is_empty() {
+            return None;
+        }
+        if text.contains(':') {
+            if let Ok(name) = Name::parse(text) {
+                return Some(Self::FattyAcid(name));
+            }
+        }
+        if let Some((onset_temperature, temperature_step)) = text.split_once('/') {
+            if let (Some(onset_temperature), Some(temperature_step)) = (
+                Number::parse(onset_temperature),
+                Number::parse(temperature_step),
+            ) {
+                return Some(Self::Mode {
+                    onset_temperature,
+                    temperature_step,
+                });
+            }
+        }
+        if let Some(number) = Number::parse(text) {
+            return Some(Self::Number(number));
+        }
+        Some(Self::Text(text.to_lowercase()))
+    }
+
+    /// Whether the decoded name of the fatty acid matches
+    pub(crate) fn name(&self, other: Option<&Name>) -> bool {
+        match self {
+            Self::FattyAcid(name) => other.is_some_and(|other| name.matches(other)),
+            _ => false,
+        }
+    }
+
+    /// Whether the fatty acid of the row matches, the row only is decoded
+    pub(crate) fn fatty_acid(&self, series: &Series, row: usize) -> PolarsResult<bool> {
+        Ok(match self {
+            Self::FattyAcid(_) => self.name(
+                Name::from_series(&series.slice(row as _, 1))?
+                    .first()
+                    .and_then(Option::as_ref),
+            ),
+            Self::Text(text) => series
+                .fa()
+                .get(row)?
+                .is_some_and(|fatty_acid| contains(&fatty_acid, text)),
+            _ => false,
+        })
+    }
+
+    /// Whether the mode of the row matches
+    pub(crate) fn mode(&self, data_frame: &DataFrame, row: usize) -> PolarsResult<bool> {
+        let Self::Mode {
+            onset_temperature,
+            temperature_step,
+        } = self
+        else {
+            return Ok(false);
+        };
+        let mode = data_frame["Mode"].struct_()?;
+        let value = |name: &str| -> PolarsResult<Option<f64>> {
+            Ok(mode
+                .field_by_name(name)?
+                .cast(&DataType::Float64)?
+                .f64()?
+                .get(row))
+        };
+        Ok(
+            value("OnsetTemperature")?.is_some_and(|value| onset_temperature.matches(value))
+                && value("TemperatureStep")?.is_some_and(|value| temperature_step.matches(value)),
+        )
+    }
+
+    /// Whether the displayed text of the cell matches
+    pub(crate) fn text(&self, text: &str) -> bool {
+        match self {
+            Self::FattyAcid(name) => Name::parse(text).is_ok_and(|other| name.matches(&other)),
+            Self::Number(number) => {
+                Number::parse(text).is_some_and(|value| number.matches(value.value))
+            }
+            Self::Text(query) => text.to_lowercase().contains(query),
+            Self::Mode { .. } => false,
+        }
+    }
+}
+
+/// Whether the displayed fatty acid contains the lowercase text
+fn contains(fatty_acid: &FattyAcid, text: &str) -> bool {
+    format!("{:#}", fatty_acid.display(COMMON))
+        .to_lowercase()
+        .contains(text)
+}
+
+/// Number with the count of the decimal digits
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Number {
+    value: f64,
+    decimals: i32,
+}
+
+impl Number {
+    fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().replace(',', ".");
+        let value = text.parse::<f64>().ok()?;
+        if !value.is_finite() {
+            return None;
+        }
+        let decimals = text
+            .split_once('.')
+            .map_or(0, |(_, fraction)| fraction.len() as _);
+        Some(Self { value, decimals })
+    }
+
+    /// Whether the value truncated to the decimal digits equals the number
+    fn matches(&self, value: f64) -> bool {
+        let factor = 10f64.powi(self.decimals);
+        (value * factor).trunc() == (self.value * factor).trunc()
+    }
+}
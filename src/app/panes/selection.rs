@@ -0,0 +1,243 @@
+use crate::localization::Text;
+use egui::{Event, Response, Ui};
+use egui_l20n::{ResponseExt as _, UiExt as _};
+use egui_phosphor::regular::COPY;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Write,
+    ops::{Range, RangeInclusive},
+};
+use tracing::error;
+
+/// Selected rectangle of the table cells
+///
+/// The anchor is the first clicked cell, the cursor is the last one. Both are
+/// `[row, column]`.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub(crate) struct Selection {
+    pub(crate) anchor: [usize; 2],
+    pub(crate) cursor: [usize; 2],
+}
+
+impl Selection {
+    pub(crate) const fn new(row: usize, column: usize) -> Self {
+        Self {
+            anchor: [row, column],
+            cursor: [row, column],
+        }
+    }
+
+    /// Clears the selection if the hash of the table (the rows, the columns
+    /// and the collapsed groups) is changed
+    pub(crate) fn reset(selection: &mut Option<Self>, hash: &mut u64, table: u64) {
+        if *hash != table {
+            *selection = None;
+            *hash = table;
+        }
+    }
+
+    pub(crate) fn rows(&self) -> RangeInclusive<usize> {
+        self.anchor[0].min(self.cursor[0])..=self.anchor[0].max(self.cursor[0])
+    }
+
+    pub(crate) fn columns(&self) -> RangeInclusive<usize> {
+        self.anchor[1].min(self.cursor[1])..=self.anchor[1].max(self.cursor[1])
+    }
+
+    pub(crate) fn contains(&self, row: usize, column: usize) -> bool {
+        self.rows().contains(&row) && self.columns().contains(&column)
+    }
+
+    /// Selects the cell on the click, the cell of the index column selects
+    /// the whole row, shift extends the selection
+    pub(crate) fn click(
+        selection: &mut Option<Self>,
+        response: &Response,
+        [row, column]: [usize; 2],
+        columns: usize,
+        index: bool,
+    ) {
+        if !response.clicked() {
+            return;
+        }
+        let extend = response.ctx.input(|input| input.modifiers.shift);
+        match selection {
+            Some(selection) if extend => {
+                selection.cursor = [row, if index { columns - 1 } else { column }];
+            }
+            _ if index => {
+                *selection = Some(Self {
+                    anchor: [row, 0],
+                    cursor: [row, columns - 1],
+                });
+            }
+            _ => *selection = Some(Self::new(row, column)),
+        }
+    }
+
+    /// Table of the selected cells with the headers, the selection is clamped
+    /// to the `[rows, columns]` size of the table
+    pub(crate) fn copy(
+        &self,
+        format: Format,
+        [rows, columns]: [usize; 2],
+        header: impl Fn(usize) -> String,
+        cell: impl Fn(usize, usize) -> PolarsResult<String>,
+    ) -> PolarsResult<String> {
+        let columns = clamp(self.columns(), columns);
+        let header = columns.clone().map(header).collect::<Vec<_>>();
+        let rows = clamp(self.rows(), rows)
+            .map(|row| columns.clone().map(|column| cell(row, column)).collect())
+            .collect::<PolarsResult<Vec<Vec<_>>>>()?;
+        Ok(format.table(&header, &rows))
+    }
+
+    /// Copies the selection on the copy event or from the context menu
+    pub(crate) fn copy_ui(
+        &self,
+        ui: &mut Ui,
+        response: Option<&Response>,
+        size: [usize; 2],
+        header: impl Fn(usize) -> String,
+        cell: impl Fn(usize, usize) -> PolarsResult<String>,
+    ) {
+        let mut format = None;
+        match response {
+            Some(response) => {
+                response.context_menu(|ui| {
+                    for variant in Format::VARIANTS {
+                        if ui
+                            .button(format!("{COPY} {}", ui.localize(variant.text())))
+                            .on_hover_localized(variant.hover_text())
+                            .clicked()
+                        {
+                            format = Some(variant);
+                            ui.close_menu();
+                        }
+                    }
+                });
+            }
+            None => {
+                if ui.ui_contains_pointer()
+                    && ui.input(|input| input.events.iter().any(|event| *event == Event::Copy))
+                {
+                    format = Some(Format::Tsv);
+                }
+            }
+        }
+        if let Some(format) = format {
+            match self.copy(format, size, header, cell) {
+                Ok(text) => ui.ctx().copy_text(text),
+                Err(error) => error!(%error),
+            }
+        }
+    }
+}
+
+/// Range of the indices less than the length
+fn clamp(range: RangeInclusive<usize>, len: usize) -> Range<usize> {
+    *range.start()..(*range.end() + 1).min(len)
+}
+
+/// Copy format
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub(crate) enum Format {
+    Tsv,
+    Markdown,
+    AsciiDoc,
+}
+
+impl Format {
+    pub(crate) const VARIANTS: [Self; 3] = [Self::Tsv, Self::Markdown, Self::AsciiDoc];
+
+    pub(crate) fn table(&self, header: &[String], rows: &[Vec<String>]) -> String {
+        let mut table = String::new();
+        match self {
+            Self::Tsv => {
+                let escape = |text: &String| text.replace(['\t', '\n'], " ");
+                for row in std::iter::once(header).chain(rows.iter().map(Vec::as_slice)) {
+                    let row = row.iter().map(escape).collect::<Vec<_>>();
+                    writeln!(table, "{}", row.join("\t")).ok();
+                }
+            }
+            Self::Markdown => {
+                let escape = |text: &String| text.replace('|', r"\|").replace('\n', " ");
+                let header = header.iter().map(escape).collect::<Vec<_>>();
+                writeln!(table, "| {} |", header.join(" | ")).ok();
+                writeln!(table, "|{}", " --- |".repeat(header.len())).ok();
+                for row in rows {
+                    let row = row.iter().map(escape).collect::<Vec<_>>();
+                    writeln!(table, "| {} |", row.join(" | ")).ok();
+                }
+            }
+            Self::AsciiDoc => {
+                let escape = |text: &String| text.replace('|', r"\|").replace('\n', " ");
+                writeln!(table, "[%header]").ok();
+                writeln!(table, "|===").ok();
+                for row in std::iter::once(header).chain(rows.iter().map(Vec::as_slice)) {
+                    for text in row {
+                        write!(table, "|{} ", escape(text)).ok();
+                    }
+                    table.pop();
+                    writeln!(table).ok();
+                }
+                writeln!(table, "|===").ok();
+            }
+        }
+        table
+    }
+}
+
+impl Text for Format {
+    fn text(&self) -> &'static str {
+        match self {
+            Self::Tsv => "copy-as-tsv",
+            Self::Markdown => "copy-as-markdown",
+            Self::AsciiDoc => "copy-as-asciidoc",
+        }
+    }
+
+    fn hover_text(&self) -> &'static str {
+        match self {
+            Self::Tsv => "copy-as-tsv.hover",
+            Self::Markdown => "copy-as-markdown.hover",
+            Self::AsciiDoc => "copy-as-asciidoc.hover",
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn copy(selection: Selection, size: [usize; 2]) -> String {
+        selection
+            .copy(
+                Format::Tsv,
+                size,
+                |column| format!("h{column}"),
+                |row, column| Ok(format!("{row}{column}")),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn copy_within_table() {
+        let selection = Selection {
+            anchor: [1, 2],
+            cursor: [0, 1],
+        };
+        assert_eq!(copy(selection, [3, 3]), "h1\th2\n01\t02\n11\t12\n");
+    }
+
+    #[test]
+    fn copy_clamped() {
+        let selection = Selection {
+            anchor: [1, 1],
+            cursor: [5, 5],
+        };
+        assert_eq!(copy(selection, [2, 3]), "h1\th2\n11\t12\n");
+        assert_eq!(copy(Selection::new(4, 4), [2, 3]), "\n");
+    }
+}
@@ -0,0 +1,97 @@
+use super::Settings;
+use crate::app::computers::source::clustering::Dendrogram;
+use egui::{Color32, Ui};
+use egui_ext::color;
+use egui_l20n::UiExt as _;
+use egui_plot::{GridMark, HLine, Line, LineStyle, Plot, PlotPoints};
+use lipid::fatty_acid::display::{COMMON, DisplayWithOptions as _};
+
+/// Dendrogram view
+pub(crate) struct DendrogramView<'a> {
+    pub(crate) dendrogram: &'a Dendrogram,
+    pub(crate) settings: &'a Settings,
+}
+
+impl<'a> DendrogramView<'a> {
+    pub(crate) const fn new(dendrogram: &'a Dendrogram, settings: &'a Settings) -> Self {
+        Self {
+            dendrogram,
+            settings,
+        }
+    }
+}
+
+impl DendrogramView<'_> {
+    pub(crate) fn show(self, ui: &mut Ui) {
+        let len = self.dendrogram.fatty_acids.len();
+        // Fatty acids without common values are merged at the top
+        let top = self
+            .dendrogram
+            .merges
+            .iter()
+            .map(|merge| merge.distance)
+            .filter(|distance| distance.is_finite())
+            .fold(0.0, f64::max)
+            * 1.1;
+        let height = |distance: f64| if distance.is_finite() { distance } else { top };
+        // Position, height and cluster of the leaves and the merges
+        let mut nodes = vec![(0.0, 0.0, None); len + self.dendrogram.merges.len()];
+        for (position, &leaf) in self.dendrogram.order.iter().enumerate() {
+            nodes[leaf] = (position as f64, 0.0, Some(self.dendrogram.clusters[leaf]));
+        }
+        let names = self
+            .dendrogram
+            .order
+            .iter()
+            .map(|&leaf| format!("{:#}", (&self.dendrogram.fatty_acids[leaf]).display(COMMON)))
+            .collect::<Vec<_>>();
+        let precision = self.settings.precision;
+        let distance = ui.localize("distance");
+        let cluster_text = ui.localize("cluster");
+        Plot::new(ui.auto_id_with("Dendrogram"))
+            .y_axis_label(&distance)
+            .x_axis_formatter(move |GridMark { value, .. }, _| {
+                if value.fract() != 0.0 || value < 0.0 {
+                    return String::new();
+                }
+                names.get(value as usize).cloned().unwrap_or_default()
+            })
+            .label_formatter(move |name, point| {
+                if name.is_empty() {
+                    return String::new();
+                }
+                format!("{name}\n{distance} = {:.precision$}", point.y)
+            })
+            .show(ui, |ui| {
+                for (index, merge) in self.dendrogram.merges.iter().enumerate() {
+                    let (left, left_height, left_cluster) = nodes[merge.left];
+                    let (right, right_height, right_cluster) = nodes[merge.right];
+                    let merge_height = height(merge.distance);
+                    let cluster = left_cluster.filter(|_| left_cluster == right_cluster);
+                    nodes[len + index] = ((left + right) / 2.0, merge_height, cluster);
+                    let points = vec![
+                        [left, left_height],
+                        [left, merge_height],
+                        [right, merge_height],
+                        [right, right_height],
+                    ];
+                    let mut line = Line::new(PlotPoints::new(points)).name(format!(
+                        "{cluster_text} {}",
+                        cluster.map_or_else(|| "-".to_owned(), |cluster| cluster.to_string()),
+                    ));
+                    line = match cluster {
+                        Some(cluster) => line.color(color(cluster as usize - 1)),
+                        None => line.color(Color32::GRAY),
+                    };
+                    ui.line(line);
+                }
+                if let Some(cut) = self.dendrogram.cut() {
+                    ui.hline(
+                        HLine::new(cut)
+                            .color(Color32::RED)
+                            .style(LineStyle::Dashed { length: 8.0 }),
+                    );
+                }
+            });
+    }
+}
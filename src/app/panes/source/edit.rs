@@ -0,0 +1,614 @@
+use super::ID_SOURCE;
+use crate::{app::panes::MARGIN, special::name::Name};
+use anyhow::{Context as _, Result, ensure};
+use egui::{
+    Color32, Frame, Id, Key, Label, Margin, RichText, ScrollArea, Sense, TextEdit, TextStyle, Ui,
+    vec2,
+};
+use egui_l20n::{ResponseExt as _, UiExt as _};
+use egui_phosphor::regular::{ARROW_COUNTER_CLOCKWISE, HASH};
+use egui_table::{AutoSizeMode, CellInfo, Column, HeaderCellInfo, HeaderRow, Table, TableDelegate};
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// Editor of the source data
+///
+/// The edits are applied to the source data frame, each edited cell keeps
+/// its original value to be reverted. The edits are kept for the source hash
+/// only, the replaced source clears them.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub(crate) struct Editor {
+    pub(crate) edits: Vec<Edit>,
+    #[serde(default)]
+    hash: u64,
+    #[serde(skip)]
+    editing: Option<Editing>,
+    #[serde(skip)]
+    texts: Option<Texts>,
+}
+
+impl Editor {
+    pub(crate) fn new() -> Self {
+        Self {
+            edits: Vec::new(),
+            hash: 0,
+            editing: None,
+            texts: None,
+        }
+    }
+
+    /// Change log of the edits of the source hash, separated by the
+    /// semicolons
+    pub(crate) fn log(&self, hash: u64) -> Option<String> {
+        if self.hash != hash || self.edits.is_empty() {
+            return None;
+        }
+        Some(
+            self.edits
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("; "),
+        )
+    }
+
+    /// Keeps the edits over the source changed by the editor itself
+    pub(crate) fn rehash(&mut self, from: u64, to: u64) {
+        if self.hash == from {
+            self.hash = to;
+        }
+    }
+
+    /// Editable table of the data frame and the change log, returns whether
+    /// the data frame changed
+    pub(crate) fn show(
+        &mut self,
+        ui: &mut Ui,
+        data_frame: &mut DataFrame,
+        hash: u64,
+    ) -> PolarsResult<bool> {
+        if self.hash != hash {
+            self.edits.clear();
+            self.editing = None;
+            self.hash = hash;
+        }
+        let texts = match self.texts.take() {
+            Some(texts) if texts.hash == hash => texts,
+            _ => Texts::new(data_frame, hash)?,
+        };
+        let mut changed = false;
+        let mut table = CellsTable {
+            editor: self,
+            texts: &texts,
+            commit: None,
+        };
+        let height = ui.available_height() * 2.0 / 3.0;
+        ui.allocate_ui(vec2(ui.available_width(), height), |ui| {
+            table.show(ui);
+        });
+        let commit = table.commit;
+        self.texts = Some(texts);
+        if let Some((row, cell, text)) = commit {
+            match self.commit(data_frame, row, cell, &text) {
+                Ok(()) => {
+                    self.editing = None;
+                    changed = true;
+                }
+                Err(error) => {
+                    if let Some(editing) = &mut self.editing {
+                        editing.error = Some(format!("{error:#}"));
+                    }
+                }
+            }
+        }
+        if let Some(error) = self
+            .editing
+            .as_ref()
+            .and_then(|editing| editing.error.as_ref())
+        {
+            ui.colored_label(Color32::RED, error);
+        }
+        // Change log
+        ui.separator();
+        ui.label(format!("{}: {}", ui.localize("changes"), self.edits.len()));
+        let mut revert = None;
+        ScrollArea::vertical().id_salt("Changes").show(ui, |ui| {
+            for (index, edit) in self.edits.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(ARROW_COUNTER_CLOCKWISE)
+                        .on_hover_localized("revert")
+                        .clicked()
+                    {
+                        revert = Some(index);
+                    }
+                    ui.label(edit.to_string());
+                });
+            }
+        });
+        if let Some(index) = revert {
+            let edit = self.edits.remove(index);
+            if let Err(error) = edit.cell.set(data_frame, edit.row, &edit.from) {
+                ui.colored_label(Color32::RED, format!("{error:#}"));
+                self.edits.insert(index, edit);
+            } else {
+                changed = true;
+            }
+        }
+        Ok(changed)
+    }
+
+    /// Cell of the table, returns the text to commit
+    fn cell_ui(&mut self, ui: &mut Ui, row: usize, cell: Cell, text: &str) -> Option<String> {
+        if let Some(editing) = self
+            .editing
+            .as_mut()
+            .filter(|editing| editing.row == row && editing.cell == cell)
+        {
+            let response = ui.add(
+                TextEdit::singleline(&mut editing.text)
+                    .desired_width(ui.spacing().interact_size.x * 2.0),
+            );
+            if !response.has_focus() && !response.lost_focus() {
+                response.request_focus();
+            }
+            if ui.input(|input| input.key_pressed(Key::Escape)) {
+                self.editing = None;
+            } else if response.lost_focus() && ui.input(|input| input.key_pressed(Key::Enter)) {
+                return Some(editing.text.clone());
+            }
+            return None;
+        }
+        let edit = self
+            .edits
+            .iter()
+            .find(|edit| edit.row == row && edit.cell == cell);
+        let mut rich_text = RichText::new(text).monospace();
+        if edit.is_some() {
+            rich_text = rich_text.background_color(ui.visuals().warn_fg_color.gamma_multiply(0.25));
+        }
+        let mut response = ui
+            .add(Label::new(rich_text).sense(Sense::click()))
+            .on_hover_localized("edit.hover");
+        if let Some(edit) = edit {
+            response = response.on_hover_text(format!("{} {}", ui.localize("original"), edit.from));
+        }
+        if response.clicked() {
+            self.editing = Some(Editing {
+                row,
+                cell,
+                text: text.to_owned(),
+                error: None,
+            });
+        }
+        None
+    }
+
+    /// Sets the cell and records the edit, the original value of the cell
+    /// is kept over the repeated edits, the unchanged text is skipped
+    fn commit(
+        &mut self,
+        data_frame: &mut DataFrame,
+        row: usize,
+        cell: Cell,
+        text: &str,
+    ) -> Result<()> {
+        let from = cell.get(data_frame, row)?;
+        if text.trim() == from {
+            return Ok(());
+        }
+        cell.set(data_frame, row, text)?;
+        let to = cell.get(data_frame, row)?;
+        match self
+            .edits
+            .iter()
+            .position(|edit| edit.row == row && edit.cell == cell)
+        {
+            Some(index) if self.edits[index].from == to => {
+                self.edits.remove(index);
+            }
+            Some(index) => self.edits[index].to = to,
+            None if from != to => self.edits.push(Edit {
+                row,
+                cell,
+                from,
+                to,
+            }),
+            None => {}
+        }
+        Ok(())
+    }
+}
+
+/// Texts of the editable cells, computed for the source hash
+#[derive(Clone, Debug)]
+struct Texts {
+    hash: u64,
+    rows: usize,
+    cells: Vec<Cell>,
+    texts: Vec<Vec<String>>,
+}
+
+impl Texts {
+    fn new(data_frame: &DataFrame, hash: u64) -> PolarsResult<Self> {
+        let replicates = data_frame["RetentionTime"]
+            .list()?
+            .into_iter()
+            .map(|values| values.map_or(0, |values| values.len()))
+            .max()
+            .unwrap_or_default();
+        let mut cells = vec![
+            Cell::FattyAcid,
+            Cell::OnsetTemperature,
+            Cell::TemperatureStep,
+            Cell::DeadTime,
+        ];
+        cells.extend((0..replicates).map(Cell::RetentionTime));
+        let texts = cells
+            .iter()
+            .map(|cell| cell.texts(data_frame))
+            .collect::<PolarsResult<_>>()?;
+        Ok(Self {
+            hash,
+            rows: data_frame.height(),
+            cells,
+            texts,
+        })
+    }
+}
+
+/// Table of the editable cells, the visible rows only are shown
+struct CellsTable<'a> {
+    editor: &'a mut Editor,
+    texts: &'a Texts,
+    commit: Option<(usize, Cell, String)>,
+}
+
+impl CellsTable<'_> {
+    fn show(&mut self, ui: &mut Ui) {
+        let height = ui.text_style_height(&TextStyle::Heading) + 2.0 * MARGIN.y;
+        let num_columns = self.texts.cells.len() + 1;
+        Table::new()
+            .id_salt(Id::new(ID_SOURCE).with("Edit"))
+            .num_rows(self.texts.rows as _)
+            .columns(vec![Column::default().resizable(true); num_columns])
+            .num_sticky_cols(1)
+            .headers([HeaderRow::new(height)])
+            .auto_size_mode(AutoSizeMode::OnParentResize)
+            .show(ui, self);
+    }
+}
+
+impl TableDelegate for CellsTable<'_> {
+    fn header_cell_ui(&mut self, ui: &mut Ui, cell: &HeaderCellInfo) {
+        Frame::new()
+            .inner_margin(Margin::from(MARGIN))
+            .show(ui, |ui| match cell.col_range.start {
+                0 => {
+                    ui.heading(HASH).on_hover_localized("index");
+                }
+                column => match self.texts.cells[column - 1] {
+                    Cell::RetentionTime(index) => {
+                        ui.heading(format!("{} {}", ui.localize("retention-time"), index + 1));
+                    }
+                    cell => {
+                        ui.heading(ui.localize(cell.text()));
+                    }
+                },
+            });
+    }
+
+    fn cell_ui(&mut self, ui: &mut Ui, cell: &CellInfo) {
+        if cell.row_nr % 2 == 0 {
+            ui.painter()
+                .rect_filled(ui.max_rect(), 0.0, ui.visuals().faint_bg_color);
+        }
+        let row = cell.row_nr as usize;
+        Frame::new()
+            .inner_margin(Margin::from(MARGIN))
+            .show(ui, |ui| match cell.col_nr {
+                0 => {
+                    ui.label(row.to_string());
+                }
+                column => {
+                    let index = column - 1;
+                    let text = &self.texts.texts[index][row];
+                    if let Some(text) = self.editor.cell_ui(ui, row, self.texts.cells[index], text)
+                    {
+                        self.commit = Some((row, self.texts.cells[index], text));
+                    }
+                }
+            });
+    }
+}
+
+/// Edit of the source cell
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub(crate) struct Edit {
+    pub(crate) row: usize,
+    pub(crate) cell: Cell,
+    pub(crate) from: String,
+    pub(crate) to: String,
+}
+
+impl Display for Edit {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} {}: {} → {}", self.row, self.cell, self.from, self.to)
+    }
+}
+
+/// Editable cell of the source data
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub(crate) enum Cell {
+    FattyAcid,
+    OnsetTemperature,
+    TemperatureStep,
+    DeadTime,
+    RetentionTime(usize),
+}
+
+impl Cell {
+    fn text(&self) -> &'static str {
+        match self {
+            Self::FattyAcid => "fatty-acid",
+            Self::OnsetTemperature => "onset-temperature",
+            Self::TemperatureStep => "temperature-step",
+            Self::DeadTime => "dead-time",
+            Self::RetentionTime(_) => "retention-time",
+        }
+    }
+
+    /// Text of the cell in the row, the row only is decoded
+    fn get(&self, data_frame: &DataFrame, row: usize) -> PolarsResult<String> {
+        let mut texts = self.texts(&data_frame.slice(row as _, 1))?;
+        polars_ensure!(!texts.is_empty(), OutOfBounds: "row {row} is out of bounds");
+        Ok(texts.swap_remove(0))
+    }
+
+    /// Texts of the cells in all rows
+    fn texts(&self, data_frame: &DataFrame) -> PolarsResult<Vec<String>> {
+        let text = |value: Option<f64>| value.map_or_else(String::new, |value| value.to_string());
+        Ok(match self {
+            Self::FattyAcid => Name::from_series(data_frame["FattyAcid"].as_materialized_series())?
+                .into_iter()
+                .map(|name| name.map_or_else(String::new, |name| name.to_string()))
+                .collect(),
+            Self::OnsetTemperature | Self::TemperatureStep | Self::DeadTime => {
+                let series = match self {
+                    Self::DeadTime => data_frame["DeadTime"].as_materialized_series().clone(),
+                    _ => data_frame["Mode"].struct_()?.field_by_name(self.field())?,
+                };
+                series
+                    .cast(&DataType::Float64)?
+                    .f64()?
+                    .into_iter()
+                    .map(text)
+                    .collect()
+            }
+            &Self::RetentionTime(index) => data_frame["RetentionTime"]
+                .list()?
+                .into_iter()
+                .map(|values| -> PolarsResult<String> {
+                    Ok(match values {
+                        Some(values) if index < values.len() => {
+                            text(values.cast(&DataType::Float64)?.f64()?.get(index))
+                        }
+                        _ => String::new(),
+                    })
+                })
+                .collect::<PolarsResult<_>>()?,
+        })
+    }
+
+    /// Sets the validated text to the cell, the data frame is unchanged on
+    /// the error
+    ///
+    /// The name has the double bonds only, so the bonds of the fatty acid at
+    /// the same positions keep their unsaturation.
+    fn set(&self, data_frame: &mut DataFrame, row: usize, text: &str) -> Result<()> {
+        let series = match self {
+            Self::FattyAcid => {
+                let mut name = Name::parse(text)?;
+                let series = data_frame["FattyAcid"].as_materialized_series();
+                if let Some(Some(previous)) = Name::from_series(&series.slice(row as _, 1))?.pop() {
+                    for bond in &mut name.bonds {
+                        if let Some(previous) = previous
+                            .bonds
+                            .iter()
+                            .find(|previous| previous.index == bond.index)
+                        {
+                            bond.unsaturation = previous.unsaturation;
+                        }
+                    }
+                }
+                let value = Name::to_series(PlSmallStr::EMPTY, &[name])?;
+                replace(series, row, &value)?
+            }
+            Self::OnsetTemperature | Self::TemperatureStep => {
+                let value = number(text)?;
+                if *self == Self::TemperatureStep {
+                    ensure!(value > 0.0, "temperature step {value} is not positive");
+                }
+                let value = Series::new(PlSmallStr::EMPTY, [value]);
+                let mode = data_frame["Mode"].as_materialized_series();
+                let fields = mode
+                    .struct_()?
+                    .fields_as_series()
+                    .into_iter()
+                    .map(|field| {
+                        if field.name() == self.field() {
+                            replace(&field, row, &value)
+                        } else {
+                            Ok(field)
+                        }
+                    })
+                    .collect::<PolarsResult<Vec<_>>>()?;
+                StructChunked::from_series(mode.name().clone(), mode.len(), fields.iter())?
+                    .into_series()
+            }
+            Self::DeadTime => {
+                let value = number(text)?;
+                ensure!(value >= 0.0, "dead time {value} is negative");
+                let value = Series::new(PlSmallStr::EMPTY, [value]);
+                replace(data_frame["DeadTime"].as_materialized_series(), row, &value)?
+            }
+            &Self::RetentionTime(index) => {
+                let value = number(text)?;
+                ensure!(value > 0.0, "retention time {value} is not positive");
+                let series = data_frame["RetentionTime"].as_materialized_series();
+                let mut values = series.list()?.get_as_series(row).map_or(
+                    Ok(Vec::new()),
+                    |values| -> PolarsResult<Vec<_>> {
+                        Ok(values
+                            .cast(&DataType::Float64)?
+                            .f64()?
+                            .into_iter()
+                            .collect())
+                    },
+                )?;
+                ensure!(index < values.len(), "no retention time {}", index + 1);
+                values[index] = Some(value);
+                let value = [Some(Series::new(PlSmallStr::EMPTY, values))]
+                    .into_iter()
+                    .collect::<ListChunked>()
+                    .into_series();
+                replace(series, row, &value)?
+            }
+        };
+        data_frame.with_column(series)?;
+        Ok(())
+    }
+
+    /// Field of the mode
+    fn field(&self) -> &'static str {
+        match self {
+            Self::TemperatureStep => "TemperatureStep",
+            _ => "OnsetTemperature",
+        }
+    }
+}
+
+impl Display for Cell {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::FattyAcid => f.write_str("FattyAcid"),
+            Self::OnsetTemperature => f.write_str("Mode.OnsetTemperature"),
+            Self::TemperatureStep => f.write_str("Mode.TemperatureStep"),
+            Self::DeadTime => f.write_str("DeadTime"),
+            Self::RetentionTime(index) => write!(f, "RetentionTime[{index}]"),
+        }
+    }
+}
+
+/// Cell in the editing
+#[derive(Clone, Debug)]
+struct Editing {
+    row: usize,
+    cell: Cell,
+    text: String,
+    error: Option<String>,
+}
+
+/// Series with the row replaced by the value
+fn replace(series: &Series, row: usize, value: &Series) -> PolarsResult<Series> {
+    let mut target = series.slice(0, row);
+    target.append(&value.cast(series.dtype())?)?;
+    target.append(&series.slice(row as i64 + 1, series.len() - row - 1))?;
+    Ok(target)
+}
+
+fn number(text: &str) -> Result<f64> {
+    let value = text
+        .trim()
+        .replace(',', ".")
+        .parse::<f64>()
+        .with_context(|| format!("invalid number {text:?}"))?;
+    ensure!(value.is_finite(), "invalid number {text:?}");
+    Ok(value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn data_frame() -> DataFrame {
+        let mut names = vec![
+            Name::parse("18:2Δ9c,12c").unwrap(),
+            Name::parse("18:2Δ9c,15").unwrap(),
+        ];
+        names[1].bonds[1].unsaturation = 2;
+        let fatty_acid = Name::to_series("FattyAcid".into(), &names).unwrap();
+        let retention_time = [
+            Some(Series::new(PlSmallStr::EMPTY, [Some(1.0), None, Some(2.0)])),
+            Some(Series::new(PlSmallStr::EMPTY, [Some(3.0)])),
+        ]
+        .into_iter()
+        .collect::<ListChunked>()
+        .with_name("RetentionTime".into())
+        .into_series();
+        DataFrame::new(vec![fatty_acid.into_column(), retention_time.into_column()]).unwrap()
+    }
+
+    fn name(data_frame: &DataFrame, row: usize) -> Name {
+        Name::from_series(data_frame["FattyAcid"].as_materialized_series()).unwrap()[row]
+            .clone()
+            .unwrap()
+    }
+
+    #[test]
+    fn keep_unsaturation() {
+        let mut data_frame = data_frame();
+        Cell::FattyAcid
+            .set(&mut data_frame, 1, "18:2Δ9t,15")
+            .unwrap();
+        let name = name(&data_frame, 1);
+        assert_eq!(name.bonds[0].isomerism, -1);
+        assert_eq!(name.bonds[0].unsaturation, 1);
+        assert_eq!(name.bonds[1].unsaturation, 2);
+    }
+
+    #[test]
+    fn skip_unchanged() {
+        let mut data_frame = data_frame();
+        let mut editor = Editor::new();
+        let text = Cell::FattyAcid.get(&data_frame, 1).unwrap();
+        editor
+            .commit(&mut data_frame, 1, Cell::FattyAcid, &text)
+            .unwrap();
+        assert!(editor.edits.is_empty());
+        assert_eq!(name(&data_frame, 1).bonds[1].unsaturation, 2);
+    }
+
+    #[test]
+    fn keep_null_replicates() {
+        let mut data_frame = data_frame();
+        Cell::RetentionTime(2)
+            .set(&mut data_frame, 0, "2.5")
+            .unwrap();
+        let values = data_frame["RetentionTime"]
+            .list()
+            .unwrap()
+            .get_as_series(0)
+            .unwrap();
+        let values = values.f64().unwrap().into_iter().collect::<Vec<_>>();
+        assert_eq!(values, [Some(1.0), None, Some(2.5)]);
+        assert_eq!(Cell::RetentionTime(2).get(&data_frame, 0).unwrap(), "2.5");
+    }
+
+    #[test]
+    fn stale_log() {
+        let mut data_frame = data_frame();
+        let mut editor = Editor::new();
+        assert_eq!(editor.log(0), None);
+        editor
+            .commit(&mut data_frame, 1, Cell::RetentionTime(0), "3.5")
+            .unwrap();
+        assert!(editor.log(0).is_some());
+        editor.rehash(0, 1);
+        assert!(editor.log(1).is_some());
+        assert_eq!(editor.log(2), None);
+        editor.rehash(0, 2);
+        assert_eq!(editor.log(2), None);
+    }
+}
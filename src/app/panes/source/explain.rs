@@ -0,0 +1,238 @@
+use super::settings::{Settings, TableColumn};
+use crate::app::panes::explain::{Explanation, Key, find, value};
+use egui::Ui;
+use lipid::{
+    fatty_acid::display::{COMMON, DisplayWithOptions as _},
+    prelude::*,
+};
+use polars::prelude::*;
+
+/// Whether the table column is explained
+pub(super) const fn is_explained(column: TableColumn) -> bool {
+    matches!(
+        column,
+        TableColumn::RelativeRetentionTime
+            | TableColumn::DeltaRetentionTime
+            | TableColumn::Temperature
+            | TableColumn::EquivalentChainLength
+            | TableColumn::FractionalChainLength
+            | TableColumn::Slope
+    )
+}
+
+/// Explanation of the value of the table column in the row with the key
+pub(super) fn show(
+    ui: &mut Ui,
+    explained: &DataFrame,
+    key: &Key,
+    column: TableColumn,
+    settings: &Settings,
+) -> PolarsResult<()> {
+    if let Some(row) = find(explained, key)? {
+        if let Some(explanation) = explanation(explained, row, column, settings)? {
+            explanation.show(ui);
+        }
+    }
+    Ok(())
+}
+
+/// Explanation of the value of the table column in the row of the explained
+/// frame
+fn explanation(
+    explained: &DataFrame,
+    row: usize,
+    column: TableColumn,
+    settings: &Settings,
+) -> PolarsResult<Option<Explanation>> {
+    let formula = match column {
+        TableColumn::RelativeRetentionTime => "RRT = t̄ / t̄ᵣ",
+        TableColumn::DeltaRetentionTime => "Δt = tₙ₊₁ − tₙ",
+        TableColumn::Temperature => "T = min(T(t̄), Tₘₐₓ)",
+        TableColumn::EquivalentChainLength if settings.logarithmic => {
+            "ECL = ECLₙ + (ECLₙ₊₁ − ECLₙ) · (ln t̄ − ln tₙ) / (ln tₙ₊₁ − ln tₙ)"
+        }
+        TableColumn::EquivalentChainLength => {
+            "ECL = ECLₙ + (ECLₙ₊₁ − ECLₙ) · (t̄ − tₙ) / (tₙ₊₁ − tₙ)"
+        }
+        TableColumn::FractionalChainLength => "FCL(t̄)",
+        TableColumn::Slope => "Slope = (ECLₙ₊₁ − ECLₙ) / (tₙ₊₁ − tₙ)",
+        _ => return Ok(None),
+    };
+    let precision = settings.precision;
+    let float = |series: &Series| -> PolarsResult<Option<f64>> {
+        Ok(series.cast(&DataType::Float64)?.f64()?.get(row))
+    };
+    let fatty_acid = |series: &Series| -> PolarsResult<String> {
+        Ok(match series.fa().get(row)? {
+            Some(fatty_acid) => format!("{:#}", fatty_acid.display(COMMON)),
+            None => "—".to_owned(),
+        })
+    };
+    let field = |name: &str, field: &str| -> PolarsResult<Series> {
+        explained[name].struct_()?.field_by_name(field)
+    };
+    let column_value = |name: &str| float(explained[name].as_materialized_series());
+    let retention_time = column_value("RetentionTimeMean")?;
+    let mut explanation = Explanation::new(Some(formula));
+    explanation
+        .entry(
+            "fatty-acid",
+            "",
+            fatty_acid(explained["FattyAcid"].as_materialized_series())?,
+        )
+        .entry("column", "", explained["Column"].str_value(row)?)
+        .entry(
+            "onset-temperature",
+            "T₀",
+            value(float(&field("Mode", "OnsetTemperature")?)?, precision),
+        )
+        .entry(
+            "temperature-step",
+            "β",
+            value(float(&field("Mode", "TemperatureStep")?)?, precision),
+        )
+        .entry(
+            "dead-time",
+            "t₀",
+            value(column_value("DeadTime")?, precision),
+        )
+        .entry("count", "n", value(column_value("Count")?, 0))
+        .entry(
+            "delta-degrees-of-freedom",
+            "ddof",
+            settings.ddof.to_string(),
+        )
+        .entry(
+            "standard-deviation",
+            "s",
+            value(column_value("RetentionTimeStandardDeviation")?, precision),
+        )
+        .entry("retention-time", "t̄", value(retention_time, precision));
+    match column {
+        TableColumn::RelativeRetentionTime => {
+            let relative = settings.relative.as_ref().map_or_else(
+                || "—".to_owned(),
+                |relative| format!("{:#}", relative.display(COMMON)),
+            );
+            explanation
+                .entry("relative-fatty-acid", "", relative)
+                .entry(
+                    "retention-time",
+                    "t̄ᵣ",
+                    value(column_value("ReferenceRetentionTime")?, precision),
+                )
+                .entry(
+                    "relative-retention-time",
+                    "RRT",
+                    value(column_value("RelativeRetentionTime")?, precision),
+                );
+        }
+        TableColumn::Temperature => {
+            explanation
+                .entry(
+                    "maximum-temperature",
+                    "Tₘₐₓ",
+                    value(Some(settings.conditions.maximum_temperature), precision),
+                )
+                .entry(
+                    "temperature",
+                    "T",
+                    value(column_value("Temperature")?, precision),
+                );
+        }
+        _ => {
+            let previous = float(&field("Previous", "RetentionTime")?)?;
+            let next = float(&field("Next", "RetentionTime")?)?;
+            let previous_chain_length = float(&field("Previous", "EquivalentChainLength")?)?;
+            let next_chain_length = float(&field("Next", "EquivalentChainLength")?)?;
+            explanation
+                .entry(
+                    "previous-saturated",
+                    "",
+                    fatty_acid(&field("Previous", "FattyAcid")?)?,
+                )
+                .entry("retention-time", "tₙ", value(previous, precision))
+                .entry(
+                    "equivalent-chain-length",
+                    "ECLₙ",
+                    value(previous_chain_length, precision),
+                )
+                .entry(
+                    "next-saturated",
+                    "",
+                    fatty_acid(&field("Next", "FattyAcid")?)?,
+                )
+                .entry("retention-time", "tₙ₊₁", value(next, precision))
+                .entry(
+                    "equivalent-chain-length",
+                    "ECLₙ₊₁",
+                    value(next_chain_length, precision),
+                );
+            match column {
+                TableColumn::DeltaRetentionTime => {
+                    explanation.entry(
+                        "delta-retention-time",
+                        "Δt",
+                        value(column_value("DeltaRetentionTime")?, precision),
+                    );
+                }
+                TableColumn::EquivalentChainLength => {
+                    let scale = |time: f64| {
+                        if settings.logarithmic {
+                            time.ln()
+                        } else {
+                            time
+                        }
+                    };
+                    let interpolation = match (retention_time, previous, next) {
+                        (Some(time), Some(previous), Some(next)) => {
+                            Some((scale(time) - scale(previous)) / (scale(next) - scale(previous)))
+                        }
+                        _ => None,
+                    };
+                    explanation
+                        .entry(
+                            "logarithm-of-the-retention-time",
+                            "ln",
+                            settings.logarithmic.to_string(),
+                        )
+                        .entry("interpolation", "x", value(interpolation, precision))
+                        .entry(
+                            "equivalent-chain-length",
+                            "ECL",
+                            value(column_value("EquivalentChainLength")?, precision),
+                        );
+                }
+                TableColumn::FractionalChainLength => {
+                    explanation
+                        .entry(
+                            "logarithm-of-the-retention-time",
+                            "ln",
+                            settings.logarithmic.to_string(),
+                        )
+                        .entry(
+                            "fractional-chain-length",
+                            "FCL",
+                            value(column_value("FCL")?, precision),
+                        );
+                }
+                _ => {
+                    let delta = |from: Option<f64>, to: Option<f64>| Some(to? - from?);
+                    explanation
+                        .entry(
+                            "equivalent-chain-length-difference",
+                            "ΔECL",
+                            value(delta(previous_chain_length, next_chain_length), precision),
+                        )
+                        .entry(
+                            "delta-retention-time",
+                            "Δt",
+                            value(delta(previous, next), precision),
+                        )
+                        .entry("slope", "Slope", value(column_value("Slope")?, precision));
+                }
+            }
+        }
+    }
+    Ok(Some(explanation))
+}
@@ -1,26 +1,44 @@
 use self::{
+    clustering::DendrogramView,
+    pivot::PivotView,
     plot::PlotView,
-    settings::{Settings, View},
+    settings::{Field, Settings, View},
     state::State,
-    table::TableView,
+    table::{Cells, TableView},
+};
+use super::{
+    formatting::Styles,
+    preset::Preset,
+    properties::Properties,
+    widgets::{ConditionsWidget, PresetsWidget, ViewWidget, float::FloatValue},
 };
 use crate::{
-    app::computers::{SourceComputed, SourceKey, SourcePlotComputed, SourcePlotKey},
-    utils::save,
+    app::computers::{
+        FormattingComputed, FormattingKey, GroupingComputed, GroupingKey, SourceClusteringComputed,
+        SourceClusteringKey, SourceComputed, SourceExplainComputed, SourceExplainKey, SourceKey,
+        SourcePivotComputed, SourcePivotKey, SourcePlotComputed, SourcePlotKey, formatting,
+        grouping, source::field,
+    },
+    localization::Text as _,
+    special::{column::mode::ColumnExt as _, data_frame::DataFrameExt as _},
+    utils::{save, save_csv},
+};
+use egui::{
+    Button, CollapsingHeader, CursorIcon, DragValue, Grid, Id, Response, RichText, Ui, Window,
+    util::hash,
 };
-use egui::{Button, CursorIcon, Id, Response, RichText, Ui, Window, util::hash};
-use egui_l20n::UiExt as _;
+use egui_l20n::{ResponseExt as _, UiExt as _};
 use egui_phosphor::regular::{
-    ARROWS_CLOCKWISE, ARROWS_HORIZONTAL, EXCLUDE, FLOPPY_DISK, GEAR, TABLE,
+    ARROWS_CLOCKWISE, ARROWS_HORIZONTAL, EXCLUDE, FLOPPY_DISK, GEAR, INFO, PENCIL_SIMPLE, SCALES,
+    SWAP, TABLE, TIMER, TREE_STRUCTURE,
 };
-use metadata::MetaDataFrame;
+use metadata::{MetaDataFrame, Metadata};
 use polars::prelude::*;
 use serde::{Deserialize, Serialize};
 use tracing::error;
 
-use super::widgets::ViewWidget;
-
 const ID_SOURCE: &str = "Source";
+const CHANGES: &str = "Changes";
 
 /// Source pane
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -50,7 +68,21 @@ impl Pane {
         self.source.frame.meta.title()
     }
 
-    pub(super) fn header(&mut self, ui: &mut Ui) -> Response {
+    /// Metadata with the change log of the edits in the `Changes` property,
+    /// the log of the previous save is replaced and removed without the
+    /// edits
+    fn meta(&self) -> Metadata {
+        let mut meta = self.source.frame.meta.clone();
+        let mut properties = Properties::parse(&meta.description);
+        match self.state.editor.log(self.source.hash) {
+            Some(log) => properties.set(CHANGES, log),
+            None => properties.pairs.retain(|(key, _)| key != CHANGES),
+        }
+        meta.description = properties.to_string();
+        meta
+    }
+
+    pub(super) fn header(&mut self, ui: &mut Ui, presets: &mut Vec<Preset>) -> Response {
         let mut response = ui
             .heading(Self::icon())
             .on_hover_text(ui.localize("source"));
@@ -79,10 +111,52 @@ impl Pane {
             &mut self.state.open_settings_window,
             RichText::new(GEAR).heading(),
         );
+        // Presets
+        ui.add(PresetsWidget::new(presets, &mut self.settings));
+        ui.separator();
+        // Edit
+        ui.toggle_value(
+            &mut self.state.open_edit_window,
+            RichText::new(PENCIL_SIMPLE).heading(),
+        )
+        .on_hover_localized("edit");
+        // Metadata
+        ui.toggle_value(
+            &mut self.state.open_metadata_window,
+            RichText::new(INFO).heading(),
+        )
+        .on_hover_localized("metadata");
+        // Dead time
+        ui.toggle_value(
+            &mut self.state.open_dead_time_window,
+            RichText::new(TIMER).heading(),
+        )
+        .on_hover_localized("dead-time-calculator");
+        // Translation
+        ui.toggle_value(
+            &mut self.state.open_translation_window,
+            RichText::new(SWAP).heading(),
+        )
+        .on_hover_localized("method-translation");
+        // Clustering
+        ui.toggle_value(
+            &mut self.state.open_clustering_window,
+            RichText::new(TREE_STRUCTURE).heading(),
+        )
+        .on_hover_localized("clustering");
         ui.separator();
         // View
-        ui.add(ViewWidget::new(&mut self.settings.view));
+        ui.add(ViewWidget::new(&mut self.settings.view).views(&[
+            View::Table,
+            View::Plot,
+            View::Pivot,
+        ]));
         ui.separator();
+        // Search
+        if self.settings.view == View::Table {
+            self.state.search.show(ui);
+            ui.separator();
+        }
         // Distance
         if ui
             .add_enabled(
@@ -94,22 +168,60 @@ impl Pane {
             ui.data_mut(|data| {
                 data.insert_temp(
                     Id::new("Distance"),
-                    MetaDataFrame::new(self.source.frame.meta.clone(), self.target.clone()),
+                    MetaDataFrame::new(self.meta(), self.target.clone()),
+                )
+            })
+        }
+        // Comparison
+        if ui
+            .add_enabled(
+                self.settings.view == View::Table,
+                Button::new(RichText::new(SCALES).heading()),
+            )
+            .on_hover_localized("comparison")
+            .clicked()
+        {
+            ui.data_mut(|data| {
+                data.insert_temp(
+                    Id::new("Comparison"),
+                    MetaDataFrame::new(self.meta(), self.target.clone()),
                 )
             })
         }
         ui.separator();
         // Save
+        if self.settings.view == View::Pivot {
+            let name = format!("{}.pivot.csv", self.source.frame.meta.title());
+            if ui
+                .button(RichText::new(FLOPPY_DISK).heading())
+                .on_hover_text(&name)
+                .clicked()
+            {
+                let value = ui.memory_mut(|memory| {
+                    memory
+                        .caches
+                        .cache::<SourcePivotComputed>()
+                        .get(SourcePivotKey {
+                            data_frame: &self.target,
+                            hash: self.source.hash,
+                            settings: &self.settings,
+                        })
+                });
+                if let Err(error) = save_csv(&name, &value.csv()) {
+                    error!(%error);
+                }
+            }
+            ui.separator();
+            return response;
+        }
         let name = format!("{}.source.ipc", self.source.frame.meta.title());
         if ui
             .button(RichText::new(FLOPPY_DISK).heading())
             .on_hover_text(&name)
             .clicked()
         {
-            if let Err(error) = save(
-                &name,
-                MetaDataFrame::new(&self.source.frame.meta, &mut self.target),
-            ) {
+            let meta = self.meta();
+            if let Err(error) = save(&name, MetaDataFrame::new(&meta, &mut self.target)) {
                 error!(%error);
             }
         }
@@ -119,10 +231,27 @@ impl Pane {
 
     pub(super) fn body(&mut self, ui: &mut Ui) {
         self.window(ui);
+        self.edit_window(ui);
+        self.metadata_window(ui);
+        self.dead_time_window(ui);
+        self.translation_window(ui);
+        self.clustering_window(ui);
         self.target = ui.memory_mut(|memory| {
+            let dendrogram = self.settings.clustering.enabled.then(|| {
+                memory
+                    .caches
+                    .cache::<SourceClusteringComputed>()
+                    .get(SourceClusteringKey {
+                        data_frame: &self.source.frame.data,
+                        hash: self.source.hash,
+                        settings: &self.settings,
+                    })
+            });
             memory.caches.cache::<SourceComputed>().get(SourceKey {
                 data_frame: &self.source.frame.data,
+                hash: self.source.hash,
                 settings: &self.settings,
+                dendrogram: dendrogram.as_ref(),
             })
         });
         match self.settings.view {
@@ -138,7 +267,85 @@ impl Pane {
                 });
                 PlotView::new(points, &self.settings).show(ui)
             }
-            View::Table => TableView::new(&self.target, &self.settings, &mut self.state).show(ui),
+            View::Pivot => {
+                let value = ui.memory_mut(|memory| {
+                    memory
+                        .caches
+                        .cache::<SourcePivotComputed>()
+                        .get(SourcePivotKey {
+                            data_frame: &self.target,
+                            hash: self.source.hash,
+                            settings: &self.settings,
+                        })
+                });
+                PivotView::new(&value, &self.settings, &mut self.state).show(ui)
+            }
+            View::Heatmap | View::Table => {
+                let columns = self
+                    .settings
+                    .layout
+                    .visible()
+                    .map(|(group, column)| (group, *column))
+                    .collect::<Vec<_>>();
+                let cells = Cells {
+                    data_frame: &self.target,
+                    columns: &columns,
+                    precision: self.settings.precision,
+                };
+                let data_frame = self
+                    .state
+                    .search
+                    .apply(
+                        ui,
+                        &self.target,
+                        &cells,
+                        hash((self.source.hash, &self.settings)),
+                    )
+                    .unwrap_or_else(|error| {
+                        error!(%error);
+                        self.target.clone()
+                    });
+                let styles = ui.memory_mut(|memory| {
+                    memory
+                        .caches
+                        .cache::<FormattingComputed<Field>>()
+                        .get(FormattingKey {
+                            data_frame: &data_frame,
+                            hash: hash((
+                                self.source.hash,
+                                &self.settings,
+                                self.state.search.filtered(),
+                            )),
+                            formatting: &self.settings.formatting,
+                            field,
+                            saturated: formatting::fatty_acid,
+                        })
+                });
+                let table = hash((
+                    self.source.hash,
+                    &self.settings,
+                    self.state.search.filtered(),
+                ));
+                let groups = ui.memory_mut(|memory| {
+                    memory.caches.cache::<GroupingComputed>().get(GroupingKey {
+                        data_frame: &data_frame,
+                        hash: table,
+                        grouping: self.settings.grouping,
+                        summary: grouping::source,
+                    })
+                });
+                let rules = self.settings.formatting.rules.clone();
+                TableView::new(
+                    &data_frame,
+                    &mut self.settings,
+                    &mut self.state,
+                    Styles::new(&styles, &rules),
+                    &groups,
+                    table,
+                )
+                .show(ui);
+                self.explain_window(ui);
+            }
         };
     }
 
@@ -152,6 +359,359 @@ impl Pane {
                 }
             });
     }
+
+    fn explain_window(&mut self, ui: &mut Ui) {
+        let Some((key, column)) = &self.state.explain else {
+            return;
+        };
+        let mut open = true;
+        Window::new(format!(
+            "{} {}",
+            ui.localize("explain"),
+            ui.localize(column.text())
+        ))
+        .id(ui.auto_id_with(ID_SOURCE).with("Explain"))
+        .open(&mut open)
+        .show(ui.ctx(), |ui| {
+            let explained = ui.memory_mut(|memory| {
+                memory
+                    .caches
+                    .cache::<SourceExplainComputed>()
+                    .get(SourceExplainKey {
+                        data_frame: &self.source.frame.data,
+                        hash: self.source.hash,
+                        settings: &self.settings,
+                    })
+            });
+            if let Err(error) = explain::show(ui, &explained, key, *column, &self.settings) {
+                error!(%error);
+            }
+        });
+        if !open {
+            self.state.explain = None;
+        }
+    }
+
+    fn edit_window(&mut self, ui: &mut Ui) {
+        Window::new(ui.localize("edit"))
+            .id(ui.auto_id_with(ID_SOURCE).with("Edit"))
+            .open(&mut self.state.open_edit_window)
+            .show(ui.ctx(), |ui| {
+                match self
+                    .state
+                    .editor
+                    .show(ui, &mut self.source.frame.data, self.source.hash)
+                {
+                    Ok(true) => {
+                        let from = self.source.hash;
+                        self.source.hash = hash(&self.source.frame);
+                        self.state.editor.rehash(from, self.source.hash);
+                    }
+                    Ok(false) => {}
+                    Err(error) => error!(%error),
+                }
+            });
+    }
+
+    fn metadata_window(&mut self, ui: &mut Ui) {
+        Window::new(ui.localize("metadata"))
+            .id(ui.auto_id_with(ID_SOURCE).with("Metadata"))
+            .open(&mut self.state.open_metadata_window)
+            .show(ui.ctx(), |ui| {
+                if self
+                    .state
+                    .form
+                    .show(ui, &mut self.source.frame.meta, self.source.hash)
+                {
+                    let from = self.source.hash;
+                    self.source.hash = hash(&self.source.frame);
+                    self.state.editor.rehash(from, self.source.hash);
+                }
+            });
+    }
+
+    fn dead_time_window(&mut self, ui: &mut Ui) {
+        Window::new(ui.localize("dead-time-calculator"))
+            .id(ui.auto_id_with(ID_SOURCE).with("DeadTime"))
+            .open(&mut self.state.open_dead_time_window)
+            .show(ui.ctx(), |ui| {
+                ui.add(ConditionsWidget::new(&mut self.settings.conditions));
+                ui.separator();
+                if let Err(error) = dead_times(ui, &self.source.frame.data, &self.settings) {
+                    error!(%error);
+                }
+                ui.separator();
+                if ui
+                    .button(ui.localize("apply-dead-time"))
+                    .on_hover_localized("apply-dead-time.hover")
+                    .clicked()
+                {
+                    match with_dead_time(&self.source.frame.data, &self.settings) {
+                        Ok(data_frame) => {
+                            self.source.frame.data = data_frame;
+                            self.source.hash = hash(&self.source.frame);
+                        }
+                        Err(error) => error!(%error),
+                    }
+                }
+            });
+    }
+
+    fn clustering_window(&mut self, ui: &mut Ui) {
+        Window::new(ui.localize("clustering"))
+            .id(ui.auto_id_with(ID_SOURCE).with("Clustering"))
+            .open(&mut self.state.open_clustering_window)
+            .show(ui.ctx(), |ui| {
+                ui.checkbox(
+                    &mut self.settings.clustering.enabled,
+                    ui.localize("clustering"),
+                )
+                .on_hover_localized("clustering.hover");
+                if !self.settings.clustering.enabled {
+                    return;
+                }
+                ui.horizontal(|ui| {
+                    ui.label(ui.localize("clusters"))
+                        .on_hover_localized("clusters.hover");
+                    ui.add(
+                        DragValue::new(&mut self.settings.clustering.clusters)
+                            .range(1..=u8::MAX as usize),
+                    );
+                });
+                // Out of range clusters
+                let clusters = self.settings.clustering.clusters;
+                self.settings
+                    .filter
+                    .clusters
+                    .values
+                    .retain(|&cluster| cluster as usize <= clusters);
+                let dendrogram = ui.memory_mut(|memory| {
+                    memory
+                        .caches
+                        .cache::<SourceClusteringComputed>()
+                        .get(SourceClusteringKey {
+                            data_frame: &self.source.frame.data,
+                            hash: self.source.hash,
+                            settings: &self.settings,
+                        })
+                });
+                DendrogramView::new(&dendrogram, &self.settings).show(ui);
+            });
+    }
+
+    fn translation_window(&mut self, ui: &mut Ui) {
+        Window::new(ui.localize("method-translation"))
+            .id(ui.auto_id_with(ID_SOURCE).with("Translation"))
+            .open(&mut self.state.open_translation_window)
+            .show(ui.ctx(), |ui| {
+                CollapsingHeader::new(ui.localize("source-column")).show(ui, |ui| {
+                    ui.add(ConditionsWidget::new(&mut self.settings.conditions));
+                });
+                CollapsingHeader::new(ui.localize("target-column"))
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        ui.add(ConditionsWidget::new(&mut self.settings.translation.target));
+                    });
+                // Speed-up
+                ui.horizontal(|ui| {
+                    let speed_up = &mut self.settings.translation.speed_up;
+                    let mut checked = speed_up.is_some();
+                    if ui
+                        .checkbox(&mut checked, ui.localize("speed-up"))
+                        .on_hover_localized("speed-up.hover")
+                        .changed()
+                    {
+                        *speed_up = checked.then_some(1.0);
+                    }
+                    if let Some(speed_up) = speed_up {
+                        ui.add(DragValue::new(speed_up).range(0.01..=100.0).speed(0.01));
+                    }
+                });
+                if !self
+                    .settings
+                    .translation
+                    .is_exact(&self.settings.conditions)
+                {
+                    ui.colored_label(
+                        ui.visuals().warn_fg_color,
+                        ui.localize("phase-ratio-mismatch"),
+                    );
+                }
+                ui.separator();
+                let reachable = translations(ui, &self.source.frame.data, &self.settings)
+                    .unwrap_or_else(|error| {
+                        error!(%error);
+                        false
+                    });
+                ui.separator();
+                if ui
+                    .add_enabled(reachable, Button::new(ui.localize("translate")))
+                    .on_hover_localized("translate.hover")
+                    .on_disabled_hover_localized("unreachable-speed-up.hover")
+                    .clicked()
+                {
+                    match self
+                        .settings
+                        .translation
+                        .translate(&self.source.frame.data, &self.settings.conditions)
+                    {
+                        Ok(data_frame) => {
+                            let mut meta = self.source.frame.meta.clone();
+                            meta.name = format!("{} ({})", meta.name, ui.localize("translated"));
+                            ui.data_mut(|data| {
+                                data.insert_temp(
+                                    Id::new("Source"),
+                                    MetaDataFrame::new(meta, data_frame),
+                                )
+                            });
+                        }
+                        Err(error) => error!(%error),
+                    }
+                }
+            });
+    }
+}
+
+/// Dead times at the onset temperatures
+fn dead_times(ui: &mut Ui, data_frame: &DataFrame, settings: &Settings) -> PolarsResult<()> {
+    let onset_temperatures = data_frame.mode().onset_temperature()?.unique().sort(false);
+    Grid::new(ui.next_auto_id()).show(ui, |ui| {
+        ui.heading(ui.localize("onset-temperature.abbreviation"))
+            .on_hover_localized("onset-temperature");
+        ui.heading(ui.localize("inlet-pressure"));
+        ui.heading(ui.localize("flow"));
+        ui.heading(ui.localize("dead-time"));
+        ui.end_row();
+        for onset_temperature in onset_temperatures.iter().flatten() {
+            let precision = Some(settings.precision);
+            let conditions = &settings.conditions;
+            ui.label(onset_temperature.to_string());
+            ui.add(
+                FloatValue::new(Some(conditions.inlet_pressure(onset_temperature)))
+                    .precision(precision)
+                    .hover(),
+            );
+            ui.add(
+                FloatValue::new(Some(conditions.flow(onset_temperature)))
+                    .precision(precision)
+                    .hover(),
+            );
+            ui.add(
+                FloatValue::new(Some(conditions.dead_time(onset_temperature)))
+                    .precision(precision)
+                    .hover(),
+            );
+            ui.end_row();
+        }
+    });
+    Ok(())
+}
+
+/// Translated modes, returns whether the target is reachable at all onset
+/// temperatures
+fn translations(ui: &mut Ui, data_frame: &DataFrame, settings: &Settings) -> PolarsResult<bool> {
+    let modes = data_frame["Mode"].unique()?.sort(Default::default())?;
+    let mode = modes.mode();
+    let conditions = &settings.conditions;
+    let translation = &settings.translation;
+    let precision = Some(settings.precision);
+    let mut unreachable = Vec::new();
+    Grid::new(ui.next_auto_id())
+        .show(ui, |ui| -> PolarsResult<()> {
+            ui.heading(ui.localize("onset-temperature.abbreviation"))
+                .on_hover_localized("onset-temperature");
+            ui.heading(ui.localize("temperature-step.abbreviation"))
+                .on_hover_localized("temperature-step");
+            ui.heading(ui.localize("translation-factor.abbreviation"))
+                .on_hover_localized("translation-factor");
+            ui.heading(ui.localize("temperature-step.abbreviation"))
+                .on_hover_localized("translated-temperature-step");
+            ui.heading(ui.localize("inlet-pressure"))
+                .on_hover_localized("inlet-pressure.hover");
+            ui.heading(ui.localize("dead-time.abbreviation"))
+                .on_hover_localized("dead-time.hover");
+            ui.end_row();
+            for (onset_temperature, temperature_step) in mode
+                .onset_temperature
+                .f64()?
+                .iter()
+                .zip(mode.temperature_step.f64()?)
+            {
+                let (Some(onset_temperature), Some(temperature_step)) =
+                    (onset_temperature, temperature_step)
+                else {
+                    continue;
+                };
+                let factor = translation.factor(conditions, onset_temperature);
+                let target = translation.target(conditions, onset_temperature);
+                ui.label(onset_temperature.to_string());
+                ui.label(temperature_step.to_string());
+                ui.add(FloatValue::new(Some(factor)).precision(precision).hover());
+                ui.add(
+                    FloatValue::new(Some(temperature_step * factor))
+                        .precision(precision)
+                        .hover(),
+                );
+                if target.is_none() {
+                    unreachable.push(onset_temperature);
+                }
+                ui.add(
+                    FloatValue::new(target.map(|target| target.inlet_pressure(onset_temperature)))
+                        .precision(precision)
+                        .hover(),
+                );
+                ui.add(
+                    FloatValue::new(target.map(|target| target.dead_time(onset_temperature)))
+                        .precision(precision)
+                        .hover(),
+                );
+                ui.end_row();
+            }
+            Ok(())
+        })
+        .inner?;
+    if !unreachable.is_empty() {
+        ui.colored_label(
+            ui.visuals().error_fg_color,
+            format!(
+                "{}: {}",
+                ui.localize("unreachable-speed-up"),
+                unreachable
+                    .iter()
+                    .map(|temperature| format!("{temperature} °C"))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+        )
+        .on_hover_localized("unreachable-speed-up.hover");
+    }
+    Ok(unreachable.is_empty())
+}
+
+/// Replace the dead time with the calculated at the onset temperature
+fn with_dead_time(data_frame: &DataFrame, settings: &Settings) -> PolarsResult<DataFrame> {
+    let conditions = settings.conditions;
+    data_frame
+        .clone()
+        .lazy()
+        .with_column(
+            col("Mode")
+                .struct_()
+                .field_by_name("OnsetTemperature")
+                .map(
+                    move |column| {
+                        Ok(Some(
+                            column
+                                .f64()?
+                                .apply_values(|temperature| conditions.dead_time(temperature))
+                                .into_column(),
+                        ))
+                    },
+                    GetOutput::from_type(DataType::Float64),
+                )
+                .alias("DeadTime"),
+        )
+        .collect()
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -162,6 +722,10 @@ struct Source {
 
 pub(crate) mod settings;
 
+mod clustering;
+mod edit;
+mod explain;
+mod pivot;
 mod plot;
 mod state;
 mod table;
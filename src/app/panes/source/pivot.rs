@@ -0,0 +1,157 @@
+use super::{ID_SOURCE, State, settings::Settings};
+use crate::{
+    app::{
+        computers::{SourcePivotValue, source::pivot::Mode},
+        panes::{MARGIN, widgets::float::FloatValue},
+    },
+    localization::Text as _,
+};
+use egui::{Frame, Id, Margin, TextStyle, TextWrapMode, Ui};
+use egui_l20n::{ResponseExt as _, UiExt as _};
+use egui_table::{
+    AutoSizeMode, CellInfo, Column, HeaderCellInfo, HeaderRow, Table, TableDelegate, TableState,
+};
+use lipid::fatty_acid::display::{COMMON, DisplayWithOptions as _};
+use std::ops::Range;
+
+const FATTY_ACID: Range<usize> = 0..1;
+
+/// Pivot view
+///
+/// The fatty acids in the rows, the columns and the modes in the hierarchical
+/// headers.
+#[derive(Debug)]
+pub(super) struct PivotView<'a> {
+    value: &'a SourcePivotValue,
+    settings: &'a Settings,
+    state: &'a mut State,
+}
+
+impl<'a> PivotView<'a> {
+    pub(super) const fn new(
+        value: &'a SourcePivotValue,
+        settings: &'a Settings,
+        state: &'a mut State,
+    ) -> Self {
+        Self {
+            value,
+            settings,
+            state,
+        }
+    }
+}
+
+impl PivotView<'_> {
+    pub(super) fn show(&mut self, ui: &mut Ui) {
+        let id_salt = Id::new(ID_SOURCE).with("Pivot");
+        if self.state.reset_table_state {
+            let id = TableState::id(ui, Id::new(id_salt));
+            TableState::reset(ui.ctx(), id);
+            self.state.reset_table_state = false;
+        }
+        let height = ui.text_style_height(&TextStyle::Heading) + 2.0 * MARGIN.y;
+        let num_rows = self.value.fatty_acids.len() as _;
+        let num_columns = FATTY_ACID.end + self.value.modes.len();
+        let mut headers = Vec::new();
+        if self.has_columns() {
+            headers.push(self.header_row(height, |mode| mode.column.clone()));
+        }
+        headers.push(self.header_row(height, |mode| {
+            (mode.column.clone(), mode.onset_temperature.to_bits())
+        }));
+        headers.push(HeaderRow::new(height));
+        Table::new()
+            .id_salt(id_salt)
+            .num_rows(num_rows)
+            .columns(vec![
+                Column::default().resizable(self.settings.resizable);
+                num_columns
+            ])
+            .num_sticky_cols(self.settings.sticky.min(num_columns))
+            .headers(headers)
+            .auto_size_mode(AutoSizeMode::OnParentResize)
+            .show(ui, self);
+    }
+
+    fn has_columns(&self) -> bool {
+        self.value.modes.iter().any(|mode| mode.column.is_some())
+    }
+
+    fn header_row<T: PartialEq>(&self, height: f32, f: impl Fn(&Mode) -> T) -> HeaderRow {
+        let mut groups = vec![FATTY_ACID];
+        for (_, start, end) in self.value.groups(f) {
+            groups.push(FATTY_ACID.end + start..FATTY_ACID.end + end);
+        }
+        HeaderRow { height, groups }
+    }
+
+    fn header_cell_content_ui(&mut self, ui: &mut Ui, row: usize, column: Range<usize>) {
+        if self.settings.truncate {
+            ui.style_mut().wrap_mode = Some(TextWrapMode::Truncate);
+        }
+        let bottom = if self.has_columns() { 2 } else { 1 };
+        if column == FATTY_ACID {
+            if row == 0 {
+                ui.heading(ui.localize("fatty-acid"))
+                    .on_hover_localized("fatty-acid.abbreviation");
+            } else if row == bottom {
+                ui.heading(ui.localize(self.settings.pivot.text()))
+                    .on_hover_localized(self.settings.pivot.hover_text());
+            }
+            return;
+        }
+        let Some(mode) = self.value.modes.get(column.start - FATTY_ACID.end) else {
+            return;
+        };
+        match bottom - row {
+            2 => {
+                ui.heading(mode.column.as_deref().unwrap_or_default())
+                    .on_hover_localized("column.hover");
+            }
+            1 => {
+                ui.heading(format!("{} °C", mode.onset_temperature))
+                    .on_hover_localized("onset-temperature");
+            }
+            _ => {
+                ui.heading(format!("{} °C/min", mode.temperature_step))
+                    .on_hover_localized("temperature-step");
+            }
+        }
+    }
+
+    fn body_cell_content_ui(&mut self, ui: &mut Ui, row: usize, column: Range<usize>) {
+        if column == FATTY_ACID {
+            let text = format!("{:#}", (&self.value.fatty_acids[row]).display(COMMON));
+            ui.label(&text).on_hover_text(&text);
+            return;
+        }
+        let value = self.value.values[row][column.start - FATTY_ACID.end];
+        ui.add(
+            FloatValue::new(value)
+                .precision(Some(self.settings.precision))
+                .hover(),
+        );
+    }
+}
+
+impl TableDelegate for PivotView<'_> {
+    fn header_cell_ui(&mut self, ui: &mut Ui, cell: &HeaderCellInfo) {
+        Frame::new()
+            .inner_margin(Margin::from(MARGIN))
+            .show(ui, |ui| {
+                self.header_cell_content_ui(ui, cell.row_nr, cell.col_range.clone())
+            });
+    }
+
+    fn cell_ui(&mut self, ui: &mut Ui, cell: &CellInfo) {
+        if cell.row_nr % 2 == 0 {
+            ui.painter()
+                .rect_filled(ui.max_rect(), 0.0, ui.visuals().faint_bg_color);
+        }
+        Frame::new()
+            .inner_margin(Margin::from(MARGIN))
+            .show(ui, |ui| {
+                self.body_cell_content_ui(ui, cell.row_nr as _, cell.col_nr..cell.col_nr + 1)
+            });
+    }
+}
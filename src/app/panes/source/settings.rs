@@ -1,11 +1,21 @@
 use crate::{
-    app::MAX_PRECISION, localization::Text, special::data_frame::DataFrameExt as _,
+    app::{
+        MAX_PRECISION,
+        panes::widgets::{FormattingWidget, ThresholdsWidget},
+    },
+    dead_time::Conditions,
+    localization::Text,
+    special::{column::gc_column::GcColumnSeries, data_frame::DataFrameExt as _},
+    translation::Translation,
     utils::VecExt as _,
 };
-use egui::{ComboBox, Grid, PopupCloseBehavior, RichText, Slider, TextWrapMode, Ui, emath::Float};
+use egui::{
+    Button, ComboBox, CursorIcon, DragValue, Grid, PopupCloseBehavior, Response, RichText, Sense,
+    Slider, TextWrapMode, Ui, emath::Float,
+};
 use egui_ext::LabeledSeparator;
 use egui_l20n::{ResponseExt, UiExt as _};
-use egui_phosphor::regular::{FUNNEL, FUNNEL_X};
+use egui_phosphor::regular::{CARET_DOWN, CARET_UP, FUNNEL, FUNNEL_X, MINUS, PLUS, X};
 use lipid::{
     fatty_acid::display::{COMMON, DisplayWithOptions as _},
     prelude::*,
@@ -13,7 +23,10 @@ use lipid::{
 use polars::prelude::*;
 use polars_utils::{format_list_container_truncated, format_list_truncated};
 use serde::{Deserialize, Serialize};
-use std::hash::{Hash, Hasher};
+use std::{
+    hash::{Hash, Hasher},
+    ops::{Not, RangeInclusive},
+};
 
 /// Settings
 #[derive(Clone, Debug, Deserialize, Hash, PartialEq, Serialize)]
@@ -22,17 +35,27 @@ pub(crate) struct Settings {
     pub(crate) resizable: bool,
     pub(crate) sticky: usize,
     pub(crate) truncate: bool,
+    pub(crate) layout: TableLayout,
+    pub(crate) grouping: Grouping,
 
     pub(crate) view: View,
     pub(crate) ddof: u8,
     pub(crate) logarithmic: bool,
     pub(crate) relative: Option<FattyAcid>,
     pub(crate) filter: Filter,
+    pub(crate) thresholds: Vec<Threshold<Field>>,
     pub(crate) sort: SortBy,
     pub(crate) order: Order,
+    pub(crate) sort_keys: SortKeys<Field>,
+    pub(crate) clustering: Clustering,
+    pub(crate) pivot: Field,
+    pub(crate) formatting: Formatting<Field>,
 
     pub(crate) legend: bool,
     pub(crate) radius_of_points: u8,
+
+    pub(crate) conditions: Conditions,
+    pub(crate) translation: Translation,
 }
 
 impl Settings {
@@ -42,17 +65,27 @@ impl Settings {
             resizable: false,
             sticky: 0,
             truncate: false,
+            layout: TableLayout::new(),
+            grouping: Grouping::None,
 
             view: View::Table,
             ddof: 1,
             logarithmic: false,
             relative: None,
             filter: Filter::new(),
+            thresholds: Vec::new(),
             sort: SortBy::Time,
             order: Order::Ascending,
+            sort_keys: SortKeys::new(),
+            clustering: Clustering::new(),
+            pivot: Field::RetentionTime,
+            formatting: Formatting::new(),
 
             radius_of_points: 2,
             legend: true,
+
+            conditions: Conditions::new(),
+            translation: Translation::new(),
         }
     }
 
@@ -66,7 +99,7 @@ impl Settings {
 
                 // Sticky columns
                 ui.label(ui.localize("sticky"));
-                ui.add(Slider::new(&mut self.sticky, 0..=data_frame.width()));
+                ui.add(Slider::new(&mut self.sticky, 0..=self.layout.len()));
                 ui.end_row();
 
                 // Truncate titles
@@ -74,6 +107,18 @@ impl Settings {
                 ui.checkbox(&mut self.truncate, "");
                 ui.end_row();
 
+                // Columns
+                ui.label(ui.localize("columns"))
+                    .on_hover_localized("columns.hover");
+                self.layout.show(ui);
+                ui.end_row();
+
+                // Grouping
+                ui.label(ui.localize("grouping"))
+                    .on_hover_localized("grouping.hover");
+                self.grouping.show(ui, &[Grouping::None, Grouping::Mode]);
+                ui.end_row();
+
                 // Calculate
                 ui.separator();
                 ui.labeled_separator(RichText::new(ui.localize("calculate")).heading());
@@ -137,6 +182,27 @@ impl Settings {
                 self.filter.show(ui, data_frame)?;
                 ui.end_row();
 
+                ui.label(ui.localize("thresholds"))
+                    .on_hover_localized("thresholds.hover");
+                ui.add(ThresholdsWidget::new(
+                    &mut self.thresholds,
+                    &Field::VARIANTS,
+                ));
+                ui.end_row();
+
+                // Formatting
+                ui.separator();
+                ui.labeled_separator(RichText::new(ui.localize("formatting")).heading());
+                ui.end_row();
+
+                ui.label(ui.localize("formatting"))
+                    .on_hover_localized("formatting.hover");
+                ui.add(FormattingWidget::new(
+                    &mut self.formatting,
+                    &Field::VARIANTS,
+                ));
+                ui.end_row();
+
                 // Sort
                 ui.separator();
                 ui.labeled_separator(RichText::new(ui.localize("sort-by")).heading());
@@ -187,6 +253,44 @@ impl Settings {
                     .on_hover_localized(self.order.hover_text());
                 ui.end_row();
 
+                // Sort keys
+                ui.label(ui.localize("sort-keys"))
+                    .on_hover_localized("sort-keys.hover");
+                self.sort_keys.show(ui);
+                ui.end_row();
+
+                // Clustering
+                ui.separator();
+                ui.labeled_separator(RichText::new(ui.localize("clustering")).heading());
+                ui.end_row();
+
+                self.clustering.show(ui, &mut self.filter.clusters);
+
+                if let View::Pivot = self.view {
+                    // Pivot
+                    ui.separator();
+                    ui.labeled_separator(RichText::new(ui.localize("pivot-view")).heading());
+                    ui.end_row();
+
+                    ui.label(ui.localize("pivot-field"))
+                        .on_hover_localized("pivot-field.hover");
+                    ComboBox::from_id_salt(ui.next_auto_id())
+                        .selected_text(ui.localize(self.pivot.text()))
+                        .show_ui(ui, |ui| {
+                            for field in Field::VARIANTS {
+                                ui.selectable_value(
+                                    &mut self.pivot,
+                                    field,
+                                    ui.localize(field.text()),
+                                )
+                                .on_hover_localized(field.hover_text());
+                            }
+                        })
+                        .response
+                        .on_hover_localized(self.pivot.hover_text());
+                    ui.end_row();
+                }
+
                 if let View::Plot = self.view {
                     // Plot
                     ui.separator();
@@ -331,6 +435,8 @@ impl Text for Group {
 /// View
 #[derive(Clone, Copy, Debug, Default, Deserialize, Hash, PartialEq, Serialize)]
 pub(crate) enum View {
+    Heatmap,
+    Pivot,
     Plot,
     #[default]
     Table,
@@ -339,6 +445,8 @@ pub(crate) enum View {
 impl Text for View {
     fn text(&self) -> &'static str {
         match self {
+            Self::Heatmap => "heatmap-view",
+            Self::Pivot => "pivot-view",
             Self::Plot => "plot-view",
             Self::Table => "table-view",
         }
@@ -346,6 +454,8 @@ impl Text for View {
 
     fn hover_text(&self) -> &'static str {
         match self {
+            Self::Heatmap => "heatmap-view.hover",
+            Self::Pivot => "pivot-view.hover",
             Self::Plot => "table-view.hover",
             Self::Table => "plot-view.hover",
         }
@@ -356,190 +466,1516 @@ impl Text for View {
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub(crate) struct Filter {
     // pub(crate) mode: Mode,
-    pub(crate) fatty_acids: Vec<FattyAcid>,
-    pub(crate) onset_temperatures: Vec<f64>,
-    pub(crate) temperature_steps: Vec<f64>,
+    pub(crate) columns: Selection<String>,
+    pub(crate) fatty_acids: Selection<FattyAcid>,
+    pub(crate) onset_temperatures: Selection<f64>,
+    pub(crate) temperature_steps: Selection<f64>,
+    pub(crate) onset_temperature: Bounds,
+    pub(crate) temperature_step: Bounds,
+    pub(crate) structure: Structure,
+    pub(crate) clusters: Selection<u32>,
 }
 
 impl Filter {
     pub(crate) fn new() -> Self {
         Self {
-            fatty_acids: Vec::new(),
-            onset_temperatures: Vec::new(),
-            temperature_steps: Vec::new(),
+            columns: Selection::new(),
+            fatty_acids: Selection::new(),
+            onset_temperatures: Selection::new(),
+            temperature_steps: Selection::new(),
+            onset_temperature: Bounds::new(),
+            temperature_step: Bounds::new(),
+            structure: Structure::new(),
+            clusters: Selection::new(),
         }
     }
 }
 
 impl Hash for Filter {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.fatty_acids.hash(state);
-        for onset_temperature in &self.onset_temperatures {
+        self.columns.kind.hash(state);
+        self.columns.values.hash(state);
+        self.fatty_acids.kind.hash(state);
+        self.fatty_acids.values.hash(state);
+        self.onset_temperatures.kind.hash(state);
+        for onset_temperature in &self.onset_temperatures.values {
             onset_temperature.ord().hash(state);
         }
-        for temperature_step in &self.temperature_steps {
+        self.temperature_steps.kind.hash(state);
+        for temperature_step in &self.temperature_steps.values {
             temperature_step.ord().hash(state);
         }
+        self.onset_temperature.hash(state);
+        self.temperature_step.hash(state);
+        self.structure.hash(state);
+        self.clusters.kind.hash(state);
+        self.clusters.values.hash(state);
     }
 }
 
 impl Filter {
     pub(crate) fn show(&mut self, ui: &mut Ui, data_frame: &DataFrame) -> PolarsResult<()> {
+        // Column filter
+        if let Some(gc_column) = data_frame.gc_column() {
+            self.column_ui(ui, gc_column)?;
+        }
+        self.show_within_column(ui, data_frame)
+    }
+
+    fn column_ui(&mut self, ui: &mut Ui, gc_column: GcColumnSeries) -> PolarsResult<()> {
+        ui.label(ui.localize("filter-by-column"))
+            .on_hover_localized("filter-by-column.hover");
+        ui.horizontal(|ui| -> PolarsResult<()> {
+            self.columns.kind.show(ui);
+            let text = format_list_truncated!(&self.columns.values, 2);
+            ComboBox::from_id_salt("ColumnFilter")
+                .close_behavior(PopupCloseBehavior::CloseOnClickOutside)
+                .selected_text(text)
+                .show_ui(ui, |ui| -> PolarsResult<()> {
+                    let names = gc_column.names()?;
+                    for name in &names {
+                        let checked = self.columns.values.contains(name);
+                        let response = ui.selectable_label(checked, name);
+                        if response.clicked() {
+                            if checked {
+                                self.columns.values.remove_by_value(name);
+                            } else {
+                                self.columns.values.push(name.clone());
+                            }
+                        }
+                        response.context_menu(|ui| {
+                            if ui.button(format!("{FUNNEL} Select all")).clicked() {
+                                self.columns.values = names.clone();
+                                ui.close_menu();
+                            }
+                            if ui.button(format!("{FUNNEL_X} Unselect all")).clicked() {
+                                self.columns.values = Vec::new();
+                                ui.close_menu();
+                            }
+                        });
+                    }
+                    Ok(())
+                })
+                .inner
+                .transpose()?;
+            Ok(())
+        })
+        .inner?;
+        ui.end_row();
+        Ok(())
+    }
+
+    /// Shows the filter without the column selection, for the panes whose
+    /// columns are chosen elsewhere
+    pub(crate) fn show_within_column(
+        &mut self,
+        ui: &mut Ui,
+        data_frame: &DataFrame,
+    ) -> PolarsResult<()> {
         // Onset temperature filter
         ui.label(ui.localize("filter-by-onset-temperature"))
             .on_hover_localized("filter-by-onset-temperature.hover");
-        let text = format_list_truncated!(&self.onset_temperatures, 2);
-        ComboBox::from_id_salt("OnsetTemperatureFilter")
-            .close_behavior(PopupCloseBehavior::CloseOnClickOutside)
-            .selected_text(text)
-            .show_ui(ui, |ui| -> PolarsResult<()> {
-                let onset_temperatures = data_frame.mode().onset_temperature()?.unique();
-                for onset_temperature in onset_temperatures.iter().flatten() {
-                    let checked = self.onset_temperatures.contains(&onset_temperature);
-                    let response =
-                        ui.selectable_label(checked, AnyValue::from(onset_temperature).to_string());
-                    if response.clicked() {
-                        if checked {
-                            self.onset_temperatures.remove_by_value(&onset_temperature);
-                        } else {
-                            self.onset_temperatures.push(onset_temperature);
+        ui.horizontal(|ui| -> PolarsResult<()> {
+            self.onset_temperatures.kind.show(ui);
+            let text = format_list_truncated!(&self.onset_temperatures.values, 2);
+            ComboBox::from_id_salt("OnsetTemperatureFilter")
+                .close_behavior(PopupCloseBehavior::CloseOnClickOutside)
+                .selected_text(text)
+                .show_ui(ui, |ui| -> PolarsResult<()> {
+                    let onset_temperatures = data_frame.mode().onset_temperature()?.unique();
+                    for onset_temperature in onset_temperatures.iter().flatten() {
+                        let checked = self.onset_temperatures.values.contains(&onset_temperature);
+                        let response = ui.selectable_label(
+                            checked,
+                            AnyValue::from(onset_temperature).to_string(),
+                        );
+                        if response.clicked() {
+                            if checked {
+                                self.onset_temperatures
+                                    .values
+                                    .remove_by_value(&onset_temperature);
+                            } else {
+                                self.onset_temperatures.values.push(onset_temperature);
+                            }
                         }
+                        response.context_menu(|ui| {
+                            if ui.button(format!("{FUNNEL} Select all")).clicked() {
+                                self.onset_temperatures.values =
+                                    onset_temperatures.iter().flatten().collect();
+                                ui.close_menu();
+                            }
+                            if ui.button(format!("{FUNNEL_X} Unselect all")).clicked() {
+                                self.onset_temperatures.values = Vec::new();
+                                ui.close_menu();
+                            }
+                        });
                     }
-                    response.context_menu(|ui| {
-                        if ui.button(format!("{FUNNEL} Select all")).clicked() {
-                            self.onset_temperatures = onset_temperatures.iter().flatten().collect();
-                            ui.close_menu();
-                        }
-                        if ui.button(format!("{FUNNEL_X} Unselect all")).clicked() {
-                            self.onset_temperatures = Vec::new();
-                            ui.close_menu();
-                        }
-                    });
-                }
-                Ok(())
-            })
-            .inner
-            .transpose()?;
+                    Ok(())
+                })
+                .inner
+                .transpose()?;
+            Ok(())
+        })
+        .inner?;
+        ui.end_row();
+
+        ui.label(ui.localize("onset-temperature-range"))
+            .on_hover_localized("onset-temperature-range.hover");
+        self.onset_temperature
+            .show(ui, data_frame.mode().onset_temperature_range(), " °C");
         ui.end_row();
 
         // Temperature step filter
         ui.label(ui.localize("filter-by-temperature-step"))
             .on_hover_localized("filter-by-temperature-step.hover");
-        let text = format_list_truncated!(&self.temperature_steps, 2);
-        ComboBox::from_id_salt("TemperatureStepFilter")
-            .close_behavior(PopupCloseBehavior::CloseOnClickOutside)
-            .selected_text(text)
-            .show_ui(ui, |ui| -> PolarsResult<()> {
-                let temperature_steps = data_frame.mode().temperature_step()?.unique();
-                for temperature_step in temperature_steps.iter().flatten() {
-                    let checked = self.temperature_steps.contains(&temperature_step);
-                    let response =
-                        ui.selectable_label(checked, AnyValue::from(temperature_step).to_string());
-                    if response.clicked() {
-                        if checked {
-                            self.temperature_steps.remove_by_value(&temperature_step);
-                        } else {
-                            self.temperature_steps.push(temperature_step);
+        ui.horizontal(|ui| -> PolarsResult<()> {
+            self.temperature_steps.kind.show(ui);
+            let text = format_list_truncated!(&self.temperature_steps.values, 2);
+            ComboBox::from_id_salt("TemperatureStepFilter")
+                .close_behavior(PopupCloseBehavior::CloseOnClickOutside)
+                .selected_text(text)
+                .show_ui(ui, |ui| -> PolarsResult<()> {
+                    let temperature_steps = data_frame.mode().temperature_step()?.unique();
+                    for temperature_step in temperature_steps.iter().flatten() {
+                        let checked = self.temperature_steps.values.contains(&temperature_step);
+                        let response = ui.selectable_label(
+                            checked,
+                            AnyValue::from(temperature_step).to_string(),
+                        );
+                        if response.clicked() {
+                            if checked {
+                                self.temperature_steps
+                                    .values
+                                    .remove_by_value(&temperature_step);
+                            } else {
+                                self.temperature_steps.values.push(temperature_step);
+                            }
                         }
+                        response.context_menu(|ui| {
+                            if ui.button(format!("{FUNNEL} Select all")).clicked() {
+                                self.temperature_steps.values =
+                                    temperature_steps.iter().flatten().collect();
+                                ui.close_menu();
+                            }
+                            if ui.button(format!("{FUNNEL_X} Unselect all")).clicked() {
+                                self.temperature_steps.values = Vec::new();
+                                ui.close_menu();
+                            }
+                        });
                     }
-                    response.context_menu(|ui| {
-                        if ui.button(format!("{FUNNEL} Select all")).clicked() {
-                            self.temperature_steps = temperature_steps.iter().flatten().collect();
-                            ui.close_menu();
-                        }
-                        if ui.button(format!("{FUNNEL_X} Unselect all")).clicked() {
-                            self.temperature_steps = Vec::new();
-                            ui.close_menu();
-                        }
-                    });
-                }
-                Ok(())
-            })
-            .inner
-            .transpose()?;
+                    Ok(())
+                })
+                .inner
+                .transpose()?;
+            Ok(())
+        })
+        .inner?;
+        ui.end_row();
+
+        ui.label(ui.localize("temperature-step-range"))
+            .on_hover_localized("temperature-step-range.hover");
+        self.temperature_step
+            .show(ui, data_frame.mode().temperature_step_range(), " °C/min");
         ui.end_row();
 
         // Fatty acids filter
         ui.label(ui.localize("filter-by-fatty-acids"))
             .on_hover_localized("filter-by-fatty-acids.hover");
-        let text = format_list_truncated!(
-            self.fatty_acids
-                .iter()
-                .map(|fatty_acid| fatty_acid.display(COMMON)),
-            2
-        );
-        let inner_response = ComboBox::from_id_salt("FattyAcidsFilter")
-            .close_behavior(PopupCloseBehavior::CloseOnClickOutside)
-            .selected_text(text)
-            .show_ui(ui, |ui| -> PolarsResult<()> {
-                let fatty_acids = data_frame["FattyAcid"]
-                    .unique()?
-                    .sort(Default::default())?
-                    .fa();
-                for index in 0..fatty_acids.len() {
-                    let Some(fatty_acid) = fatty_acids.get(index)? else {
-                        continue;
-                    };
-                    let checked = self.fatty_acids.contains(&fatty_acid);
-                    let response = ui
-                        .selectable_label(checked, format!("{:#}", (&fatty_acid).display(COMMON)));
-                    if response.clicked() {
-                        if checked {
-                            self.fatty_acids.remove_by_value(&fatty_acid);
-                        } else {
-                            self.fatty_acids.push(fatty_acid);
+        ui.horizontal(|ui| -> PolarsResult<()> {
+            self.fatty_acids.kind.show(ui);
+            let text = format_list_truncated!(
+                self.fatty_acids
+                    .values
+                    .iter()
+                    .map(|fatty_acid| fatty_acid.display(COMMON)),
+                2
+            );
+            let inner_response = ComboBox::from_id_salt("FattyAcidsFilter")
+                .close_behavior(PopupCloseBehavior::CloseOnClickOutside)
+                .selected_text(text)
+                .show_ui(ui, |ui| -> PolarsResult<()> {
+                    let fatty_acids = data_frame["FattyAcid"]
+                        .unique()?
+                        .sort(Default::default())?
+                        .fa();
+                    for index in 0..fatty_acids.len() {
+                        let Some(fatty_acid) = fatty_acids.get(index)? else {
+                            continue;
+                        };
+                        let checked = self.fatty_acids.values.contains(&fatty_acid);
+                        let response = ui.selectable_label(
+                            checked,
+                            format!("{:#}", (&fatty_acid).display(COMMON)),
+                        );
+                        if response.clicked() {
+                            if checked {
+                                self.fatty_acids.values.remove_by_value(&fatty_acid);
+                            } else {
+                                self.fatty_acids.values.push(fatty_acid);
+                            }
                         }
+                        response.context_menu(|ui| {
+                            if ui.button(format!("{FUNNEL} Select all")).clicked() {
+                                self.fatty_acids.values =
+                                    fatty_acids.clone().into_iter().flatten().collect();
+                                ui.close_menu();
+                            }
+                            if ui.button(format!("{FUNNEL_X} Unselect all")).clicked() {
+                                self.fatty_acids.values = Vec::new();
+                                ui.close_menu();
+                            }
+                        });
                     }
-                    response.context_menu(|ui| {
-                        if ui.button(format!("{FUNNEL} Select all")).clicked() {
-                            self.fatty_acids = fatty_acids.clone().into_iter().flatten().collect();
-                            ui.close_menu();
-                        }
-                        if ui.button(format!("{FUNNEL_X} Unselect all")).clicked() {
-                            self.fatty_acids = Vec::new();
+                    Ok(())
+                });
+            inner_response.inner.transpose()?;
+            inner_response.response.on_hover_ui(|ui| {
+                ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+                ui.label(self.fatty_acids.values.len().to_string());
+            });
+            Ok(())
+        })
+        .inner?;
+        ui.end_row();
+
+        // Structure filter
+        ui.label(ui.localize("filter-by-structure"))
+            .on_hover_localized("filter-by-structure.hover");
+        self.structure.show(ui);
+        Ok(())
+    }
+}
+
+/// Selection filter
+///
+/// Keeps only the selected values or all values except them.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub(crate) struct Selection<T> {
+    pub(crate) kind: Kind,
+    pub(crate) values: Vec<T>,
+}
+
+impl<T> Selection<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            kind: Kind::Exclude,
+            values: Vec::new(),
+        }
+    }
+}
+
+impl<T> Default for Selection<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Selection kind
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub(crate) enum Kind {
+    Include,
+    #[default]
+    Exclude,
+}
+
+impl Kind {
+    fn show(&mut self, ui: &mut Ui) {
+        let text = match self {
+            Self::Include => FUNNEL,
+            Self::Exclude => FUNNEL_X,
+        };
+        if ui
+            .button(text)
+            .on_hover_localized(self.hover_text())
+            .clicked()
+        {
+            *self = match self {
+                Self::Include => Self::Exclude,
+                Self::Exclude => Self::Include,
+            };
+        }
+    }
+}
+
+impl Text for Kind {
+    fn text(&self) -> &'static str {
+        match self {
+            Self::Include => "include",
+            Self::Exclude => "exclude",
+        }
+    }
+
+    fn hover_text(&self) -> &'static str {
+        match self {
+            Self::Include => "include.hover",
+            Self::Exclude => "exclude.hover",
+        }
+    }
+}
+
+/// Structure filter
+///
+/// Conditions on the structure of the fatty acids combined with the operator.
+#[derive(Clone, Debug, Default, Deserialize, Hash, PartialEq, Serialize)]
+pub(crate) struct Structure {
+    pub(crate) operator: Operator,
+    pub(crate) conditions: Vec<Condition>,
+    /// Fatty acids of the distance pairs the conditions are applied to
+    pub(crate) target: Target,
+}
+
+impl Structure {
+    pub(crate) fn new() -> Self {
+        Self {
+            operator: Operator::And,
+            conditions: Vec::new(),
+            target: Target::Both,
+        }
+    }
+
+    fn show(&mut self, ui: &mut Ui) {
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                for operator in [Operator::And, Operator::Or] {
+                    ui.selectable_value(&mut self.operator, operator, ui.localize(operator.text()))
+                        .on_hover_localized(operator.hover_text());
+                }
+                ui.menu_button(PLUS, |ui| {
+                    for condition in Condition::VARIANTS {
+                        if ui
+                            .button(ui.localize(condition.text()))
+                            .on_hover_localized(condition.hover_text())
+                            .clicked()
+                        {
+                            self.conditions.push(condition);
                             ui.close_menu();
                         }
-                    });
-                }
-                Ok(())
+                    }
+                });
             });
-        inner_response.inner.transpose()?;
-        inner_response.response.on_hover_ui(|ui| {
-            ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
-            ui.label(self.fatty_acids.len().to_string());
+            let mut remove = None;
+            for (index, condition) in self.conditions.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    if ui.button(MINUS).clicked() {
+                        remove = Some(index);
+                    }
+                    ui.label(ui.localize(condition.text()))
+                        .on_hover_localized(condition.hover_text());
+                    match condition {
+                        Condition::Carbons { min, max } | Condition::DoubleBonds { min, max } => {
+                            ui.add(DragValue::new(min).range(0..=*max));
+                            ui.label("..=");
+                            ui.add(DragValue::new(max).range(*min..=u8::MAX));
+                        }
+                        Condition::Omega(n) | Condition::Delta(n) => {
+                            ui.add(DragValue::new(n).range(1..=u8::MAX));
+                        }
+                        Condition::Saturated | Condition::Trans => {}
+                    }
+                });
+            }
+            if let Some(index) = remove {
+                self.conditions.remove(index);
+            }
         });
-        Ok(())
     }
 }
 
-/// Sort by
-#[derive(Clone, Debug, Deserialize, Hash, PartialEq, Serialize)]
-pub(crate) enum SortBy {
-    FattyAcid,
-    Time,
+/// Structure condition operator
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub(crate) enum Operator {
+    #[default]
+    And,
+    Or,
 }
 
-impl Text for SortBy {
+impl Text for Operator {
     fn text(&self) -> &'static str {
         match self {
-            Self::FattyAcid => "sort-by-fatty-acids",
-            Self::Time => "sort-by-retention-time",
+            Self::And => "and",
+            Self::Or => "or",
         }
     }
 
     fn hover_text(&self) -> &'static str {
         match self {
-            Self::FattyAcid => "sort-by-fatty-acids.hover",
-            Self::Time => "sort-by-retention-time.hover",
+            Self::And => "and.hover",
+            Self::Or => "or.hover",
         }
     }
 }
 
-/// Order
+/// Structure condition
 #[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
-pub(crate) enum Order {
-    Ascending,
-    Descending,
+pub(crate) enum Condition {
+    /// Number of carbons within the range
+    Carbons { min: u8, max: u8 },
+    /// Number of double bonds within the range
+    DoubleBonds { min: u8, max: u8 },
+    /// Any trans double bond
+    Trans,
+    /// ω-n family
+    Omega(u8),
+    /// Double bond at Δn
+    Delta(u8),
+    /// No unsaturated bonds
+    Saturated,
+}
+
+impl Condition {
+    const VARIANTS: [Self; 6] = [
+        Self::Carbons { min: 18, max: 18 },
+        Self::DoubleBonds { min: 1, max: 1 },
+        Self::Trans,
+        Self::Omega(3),
+        Self::Delta(9),
+        Self::Saturated,
+    ];
+}
+
+impl Text for Condition {
+    fn text(&self) -> &'static str {
+        match self {
+            Self::Carbons { .. } => "carbons",
+            Self::DoubleBonds { .. } => "double-bonds",
+            Self::Trans => "trans",
+            Self::Omega(_) => "omega",
+            Self::Delta(_) => "delta",
+            Self::Saturated => "saturated",
+        }
+    }
+
+    fn hover_text(&self) -> &'static str {
+        match self {
+            Self::Carbons { .. } => "carbons.hover",
+            Self::DoubleBonds { .. } => "double-bonds.hover",
+            Self::Trans => "trans.hover",
+            Self::Omega(_) => "omega.hover",
+            Self::Delta(_) => "delta.hover",
+            Self::Saturated => "saturated.hover",
+        }
+    }
+}
+
+/// Fatty acids of the distance pairs
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub(crate) enum Target {
+    From,
+    To,
+    #[default]
+    Both,
+}
+
+impl Target {
+    pub(crate) const VARIANTS: [Self; 3] = [Self::From, Self::To, Self::Both];
+}
+
+impl Text for Target {
+    fn text(&self) -> &'static str {
+        match self {
+            Self::From => "from",
+            Self::To => "to",
+            Self::Both => "both",
+        }
+    }
+
+    fn hover_text(&self) -> &'static str {
+        match self {
+            Self::From => "from.hover",
+            Self::To => "to.hover",
+            Self::Both => "both.hover",
+        }
+    }
+}
+
+/// Threshold filter of a computed value
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub(crate) struct Threshold<F> {
+    pub(crate) field: F,
+    /// Compare the absolute value
+    pub(crate) absolute: bool,
+    pub(crate) comparison: Comparison,
+    pub(crate) value: f64,
+}
+
+impl<F: Text> Threshold<F> {
+    /// Localized description, for example `|α| < 1.02`
+    pub(crate) fn text(&self, ui: &Ui) -> String {
+        let field = ui.localize(self.field.text());
+        let field = if self.absolute {
+            format!("|{field}|")
+        } else {
+            field
+        };
+        format!("{field} {} {}", self.comparison.symbol(), self.value)
+    }
+}
+
+impl<F: Hash> Hash for Threshold<F> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.field.hash(state);
+        self.absolute.hash(state);
+        self.comparison.hash(state);
+        self.value.ord().hash(state);
+    }
+}
+
+/// Conditional formatting of the table cells
+#[derive(Clone, Debug, Deserialize, Hash, PartialEq, Serialize)]
+pub(crate) struct Formatting<F> {
+    pub(crate) rules: Vec<Rule<F>>,
+    /// Mark the saturated and the unsaturated rows
+    pub(crate) saturation: bool,
+}
+
+impl<F> Formatting<F> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            rules: Vec::new(),
+            saturation: false,
+        }
+    }
+}
+
+impl<F> Default for Formatting<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Formatting rule
+#[derive(Clone, Copy, Debug, Deserialize, Hash, PartialEq, Serialize)]
+pub(crate) enum Rule<F> {
+    /// Red to green color scale over the range of the field
+    Scale { field: F, reverse: bool },
+    /// Color of the cells satisfying the threshold
+    Highlight {
+        threshold: Threshold<F>,
+        color: [u8; 3],
+    },
+}
+
+impl<F: Copy> Rule<F> {
+    pub(crate) const fn field(&self) -> F {
+        match self {
+            Self::Scale { field, .. } => *field,
+            Self::Highlight { threshold, .. } => threshold.field,
+        }
+    }
+}
+
+impl<F: Text> Rule<F> {
+    /// Localized description, for example `|α| < 1.02`
+    pub(crate) fn text(&self, ui: &Ui) -> String {
+        match self {
+            Self::Scale { field, reverse } => {
+                let mut text = format!(
+                    "{} {}",
+                    ui.localize(field.text()),
+                    ui.localize("color-scale"),
+                );
+                if *reverse {
+                    text.push_str(&format!(" ({})", ui.localize("reverse")));
+                }
+                text
+            }
+            Self::Highlight { threshold, .. } => threshold.text(ui),
+        }
+    }
+}
+
+impl<F: Default> Default for Rule<F> {
+    fn default() -> Self {
+        Self::Scale {
+            field: F::default(),
+            reverse: false,
+        }
+    }
+}
+
+/// Threshold comparison
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub(crate) enum Comparison {
+    #[default]
+    Less,
+    Greater,
+}
+
+impl Comparison {
+    pub(crate) const VARIANTS: [Self; 2] = [Self::Less, Self::Greater];
+
+    pub(crate) const fn symbol(&self) -> &'static str {
+        match self {
+            Self::Less => "<",
+            Self::Greater => ">",
+        }
+    }
+}
+
+/// Sort keys of the header columns
+///
+/// Applied within the column and mode groups and take precedence over the
+/// sort by.
+#[derive(Clone, Debug, Default, Deserialize, Hash, PartialEq, Serialize)]
+pub(crate) struct SortKeys<F> {
+    pub(crate) keys: Vec<SortKey<F>>,
+}
+
+impl<F> SortKeys<F> {
+    pub(crate) const fn new() -> Self {
+        Self { keys: Vec::new() }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.keys.clear();
+    }
+}
+
+impl<F: Copy + PartialEq + Text> SortKeys<F> {
+    /// Sorts by the field, toggles the order if already sorted by it
+    ///
+    /// If extend, the field is added as the next key instead of replacing the
+    /// others.
+    pub(crate) fn click(&mut self, field: F, extend: bool) {
+        match self.keys.iter().position(|key| key.field == field) {
+            Some(index) if extend || self.keys.len() == 1 => {
+                self.keys[index].order = !self.keys[index].order;
+            }
+            None if extend => self.keys.push(SortKey {
+                field,
+                order: Order::Ascending,
+            }),
+            _ => {
+                self.keys = vec![SortKey {
+                    field,
+                    order: Order::Ascending,
+                }];
+            }
+        }
+    }
+
+    /// Header indicator of the field, for example `⏶2`
+    pub(crate) fn indicator(&self, field: F) -> Option<String> {
+        let index = self.keys.iter().position(|key| key.field == field)?;
+        let arrow = match self.keys[index].order {
+            Order::Ascending => CARET_UP,
+            Order::Descending => CARET_DOWN,
+        };
+        Some(if self.keys.len() > 1 {
+            format!("{arrow}{}", index + 1)
+        } else {
+            arrow.to_owned()
+        })
+    }
+
+    /// Sortable table header
+    ///
+    /// Click to sort by the field, shift-click to add it as the next key.
+    pub(crate) fn header(&mut self, ui: &mut Ui, text: String, field: F) -> Response {
+        let text = match self.indicator(field) {
+            Some(indicator) => format!("{text} {indicator}"),
+            None => text,
+        };
+        let response = ui
+            .heading(text)
+            .interact(Sense::click())
+            .on_hover_cursor(CursorIcon::PointingHand);
+        if response.clicked() {
+            let extend = ui.input(|input| input.modifiers.shift);
+            self.click(field, extend);
+        }
+        response
+    }
+
+    pub(crate) fn show(&mut self, ui: &mut Ui) {
+        ui.horizontal_wrapped(|ui| {
+            for key in &self.keys {
+                ui.label(format!(
+                    "{} {}",
+                    ui.localize(key.field.text()),
+                    self.indicator(key.field).unwrap_or_default(),
+                ))
+                .on_hover_localized(key.order.hover_text());
+            }
+            if ui
+                .add_enabled(!self.is_empty(), Button::new(X).small())
+                .on_hover_localized("clear-sort-keys")
+                .clicked()
+            {
+                self.clear();
+            }
+        });
+    }
+}
+
+/// Sort key
+#[derive(Clone, Copy, Debug, Deserialize, Hash, PartialEq, Serialize)]
+pub(crate) struct SortKey<F> {
+    pub(crate) field: F,
+    pub(crate) order: Order,
+}
+
+/// Source computed field
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub(crate) enum Field {
+    #[default]
+    RetentionTime,
+    RelativeRetentionTime,
+    DeltaRetentionTime,
+    StandardDeviation,
+    RelativeStandardDeviation,
+    Temperature,
+    EquivalentChainLength,
+    FractionalChainLength,
+    EquivalentCarbonNumber,
+    Mass,
+    Slope,
+    Angle,
+}
+
+impl Field {
+    pub(crate) const VARIANTS: [Self; 12] = [
+        Self::RetentionTime,
+        Self::RelativeRetentionTime,
+        Self::DeltaRetentionTime,
+        Self::StandardDeviation,
+        Self::RelativeStandardDeviation,
+        Self::Temperature,
+        Self::EquivalentChainLength,
+        Self::FractionalChainLength,
+        Self::EquivalentCarbonNumber,
+        Self::Mass,
+        Self::Slope,
+        Self::Angle,
+    ];
+
+    /// Table column of the field
+    pub(crate) const fn column(&self) -> TableColumn {
+        match self {
+            Self::RetentionTime | Self::StandardDeviation | Self::RelativeStandardDeviation => {
+                TableColumn::AbsoluteRetentionTime
+            }
+            Self::RelativeRetentionTime => TableColumn::RelativeRetentionTime,
+            Self::DeltaRetentionTime => TableColumn::DeltaRetentionTime,
+            Self::Temperature => TableColumn::Temperature,
+            Self::EquivalentChainLength => TableColumn::EquivalentChainLength,
+            Self::FractionalChainLength => TableColumn::FractionalChainLength,
+            Self::EquivalentCarbonNumber => TableColumn::EquivalentCarbonNumber,
+            Self::Mass => TableColumn::Mass,
+            Self::Slope => TableColumn::Slope,
+            Self::Angle => TableColumn::Angle,
+        }
+    }
+}
+
+impl Text for Field {
+    fn text(&self) -> &'static str {
+        match self {
+            Self::RetentionTime => "retention-time",
+            Self::RelativeRetentionTime => "retention-time-relative",
+            Self::DeltaRetentionTime => "retention-time-delta",
+            Self::StandardDeviation => "standard-deviation",
+            Self::RelativeStandardDeviation => "relative-standard-deviation",
+            Self::Temperature => "temperature",
+            Self::EquivalentChainLength => "equivalent-chain-length",
+            Self::FractionalChainLength => "fractional-chain-length",
+            Self::EquivalentCarbonNumber => "equivalent-carbon-number",
+            Self::Mass => "mass",
+            Self::Slope => "slope",
+            Self::Angle => "angle",
+        }
+    }
+
+    fn hover_text(&self) -> &'static str {
+        match self {
+            Self::RetentionTime => "absolute-retention-time.hover",
+            Self::RelativeRetentionTime => "relative-retention-time.hover",
+            Self::DeltaRetentionTime => "delta-retention-time.hover",
+            Self::StandardDeviation => "standard-deviation.hover",
+            Self::RelativeStandardDeviation => "relative-standard-deviation.hover",
+            Self::Temperature => "temperature.hover",
+            Self::EquivalentChainLength => "equivalent-chain-length.abbreviation",
+            Self::FractionalChainLength => "fractional-chain-length.abbreviation",
+            Self::EquivalentCarbonNumber => "equivalent-carbon-number.abbreviation",
+            Self::Mass => "mass.hover",
+            Self::Slope => "slope.hover",
+            Self::Angle => "angle.hover",
+        }
+    }
+}
+
+/// Table layout
+///
+/// The order of the column groups and the visibility and the format of the
+/// columns in them.
+#[derive(Clone, Debug, Deserialize, Hash, PartialEq, Serialize)]
+pub(crate) struct TableLayout {
+    pub(crate) groups: Vec<GroupLayout>,
+}
+
+impl TableLayout {
+    pub(crate) fn new() -> Self {
+        Self {
+            groups: ColumnGroup::VARIANTS
+                .into_iter()
+                .map(|group| GroupLayout {
+                    group,
+                    columns: group
+                        .columns()
+                        .iter()
+                        .map(|&column| ColumnLayout::new(column))
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Count of the visible columns
+    pub(crate) fn len(&self) -> usize {
+        self.visible().count()
+    }
+
+    /// Visible columns in the order
+    pub(crate) fn visible(&self) -> impl Iterator<Item = (ColumnGroup, &ColumnLayout)> {
+        self.groups.iter().flat_map(|group| {
+            group
+                .columns
+                .iter()
+                .filter(|column| column.visible)
+                .map(|column| (group.group, column))
+        })
+    }
+
+    fn show(&mut self, ui: &mut Ui) {
+        ui.vertical(|ui| {
+            let len = self.groups.len();
+            let mut swap = None;
+            for (index, group) in self.groups.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(index > 0, Button::new(CARET_UP).small())
+                        .on_hover_localized("move-up")
+                        .clicked()
+                    {
+                        swap = Some(index - 1);
+                    }
+                    if ui
+                        .add_enabled(index + 1 < len, Button::new(CARET_DOWN).small())
+                        .on_hover_localized("move-down")
+                        .clicked()
+                    {
+                        swap = Some(index);
+                    }
+                    ui.label(RichText::new(ui.localize(group.group.text())).strong())
+                        .on_hover_localized(group.group.hover_text());
+                });
+                ui.indent(ui.next_auto_id(), |ui| {
+                    for column in &mut group.columns {
+                        column.show(ui);
+                    }
+                });
+            }
+            if let Some(index) = swap {
+                self.groups.swap(index, index + 1);
+            }
+            if ui.button(ui.localize("reset")).clicked() {
+                *self = Self::new();
+            }
+        });
+    }
+}
+
+impl Default for TableLayout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Column group layout
+#[derive(Clone, Debug, Deserialize, Hash, PartialEq, Serialize)]
+pub(crate) struct GroupLayout {
+    pub(crate) group: ColumnGroup,
+    pub(crate) columns: Vec<ColumnLayout>,
+}
+
+/// Column layout
+#[derive(Clone, Copy, Debug, Deserialize, Hash, PartialEq, Serialize)]
+pub(crate) struct ColumnLayout {
+    pub(crate) column: TableColumn,
+    pub(crate) visible: bool,
+    pub(crate) precision: Option<usize>,
+    pub(crate) unit: Option<Unit>,
+}
+
+impl ColumnLayout {
+    pub(crate) fn new(column: TableColumn) -> Self {
+        Self {
+            column,
+            visible: true,
+            precision: None,
+            unit: column.units().first().copied(),
+        }
+    }
+
+    /// Precision of the column or the default
+    pub(crate) fn precision(&self, default: usize) -> usize {
+        self.precision.unwrap_or(default)
+    }
+
+    /// Value in the unit of the column
+    pub(crate) fn convert(&self, value: Option<f64>) -> Option<f64> {
+        match self.unit {
+            Some(unit) => value.map(|value| unit.convert(value)),
+            None => value,
+        }
+    }
+
+    fn show(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.visible, ui.localize(self.column.text()))
+                .on_hover_localized(self.column.hover_text());
+            if !self.column.is_numeric() {
+                return;
+            }
+            ui.add_enabled_ui(self.visible, |ui| {
+                let mut checked = self.precision.is_some();
+                if ui
+                    .checkbox(&mut checked, ui.localize("precision"))
+                    .changed()
+                {
+                    self.precision = checked.then_some(2);
+                }
+                if let Some(precision) = &mut self.precision {
+                    ui.add(DragValue::new(precision).range(0..=MAX_PRECISION));
+                }
+                let units = self.column.units();
+                if let Some(unit) = &mut self.unit {
+                    ComboBox::from_id_salt(ui.next_auto_id())
+                        .width(0.0)
+                        .selected_text(unit.symbol())
+                        .show_ui(ui, |ui| {
+                            for &selected_value in units {
+                                ui.selectable_value(unit, selected_value, selected_value.symbol());
+                            }
+                        });
+                }
+            });
+        });
+    }
+}
+
+/// Column group of the table
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub(crate) enum ColumnGroup {
+    Index,
+    Column,
+    Mode,
+    FattyAcid,
+    RetentionTime,
+    Temperature,
+    ChainLength,
+    Mass,
+    Derivative,
+}
+
+impl ColumnGroup {
+    pub(crate) const VARIANTS: [Self; 9] = [
+        Self::Index,
+        Self::Column,
+        Self::Mode,
+        Self::FattyAcid,
+        Self::RetentionTime,
+        Self::Temperature,
+        Self::ChainLength,
+        Self::Mass,
+        Self::Derivative,
+    ];
+
+    pub(crate) const fn columns(&self) -> &'static [TableColumn] {
+        match self {
+            Self::Index => &[TableColumn::Index],
+            Self::Column => &[TableColumn::Column],
+            Self::Mode => &[TableColumn::OnsetTemperature, TableColumn::TemperatureStep],
+            Self::FattyAcid => &[TableColumn::FattyAcid],
+            Self::RetentionTime => &[
+                TableColumn::AbsoluteRetentionTime,
+                TableColumn::RelativeRetentionTime,
+                TableColumn::DeltaRetentionTime,
+            ],
+            Self::Temperature => &[TableColumn::Temperature],
+            Self::ChainLength => &[
+                TableColumn::EquivalentChainLength,
+                TableColumn::FractionalChainLength,
+                TableColumn::EquivalentCarbonNumber,
+            ],
+            Self::Mass => &[TableColumn::Mass],
+            Self::Derivative => &[TableColumn::Slope, TableColumn::Angle],
+        }
+    }
+
+    /// The group is a single column without the subheaders
+    pub(crate) const fn is_single(&self) -> bool {
+        self.columns().len() == 1
+    }
+}
+
+impl Text for ColumnGroup {
+    fn text(&self) -> &'static str {
+        match self {
+            Self::Index => "index",
+            Self::Column => "column",
+            Self::Mode => "mode",
+            Self::FattyAcid => "fatty-acid",
+            Self::RetentionTime => "retention-time",
+            Self::Temperature => "temperature",
+            Self::ChainLength => "chain-length",
+            Self::Mass => "mass",
+            Self::Derivative => "derivative",
+        }
+    }
+
+    fn hover_text(&self) -> &'static str {
+        match self {
+            Self::Index => "index",
+            Self::Column => "column.hover",
+            Self::Mode => "mode.hover",
+            Self::FattyAcid => "fatty-acid.abbreviation",
+            Self::RetentionTime => "retention-time.hover",
+            Self::Temperature => "temperature.hover",
+            Self::ChainLength => "chain-length.hover",
+            Self::Mass => "mass.hover",
+            Self::Derivative => "derivative.hover",
+        }
+    }
+}
+
+/// Column of the table
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub(crate) enum TableColumn {
+    Index,
+    Column,
+    OnsetTemperature,
+    TemperatureStep,
+    FattyAcid,
+    AbsoluteRetentionTime,
+    RelativeRetentionTime,
+    DeltaRetentionTime,
+    Temperature,
+    EquivalentChainLength,
+    FractionalChainLength,
+    EquivalentCarbonNumber,
+    Mass,
+    Slope,
+    Angle,
+}
+
+impl TableColumn {
+    pub(crate) const fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            Self::AbsoluteRetentionTime
+                | Self::RelativeRetentionTime
+                | Self::DeltaRetentionTime
+                | Self::Temperature
+                | Self::EquivalentChainLength
+                | Self::FractionalChainLength
+                | Self::Mass
+                | Self::Slope
+                | Self::Angle
+        )
+    }
+
+    /// Units of the column, the first is the unit of the computed values
+    pub(crate) const fn units(&self) -> &'static [Unit] {
+        match self {
+            Self::AbsoluteRetentionTime | Self::DeltaRetentionTime => {
+                &[Unit::Minutes, Unit::Seconds]
+            }
+            Self::Temperature => &[Unit::Celsius, Unit::Kelvin],
+            Self::Angle => &[Unit::Degrees, Unit::Radians],
+            _ => &[],
+        }
+    }
+}
+
+impl Text for TableColumn {
+    fn text(&self) -> &'static str {
+        match self {
+            Self::Index => "index",
+            Self::Column => "column",
+            Self::OnsetTemperature => "onset-temperature",
+            Self::TemperatureStep => "temperature-step",
+            Self::FattyAcid => "fatty-acid",
+            Self::AbsoluteRetentionTime => "absolute-retention-time",
+            Self::RelativeRetentionTime => "relative-retention-time",
+            Self::DeltaRetentionTime => "delta-retention-time",
+            Self::Temperature => "temperature",
+            Self::EquivalentChainLength => "equivalent-chain-length",
+            Self::FractionalChainLength => "fractional-chain-length",
+            Self::EquivalentCarbonNumber => "equivalent-carbon-number",
+            Self::Mass => "mass",
+            Self::Slope => "slope",
+            Self::Angle => "angle",
+        }
+    }
+
+    fn hover_text(&self) -> &'static str {
+        match self {
+            Self::Index => "index",
+            Self::Column => "column.hover",
+            Self::OnsetTemperature => "onset-temperature",
+            Self::TemperatureStep => "temperature-step.hover",
+            Self::FattyAcid => "fatty-acid.abbreviation",
+            Self::AbsoluteRetentionTime => "absolute-retention-time.hover",
+            Self::RelativeRetentionTime => "relative-retention-time.hover",
+            Self::DeltaRetentionTime => "delta-retention-time.hover",
+            Self::Temperature => "temperature.hover",
+            Self::EquivalentChainLength => "equivalent-chain-length.abbreviation",
+            Self::FractionalChainLength => "fractional-chain-length.abbreviation",
+            Self::EquivalentCarbonNumber => "equivalent-carbon-number.abbreviation",
+            Self::Mass => "mass.hover",
+            Self::Slope => "slope.hover",
+            Self::Angle => "angle.hover",
+        }
+    }
+}
+
+/// Unit of the column
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub(crate) enum Unit {
+    Minutes,
+    Seconds,
+    Celsius,
+    Kelvin,
+    Degrees,
+    Radians,
+}
+
+impl Unit {
+    pub(crate) const fn symbol(&self) -> &'static str {
+        match self {
+            Self::Minutes => "min",
+            Self::Seconds => "s",
+            Self::Celsius => "°C",
+            Self::Kelvin => "K",
+            Self::Degrees => "°",
+            Self::Radians => "rad",
+        }
+    }
+
+    /// Converts the value from the first unit of the column
+    pub(crate) fn convert(&self, value: f64) -> f64 {
+        match self {
+            Self::Minutes | Self::Celsius | Self::Degrees => value,
+            Self::Seconds => value * 60.0,
+            Self::Kelvin => value + 273.15,
+            Self::Radians => value.to_radians(),
+        }
+    }
+}
+
+/// Hierarchical clustering of the fatty acids
+#[derive(Clone, Copy, Debug, Deserialize, Hash, PartialEq, Serialize)]
+pub(crate) struct Clustering {
+    pub(crate) enabled: bool,
+    pub(crate) feature: Feature,
+    pub(crate) linkage: Linkage,
+    pub(crate) clusters: usize,
+}
+
+impl Clustering {
+    pub(crate) const fn new() -> Self {
+        Self {
+            enabled: false,
+            feature: Feature::EquivalentChainLength,
+            linkage: Linkage::Average,
+            clusters: 4,
+        }
+    }
+
+    fn show(&mut self, ui: &mut Ui, selection: &mut Selection<u32>) {
+        ui.label(ui.localize("clustering"))
+            .on_hover_localized("clustering.hover");
+        ui.checkbox(&mut self.enabled, "");
+        ui.end_row();
+
+        ui.add_enabled_ui(self.enabled, |ui| {
+            ui.label(ui.localize("clustering-feature"))
+                .on_hover_localized("clustering-feature.hover");
+        });
+        ui.add_enabled_ui(self.enabled, |ui| {
+            ComboBox::from_id_salt(ui.next_auto_id())
+                .selected_text(ui.localize(self.feature.text()))
+                .show_ui(ui, |ui| {
+                    for feature in Feature::VARIANTS {
+                        ui.selectable_value(
+                            &mut self.feature,
+                            feature,
+                            ui.localize(feature.text()),
+                        )
+                        .on_hover_localized(feature.hover_text());
+                    }
+                })
+                .response
+                .on_hover_localized(self.feature.hover_text());
+        });
+        ui.end_row();
+
+        ui.add_enabled_ui(self.enabled, |ui| {
+            ui.label(ui.localize("linkage"))
+                .on_hover_localized("linkage.hover");
+        });
+        ui.add_enabled_ui(self.enabled, |ui| {
+            ComboBox::from_id_salt(ui.next_auto_id())
+                .selected_text(ui.localize(self.linkage.text()))
+                .show_ui(ui, |ui| {
+                    for linkage in Linkage::VARIANTS {
+                        ui.selectable_value(
+                            &mut self.linkage,
+                            linkage,
+                            ui.localize(linkage.text()),
+                        )
+                        .on_hover_localized(linkage.hover_text());
+                    }
+                })
+                .response
+                .on_hover_localized(self.linkage.hover_text());
+        });
+        ui.end_row();
+
+        ui.add_enabled_ui(self.enabled, |ui| {
+            ui.label(ui.localize("clusters"))
+                .on_hover_localized("clusters.hover");
+        });
+        ui.add_enabled_ui(self.enabled, |ui| {
+            ui.add(DragValue::new(&mut self.clusters).range(1..=u8::MAX as usize));
+        });
+        ui.end_row();
+        // Out of range clusters
+        selection
+            .values
+            .retain(|&cluster| cluster as usize <= self.clusters);
+
+        // Cluster filter
+        ui.add_enabled_ui(self.enabled, |ui| {
+            ui.label(ui.localize("filter-by-cluster"))
+                .on_hover_localized("filter-by-cluster.hover");
+        });
+        ui.add_enabled_ui(self.enabled, |ui| {
+            ui.horizontal(|ui| {
+                selection.kind.show(ui);
+                let text = format_list_truncated!(&selection.values, 2);
+                ComboBox::from_id_salt("ClusterFilter")
+                    .close_behavior(PopupCloseBehavior::CloseOnClickOutside)
+                    .selected_text(text)
+                    .show_ui(ui, |ui| {
+                        for cluster in 1..=self.clusters as u32 {
+                            let checked = selection.values.contains(&cluster);
+                            if ui.selectable_label(checked, cluster.to_string()).clicked() {
+                                if checked {
+                                    selection.values.remove_by_value(&cluster);
+                                } else {
+                                    selection.values.push(cluster);
+                                }
+                            }
+                        }
+                    });
+            });
+        });
+        ui.end_row();
+    }
+}
+
+impl Default for Clustering {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Clustering feature
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub(crate) enum Feature {
+    EquivalentChainLength,
+    RetentionTime,
+}
+
+impl Feature {
+    pub(crate) const VARIANTS: [Self; 2] = [Self::EquivalentChainLength, Self::RetentionTime];
+}
+
+impl Text for Feature {
+    fn text(&self) -> &'static str {
+        match self {
+            Self::EquivalentChainLength => "equivalent-chain-length",
+            Self::RetentionTime => "retention-time",
+        }
+    }
+
+    fn hover_text(&self) -> &'static str {
+        match self {
+            Self::EquivalentChainLength => "equivalent-chain-length.abbreviation",
+            Self::RetentionTime => "absolute-retention-time.hover",
+        }
+    }
+}
+
+/// Clustering linkage
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub(crate) enum Linkage {
+    Single,
+    Complete,
+    Average,
+}
+
+impl Linkage {
+    pub(crate) const VARIANTS: [Self; 3] = [Self::Single, Self::Complete, Self::Average];
+}
+
+impl Text for Linkage {
+    fn text(&self) -> &'static str {
+        match self {
+            Self::Single => "single-linkage",
+            Self::Complete => "complete-linkage",
+            Self::Average => "average-linkage",
+        }
+    }
+
+    fn hover_text(&self) -> &'static str {
+        match self {
+            Self::Single => "single-linkage.hover",
+            Self::Complete => "complete-linkage.hover",
+            Self::Average => "average-linkage.hover",
+        }
+    }
+}
+
+/// Grouping of the table rows
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub(crate) enum Grouping {
+    #[default]
+    None,
+    /// By the column and the mode
+    Mode,
+    /// By the pair of the fatty acids
+    Pair,
+}
+
+impl Grouping {
+    pub(crate) fn show(&mut self, ui: &mut Ui, variants: &[Self]) {
+        ComboBox::from_id_salt(ui.next_auto_id())
+            .selected_text(ui.localize(self.text()))
+            .show_ui(ui, |ui| {
+                for &variant in variants {
+                    ui.selectable_value(self, variant, ui.localize(variant.text()))
+                        .on_hover_localized(variant.hover_text());
+                }
+            })
+            .response
+            .on_hover_localized(self.hover_text());
+    }
+}
+
+impl Text for Grouping {
+    fn text(&self) -> &'static str {
+        match self {
+            Self::None => "no-grouping",
+            Self::Mode => "group-by-mode",
+            Self::Pair => "group-by-pair",
+        }
+    }
+
+    fn hover_text(&self) -> &'static str {
+        match self {
+            Self::None => "no-grouping.hover",
+            Self::Mode => "group-by-mode.hover",
+            Self::Pair => "group-by-pair.hover",
+        }
+    }
+}
+
+/// Range filter
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub(crate) struct Bounds {
+    pub(crate) min: Option<f64>,
+    pub(crate) max: Option<f64>,
+}
+
+impl Bounds {
+    pub(crate) fn new() -> Self {
+        Self {
+            min: None,
+            max: None,
+        }
+    }
+
+    fn show(&mut self, ui: &mut Ui, range: RangeInclusive<f64>, suffix: &str) {
+        ui.horizontal(|ui| {
+            for (bound, default, text) in [
+                (&mut self.min, *range.start(), "minimum"),
+                (&mut self.max, *range.end(), "maximum"),
+            ] {
+                let mut checked = bound.is_some();
+                if ui.checkbox(&mut checked, ui.localize(text)).changed() {
+                    *bound = checked.then_some(default);
+                }
+                if let Some(value) = bound {
+                    ui.add(DragValue::new(value).speed(0.1).suffix(suffix));
+                }
+            }
+        });
+    }
+}
+
+impl Hash for Bounds {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.min.map(|min| min.ord()).hash(state);
+        self.max.map(|max| max.ord()).hash(state);
+    }
+}
+
+/// Sort by
+#[derive(Clone, Debug, Deserialize, Hash, PartialEq, Serialize)]
+pub(crate) enum SortBy {
+    FattyAcid,
+    Time,
+}
+
+impl Text for SortBy {
+    fn text(&self) -> &'static str {
+        match self {
+            Self::FattyAcid => "sort-by-fatty-acids",
+            Self::Time => "sort-by-retention-time",
+        }
+    }
+
+    fn hover_text(&self) -> &'static str {
+        match self {
+            Self::FattyAcid => "sort-by-fatty-acids.hover",
+            Self::Time => "sort-by-retention-time.hover",
+        }
+    }
+}
+
+/// Order
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub(crate) enum Order {
+    Ascending,
+    Descending,
+}
+
+impl Not for Order {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        match self {
+            Self::Ascending => Self::Descending,
+            Self::Descending => Self::Ascending,
+        }
+    }
 }
 
 impl Text for Order {
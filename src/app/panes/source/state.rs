@@ -1,16 +1,45 @@
+use super::{edit::Editor, settings::TableColumn};
+use crate::app::panes::{explain::Key, properties::Form, search::Search, selection::Selection};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
-#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub(crate) struct State {
+    pub(crate) collapsed: HashSet<u64>,
+    pub(crate) editor: Editor,
+    pub(crate) explain: Option<(Key, TableColumn)>,
+    pub(crate) form: Form,
+    pub(crate) open_clustering_window: bool,
+    pub(crate) open_dead_time_window: bool,
+    pub(crate) open_edit_window: bool,
+    pub(crate) open_metadata_window: bool,
     pub(crate) open_settings_window: bool,
+    pub(crate) open_translation_window: bool,
     pub(crate) reset_table_state: bool,
+    pub(crate) search: Search,
+    pub(crate) selection: Option<Selection>,
+    /// Hash of the table of the selection
+    #[serde(skip)]
+    pub(crate) selection_hash: u64,
 }
 
 impl State {
     pub(crate) fn new() -> Self {
         Self {
+            collapsed: HashSet::new(),
+            editor: Editor::new(),
+            explain: None,
+            form: Form::new(),
+            open_clustering_window: false,
+            open_dead_time_window: false,
+            open_edit_window: false,
+            open_metadata_window: false,
             open_settings_window: false,
+            open_translation_window: false,
             reset_table_state: false,
+            search: Search::new(),
+            selection: None,
+            selection_hash: 0,
         }
     }
 }
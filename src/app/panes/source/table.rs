@@ -1,8 +1,29 @@
-use super::{ID_SOURCE, Settings, State};
-use crate::app::panes::{MARGIN, widgets::float::FloatValue};
-use egui::{Color32, Frame, Grid, Id, Margin, TextStyle, TextWrapMode, Ui};
+use super::{
+    ID_SOURCE, State, explain,
+    settings::{ColumnGroup, ColumnLayout, Field, Settings, SortBy, TableColumn},
+};
+use crate::{
+    app::{
+        computers::grouping::Group,
+        panes::{
+            MARGIN,
+            explain::key,
+            formatting::Styles,
+            grouping::{self, Row, Rows},
+            search::{Query, Searchable},
+            selection::Selection,
+            widgets::float::FloatValue,
+        },
+    },
+    localization::Text as _,
+    special::{column::mode::ColumnExt as _, data_frame::DataFrameExt as _},
+};
+use egui::{
+    Align, Color32, CursorIcon, Frame, Grid, Id, Margin, Sense, StrokeKind, TextStyle,
+    TextWrapMode, Ui, util::hash,
+};
 use egui_l20n::{ResponseExt, UiExt};
-use egui_phosphor::regular::HASH;
+use egui_phosphor::regular::{CARET_DOWN, CARET_RIGHT, HASH};
 use egui_table::{
     AutoSizeMode, CellInfo, Column, HeaderCellInfo, HeaderRow, Table, TableDelegate, TableState,
 };
@@ -12,46 +33,56 @@ use lipid::{
 };
 use polars::prelude::*;
 use std::ops::Range;
-
-const INDEX: Range<usize> = 0..1;
-const MODE: Range<usize> = INDEX.end..INDEX.end + 2;
-const FATTY_ACID: Range<usize> = MODE.end..MODE.end + 1;
-const RETENTION_TIME: Range<usize> = FATTY_ACID.end..FATTY_ACID.end + 3;
-const TEMPERATURE: Range<usize> = RETENTION_TIME.end..RETENTION_TIME.end + 1;
-const CHAIN_LENGTH: Range<usize> = TEMPERATURE.end..TEMPERATURE.end + 3;
-const MASS: Range<usize> = CHAIN_LENGTH.end..CHAIN_LENGTH.end + 1;
-const DERIVATIVE: Range<usize> = MASS.end..MASS.end + 2;
-const LEN: usize = DERIVATIVE.end;
-
-const TOP: &[Range<usize>] = &[
-    INDEX,
-    MODE,
-    FATTY_ACID,
-    RETENTION_TIME,
-    TEMPERATURE,
-    CHAIN_LENGTH,
-    MASS,
-    DERIVATIVE,
-];
+use tracing::error;
 
 /// Table view
 #[derive(Debug)]
 pub(super) struct TableView<'a> {
     data_frame: &'a DataFrame,
-    settings: &'a Settings,
+    settings: &'a mut Settings,
     state: &'a mut State,
+    styles: Styles<'a, Field>,
+    columns: Vec<(ColumnGroup, ColumnLayout)>,
+    headers: Vec<String>,
+    query: Option<Query>,
+    rows: Rows<'a>,
 }
 
 impl<'a> TableView<'a> {
-    pub(super) const fn new(
+    pub(super) fn new(
         data_frame: &'a DataFrame,
-        settings: &'a Settings,
+        settings: &'a mut Settings,
         state: &'a mut State,
+        styles: Styles<'a, Field>,
+        groups: &'a [Group],
+        table: u64,
     ) -> Self {
+        if let Some(row) = state.search.scroll {
+            grouping::expand(groups, &mut state.collapsed, row);
+        }
+        let mut collapsed = state.collapsed.iter().copied().collect::<Vec<_>>();
+        collapsed.sort_unstable();
+        Selection::reset(
+            &mut state.selection,
+            &mut state.selection_hash,
+            hash((table, collapsed)),
+        );
+        let rows = Rows::new(groups, &state.collapsed, data_frame.height());
+        let columns = settings
+            .layout
+            .visible()
+            .map(|(group, column)| (group, *column))
+            .collect();
+        let query = state.search.query();
         Self {
             data_frame,
             settings,
             state,
+            styles,
+            columns,
+            headers: Vec::new(),
+            query,
+            rows,
         }
     }
 }
@@ -65,121 +96,331 @@ impl TableView<'_> {
             self.state.reset_table_state = false;
         }
         let height = ui.text_style_height(&TextStyle::Heading) + 2.0 * MARGIN.y;
-        let num_rows = self.data_frame.height() as _;
-        let num_columns = LEN;
-        Table::new()
+        let num_rows = self.rows.len() as _;
+        let num_columns = self.columns.len();
+        self.headers = self.headers(ui);
+        let mut table = Table::new()
             .id_salt(id_salt)
             .num_rows(num_rows)
             .columns(vec![
                 Column::default().resizable(self.settings.resizable);
                 num_columns
             ])
-            .num_sticky_cols(self.settings.sticky)
+            .num_sticky_cols(self.settings.sticky.min(num_columns))
             .headers([
                 HeaderRow {
                     height,
-                    groups: TOP.to_vec(),
+                    groups: self.groups(),
                 },
                 HeaderRow::new(height),
             ])
-            .auto_size_mode(AutoSizeMode::OnParentResize)
-            .show(ui, self);
+            .auto_size_mode(AutoSizeMode::OnParentResize);
+        if let Some(row) = self
+            .state
+            .search
+            .scroll
+            .take()
+            .and_then(|row| self.rows.position(row))
+        {
+            table = table.scroll_to_row(row as _, Some(Align::Center));
+        }
+        table.show(ui, self);
+        if let Some(selection) = self.state.selection {
+            selection.copy_ui(
+                ui,
+                None,
+                [self.rows.len(), self.columns.len()],
+                |column| self.headers[column].clone(),
+                |row, column| self.table_cell_text(row, column),
+            );
+        }
+    }
+
+    /// Localized headers of the columns with the converted units
+    fn headers(&self, ui: &Ui) -> Vec<String> {
+        self.columns
+            .iter()
+            .map(|(_, layout)| {
+                let text = ui.localize(layout.column.text());
+                match layout.unit {
+                    Some(unit) => format!("{text}, {}", unit.symbol()),
+                    None => text,
+                }
+            })
+            .collect()
+    }
+
+    /// Header of the group, toggled on the click
+    fn group_cell_ui(&mut self, ui: &mut Ui, index: usize, column: usize) {
+        let groups = self.rows.groups;
+        let group = &groups[index];
+        ui.painter()
+            .rect_filled(ui.max_rect(), 0.0, ui.visuals().widgets.inactive.bg_fill);
+        let response = ui.interact(ui.max_rect(), ui.id().with("Group"), Sense::click());
+        grouping::toggle(&response, group, &mut self.state.collapsed);
+        let precision = self.settings.precision;
+        response.on_hover_ui(|ui| group.summary.show(ui, precision));
+        Frame::new()
+            .inner_margin(Margin::from(MARGIN))
+            .show(ui, |ui| -> PolarsResult<()> {
+                if self.columns[column].1.column == TableColumn::Index {
+                    let collapsed = self.state.collapsed.contains(&group.key);
+                    ui.label(if collapsed { CARET_RIGHT } else { CARET_DOWN });
+                } else if self.is_grouped(column) {
+                    self.body_cell_content_ui(ui, group.rows[0], column)?;
+                } else if column == self.summary_column() {
+                    ui.label(group.summary.text(precision));
+                }
+                Ok(())
+            })
+            .inner
+            .unwrap();
+    }
+
+    /// Text of the cell of the table row
+    fn table_cell_text(&self, row: usize, column: usize) -> PolarsResult<String> {
+        match self.rows.get(row) {
+            Row::Group(index) => self.group_cell_text(index, column),
+            Row::Data(row) => self.cell_text(row, column),
+        }
+    }
+
+    /// Text of the cell of the group header
+    fn group_cell_text(&self, index: usize, column: usize) -> PolarsResult<String> {
+        let group = &self.rows.groups[index];
+        Ok(if self.is_grouped(column) {
+            self.cell_text(group.rows[0], column)?
+        } else if column == self.summary_column() {
+            group.summary.text(self.settings.precision)
+        } else {
+            String::new()
+        })
+    }
+
+    /// Whether the column is of the grouped values
+    fn is_grouped(&self, column: usize) -> bool {
+        matches!(
+            self.columns[column].1.column,
+            TableColumn::Column | TableColumn::OnsetTemperature | TableColumn::TemperatureStep
+        )
+    }
+
+    /// Column of the group summary, the first one after the grouped columns
+    fn summary_column(&self) -> usize {
+        (0..self.columns.len())
+            .rposition(|column| self.is_grouped(column))
+            .map_or(1, |column| column + 1)
+    }
+
+    /// Whether the cell matches the search query
+    fn matches(&self, row: usize, column: usize) -> PolarsResult<bool> {
+        match &self.query {
+            Some(query) => self.cells().matches(query, row, column),
+            None => Ok(false),
+        }
+    }
+
+    /// Text of the cell as it is displayed
+    fn cell_text(&self, row: usize, column: usize) -> PolarsResult<String> {
+        self.cells().text(row, column)
+    }
+
+    /// Displayed cells of the table frame
+    fn cells(&self) -> Cells<'_> {
+        Cells {
+            data_frame: self.data_frame,
+            columns: &self.columns,
+            precision: self.settings.precision,
+        }
+    }
+
+    /// Ranges of the adjacent visible columns of the same group
+    fn groups(&self) -> Vec<Range<usize>> {
+        let mut groups = Vec::<Range<usize>>::new();
+        for (index, (group, _)) in self.columns.iter().enumerate() {
+            match groups.last_mut() {
+                Some(last) if self.columns[last.start].0 == *group => last.end = index + 1,
+                _ => groups.push(index..index + 1),
+            }
+        }
+        groups
     }
 
     fn header_cell_content_ui(&mut self, ui: &mut Ui, row: usize, column: Range<usize>) {
         if self.settings.truncate {
             ui.style_mut().wrap_mode = Some(TextWrapMode::Truncate);
         }
-        match (row, column) {
-            // Top
-            (0, INDEX) => {
+        let Some(&(group, layout)) = self.columns.get(column.start) else {
+            return;
+        };
+        // Unit converted from the computed one
+        let unit = layout
+            .unit
+            .filter(|unit| Some(unit) != layout.column.units().first());
+        ui.horizontal(|ui| {
+            match row {
+                0 => self.top_header_ui(ui, group),
+                _ if !group.is_single() => self.bottom_header_ui(ui, layout.column),
+                _ => return,
+            }
+            if let Some(unit) = unit {
+                if row == 0 && !group.is_single() {
+                    return;
+                }
+                ui.label(unit.symbol());
+            }
+        });
+    }
+
+    fn top_header_ui(&mut self, ui: &mut Ui, group: ColumnGroup) {
+        match group {
+            ColumnGroup::Index => {
                 ui.heading(HASH).on_hover_localized("index");
             }
-            (0, MODE) => {
+            ColumnGroup::Column => {
+                ui.heading(ui.localize("column"))
+                    .on_hover_localized("column.hover");
+            }
+            ColumnGroup::Mode => {
                 ui.heading(ui.localize("mode"))
                     .on_hover_localized("mode.hover");
             }
-            (0, FATTY_ACID) => {
-                ui.heading(ui.localize("fatty-acid"))
+            ColumnGroup::FattyAcid => {
+                let response = ui
+                    .heading(ui.localize("fatty-acid"))
+                    .interact(Sense::click())
+                    .on_hover_cursor(CursorIcon::PointingHand)
                     .on_hover_localized("fatty-acid.abbreviation");
+                if response.clicked() {
+                    if self.settings.sort == SortBy::FattyAcid && self.settings.sort_keys.is_empty()
+                    {
+                        self.settings.order = !self.settings.order;
+                    }
+                    self.settings.sort = SortBy::FattyAcid;
+                    self.settings.sort_keys.clear();
+                }
             }
-            (0, RETENTION_TIME) => {
+            ColumnGroup::RetentionTime => {
                 ui.heading(ui.localize("retention-time"))
                     .on_hover_localized("retention-time.abbreviation")
                     .on_hover_localized("retention-time.hover");
             }
-            (0, TEMPERATURE) => {
-                ui.heading(ui.localize("temperature"))
+            ColumnGroup::Temperature => {
+                let text = ui.localize("temperature");
+                self.settings
+                    .sort_keys
+                    .header(ui, text, Field::Temperature)
                     .on_hover_localized("temperature.abbreviation")
                     .on_hover_localized("temperature.hover");
             }
-            (0, CHAIN_LENGTH) => {
+            ColumnGroup::ChainLength => {
                 ui.heading(ui.localize("chain-length"))
                     .on_hover_localized("chain-length.hover");
             }
-            (0, MASS) => {
-                ui.heading(ui.localize("mass"))
+            ColumnGroup::Mass => {
+                let text = ui.localize("mass");
+                self.settings
+                    .sort_keys
+                    .header(ui, text, Field::Mass)
                     .on_hover_localized("mass.hover");
             }
-            (0, DERIVATIVE) => {
+            ColumnGroup::Derivative => {
                 ui.heading(ui.localize("derivative"))
                     .on_hover_localized("derivative.hover");
             }
-            // Bottom
-            (1, mode::ONSET) => {
+            _ => {}
+        }
+    }
+
+    fn bottom_header_ui(&mut self, ui: &mut Ui, column: TableColumn) {
+        match column {
+            TableColumn::OnsetTemperature => {
                 ui.heading(ui.localize("onset-temperature.abbreviation"))
                     .on_hover_localized("onset-temperature");
             }
-            (1, mode::STEP) => {
+            TableColumn::TemperatureStep => {
                 ui.heading(ui.localize("temperature-step.abbreviation"))
                     .on_hover_localized("temperature-step")
                     .on_hover_localized("temperature-step.hover");
             }
-            (1, retention_time::ABSOLUTE) => {
-                ui.heading(ui.localize("absolute-retention-time"))
+            TableColumn::AbsoluteRetentionTime => {
+                let text = ui.localize("absolute-retention-time");
+                self.settings
+                    .sort_keys
+                    .header(ui, text, Field::RetentionTime)
                     .on_hover_localized("absolute-retention-time.hover");
             }
-            (1, retention_time::RELATIVE) => {
-                ui.heading(ui.localize("relative-retention-time"))
+            TableColumn::RelativeRetentionTime => {
+                let text = ui.localize("relative-retention-time");
+                self.settings
+                    .sort_keys
+                    .header(ui, text, Field::RelativeRetentionTime)
                     .on_hover_localized("relative-retention-time.hover");
             }
-            (1, retention_time::DELTA) => {
-                ui.heading(ui.localize("delta-retention-time"))
+            TableColumn::DeltaRetentionTime => {
+                let text = ui.localize("delta-retention-time");
+                self.settings
+                    .sort_keys
+                    .header(ui, text, Field::DeltaRetentionTime)
                     .on_hover_localized("delta-retention-time.hover");
             }
-            (1, chain_length::ECL) => {
-                ui.heading(ui.localize("equivalent-chain-length.abbreviation"))
+            TableColumn::EquivalentChainLength => {
+                let text = ui.localize("equivalent-chain-length.abbreviation");
+                self.settings
+                    .sort_keys
+                    .header(ui, text, Field::EquivalentChainLength)
                     .on_hover_localized("equivalent-chain-length");
             }
-            (1, chain_length::FCL) => {
-                ui.heading(ui.localize("fractional-chain-length.abbreviation"))
+            TableColumn::FractionalChainLength => {
+                let text = ui.localize("fractional-chain-length.abbreviation");
+                self.settings
+                    .sort_keys
+                    .header(ui, text, Field::FractionalChainLength)
                     .on_hover_localized("fractional-chain-length");
             }
-            (1, chain_length::ECN) => {
-                ui.heading(ui.localize("equivalent-carbon-number.abbreviation"))
+            TableColumn::EquivalentCarbonNumber => {
+                let text = ui.localize("equivalent-carbon-number.abbreviation");
+                self.settings
+                    .sort_keys
+                    .header(ui, text, Field::EquivalentCarbonNumber)
                     .on_hover_localized("equivalent-carbon-number");
             }
-            (1, derivative::SLOPE) => {
-                ui.heading(ui.localize("slope"));
+            TableColumn::Slope => {
+                let text = ui.localize("slope");
+                self.settings
+                    .sort_keys
+                    .header(ui, text, Field::Slope)
+                    .on_hover_localized("slope.hover");
             }
-            (1, derivative::ANGLE) => {
-                ui.heading(ui.localize("angle"));
+            TableColumn::Angle => {
+                let text = ui.localize("angle");
+                self.settings
+                    .sort_keys
+                    .header(ui, text, Field::Angle)
+                    .on_hover_localized("angle.hover");
             }
             _ => {}
         }
     }
 
-    fn body_cell_content_ui(
-        &mut self,
-        ui: &mut Ui,
-        row: usize,
-        column: Range<usize>,
-    ) -> PolarsResult<()> {
-        match (row, column) {
-            (row, INDEX) => {
+    fn body_cell_content_ui(&mut self, ui: &mut Ui, row: usize, column: usize) -> PolarsResult<()> {
+        let (_, layout) = self.columns[column];
+        let precision = Some(layout.precision(self.settings.precision));
+        match layout.column {
+            TableColumn::Index => {
                 ui.label(row.to_string());
             }
-            (row, mode::ONSET) => {
+            TableColumn::Column => {
+                let Some(gc_column) = self.data_frame.gc_column() else {
+                    polars_bail!(ColumnNotFound: "Column");
+                };
+                let description = gc_column.description(row)?;
+                let response = ui.label(gc_column.name.str()?.get(row).unwrap_or_default());
+                if !description.is_empty() {
+                    response.on_hover_text(description);
+                }
+            }
+            TableColumn::OnsetTemperature => {
                 let mode = self.data_frame["Mode"].struct_()?;
                 let onset_temperature = mode.field_by_name("OnsetTemperature")?;
                 ui.label(onset_temperature.str_value(row)?)
@@ -195,20 +436,45 @@ impl TableView<'_> {
                         .unwrap()
                     });
             }
-            (row, mode::STEP) => {
+            TableColumn::TemperatureStep => {
                 let mode = self.data_frame["Mode"].struct_()?;
                 let temperature_step = mode.field_by_name("TemperatureStep")?;
-                ui.label(temperature_step.str_value(row)?);
+                let response = ui.label(temperature_step.str_value(row)?);
+                let mode = self.data_frame["Mode"].mode();
+                if mode.is_program() {
+                    if let Some(program) =
+                        mode.program(row, self.settings.conditions.maximum_temperature)?
+                    {
+                        response.on_hover_ui(|ui| {
+                            ui.heading(ui.localize("temperature-program"));
+                            ui.label(program.to_string());
+                            ui.label(format!(
+                                "{}: {} °C",
+                                ui.localize("final-temperature"),
+                                program.final_temperature(),
+                            ));
+                        });
+                    }
+                }
             }
-            (row, FATTY_ACID) => {
+            TableColumn::FattyAcid => {
                 let fatty_acids = self.data_frame.fa();
                 let Some(fatty_acid) = fatty_acids.get(row)? else {
                     polars_bail!(NoData: "FattyAcid[{row}]");
                 };
                 let text = format!("{:#}", fatty_acid.display(COMMON));
-                ui.label(&text).on_hover_text(&text);
+                let response = ui.label(&text).on_hover_text(&text);
+                if let Ok(cluster) = self
+                    .data_frame
+                    .column("Cluster")
+                    .and_then(|column| column.u32())
+                {
+                    if let Some(cluster) = cluster.get(row) {
+                        response.on_hover_text(format!("{} {cluster}", ui.localize("cluster")));
+                    }
+                }
             }
-            (row, retention_time::ABSOLUTE) => {
+            TableColumn::AbsoluteRetentionTime => {
                 let retention_time = self.data_frame["RetentionTime"].struct_()?;
                 let absolute = retention_time.field_by_name("Absolute")?;
                 let absolute = absolute.struct_()?;
@@ -220,96 +486,93 @@ impl TableView<'_> {
                         ui.visuals_mut().override_text_color = Some(Color32::YELLOW);
                     }
                 }
-                ui.add(
-                    FloatValue::new(mean.f64()?.get(row)).precision(Some(self.settings.precision)),
-                )
-                .on_hover_ui(|ui| {
-                    (|| -> PolarsResult<()> {
-                        let mean = mean.str_value(row)?;
-                        let standard_deviation = absolute.field_by_name("StandardDeviation")?;
-                        let standard_deviation = standard_deviation.str_value(row)?;
-                        ui.horizontal(|ui| {
-                            ui.label(mean);
-                            ui.label("±");
-                            ui.label(standard_deviation);
-                        });
-                        Ok(())
-                    })()
-                    .unwrap()
-                })
-                .on_hover_ui(|ui| {
-                    (|| -> PolarsResult<()> {
-                        ui.heading("Repeats");
-                        let Some(values) =
-                            absolute.field_by_name("Values")?.list()?.get_as_series(row)
-                        else {
-                            polars_bail!(NoData: "Values[{row}]");
-                        };
-                        ui.vertical(|ui| {
-                            for value in values.iter() {
-                                ui.label(value.to_string());
-                            }
-                        });
-                        Ok(())
-                    })()
-                    .unwrap()
-                });
+                ui.add(FloatValue::new(layout.convert(mean.f64()?.get(row))).precision(precision))
+                    .on_hover_ui(|ui| {
+                        (|| -> PolarsResult<()> {
+                            let mean = mean.str_value(row)?;
+                            let standard_deviation = absolute.field_by_name("StandardDeviation")?;
+                            let standard_deviation = standard_deviation.str_value(row)?;
+                            ui.horizontal(|ui| {
+                                ui.label(mean);
+                                ui.label("±");
+                                ui.label(standard_deviation);
+                            });
+                            Ok(())
+                        })()
+                        .unwrap()
+                    })
+                    .on_hover_ui(|ui| {
+                        (|| -> PolarsResult<()> {
+                            ui.heading("Repeats");
+                            let Some(values) =
+                                absolute.field_by_name("Values")?.list()?.get_as_series(row)
+                            else {
+                                polars_bail!(NoData: "Values[{row}]");
+                            };
+                            ui.vertical(|ui| {
+                                for value in values.iter() {
+                                    ui.label(value.to_string());
+                                }
+                            });
+                            Ok(())
+                        })()
+                        .unwrap()
+                    });
             }
-            (row, retention_time::RELATIVE) => {
+            TableColumn::RelativeRetentionTime => {
                 let retention_time = self.data_frame["RetentionTime"].struct_()?;
                 let relative = retention_time.field_by_name("Relative")?;
                 ui.add(
-                    FloatValue::new(relative.f64()?.get(row))
-                        .precision(Some(self.settings.precision))
+                    FloatValue::new(layout.convert(relative.f64()?.get(row)))
+                        .precision(precision)
                         .hover(),
                 );
             }
-            (row, retention_time::DELTA) => {
+            TableColumn::DeltaRetentionTime => {
                 let retention_time = self.data_frame["RetentionTime"].struct_()?;
                 let delta = retention_time.field_by_name("Delta")?;
                 ui.add(
-                    FloatValue::new(delta.f64()?.get(row))
-                        .precision(Some(self.settings.precision))
+                    FloatValue::new(layout.convert(delta.f64()?.get(row)))
+                        .precision(precision)
                         .hover(),
                 );
             }
-            (row, TEMPERATURE) => {
+            TableColumn::Temperature => {
                 let temperature = &self.data_frame["Temperature"];
                 ui.add(
-                    FloatValue::new(temperature.f64()?.get(row))
-                        .precision(Some(self.settings.precision))
+                    FloatValue::new(layout.convert(temperature.f64()?.get(row)))
+                        .precision(precision)
                         .hover(),
                 );
             }
-            (row, chain_length::ECL) => {
+            TableColumn::EquivalentChainLength => {
                 let chain_length = self.data_frame["ChainLength"].struct_()?;
                 let ecl = chain_length.field_by_name("EquivalentChainLength")?;
                 ui.add(
-                    FloatValue::new(ecl.f64()?.get(row))
-                        .precision(Some(self.settings.precision))
+                    FloatValue::new(layout.convert(ecl.f64()?.get(row)))
+                        .precision(precision)
                         .hover(),
                 );
             }
-            (row, chain_length::FCL) => {
+            TableColumn::FractionalChainLength => {
                 let chain_length = self.data_frame["ChainLength"].struct_()?;
                 let fcl = chain_length.field_by_name("FCL")?;
                 ui.add(
-                    FloatValue::new(fcl.f64()?.get(row))
-                        .precision(Some(self.settings.precision))
+                    FloatValue::new(layout.convert(fcl.f64()?.get(row)))
+                        .precision(precision)
                         .hover(),
                 );
             }
-            (row, chain_length::ECN) => {
+            TableColumn::EquivalentCarbonNumber => {
                 let chain_length = self.data_frame["ChainLength"].struct_()?;
                 let ecn = chain_length.field_by_name("ECN")?;
                 ui.label(ecn.str_value(row)?);
             }
-            (row, MASS) => {
+            TableColumn::Mass => {
                 let mass = self.data_frame["Mass"].struct_()?;
                 let rcooch3 = mass.field_by_name("RCOOCH3")?;
                 ui.add(
-                    FloatValue::new(rcooch3.f64()?.get(row))
-                        .precision(Some(self.settings.precision)),
+                    FloatValue::new(layout.convert(rcooch3.f64()?.get(row))).precision(precision),
                 )
                 .on_hover_ui(|ui| {
                     Grid::new(ui.next_auto_id()).show(ui, |ui| {
@@ -338,30 +601,155 @@ impl TableView<'_> {
                     });
                 });
             }
-            (row, derivative::SLOPE) => {
+            TableColumn::Slope => {
                 let derivative = self.data_frame["Derivative"].struct_()?;
                 let slope = derivative.field_by_name("Slope")?;
                 ui.add(
-                    FloatValue::new(slope.f64()?.get(row))
-                        .precision(Some(self.settings.precision))
+                    FloatValue::new(layout.convert(slope.f64()?.get(row)))
+                        .precision(precision)
                         .hover(),
                 );
             }
-            (row, derivative::ANGLE) => {
+            TableColumn::Angle => {
                 let derivative = self.data_frame["Derivative"].struct_()?;
                 let angle = derivative.field_by_name("Angle")?;
                 ui.add(
-                    FloatValue::new(angle.f64()?.get(row))
-                        .precision(Some(self.settings.precision))
+                    FloatValue::new(layout.convert(angle.f64()?.get(row)))
+                        .precision(precision)
                         .hover(),
                 );
             }
-            _ => unreachable!(),
         }
         Ok(())
     }
 }
 
+/// Displayed cells of the visible columns
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Cells<'a> {
+    pub(super) data_frame: &'a DataFrame,
+    pub(super) columns: &'a [(ColumnGroup, ColumnLayout)],
+    pub(super) precision: usize,
+}
+
+impl Cells<'_> {
+    /// Text of the cell as it is displayed
+    fn text(&self, row: usize, column: usize) -> PolarsResult<String> {
+        let (_, layout) = self.columns[column];
+        let precision = layout.precision(self.precision);
+        let float = |value: Option<f64>| {
+            layout
+                .convert(value)
+                .map(|value| format!("{value:.precision$}"))
+                .unwrap_or_default()
+        };
+        Ok(match layout.column {
+            TableColumn::Index => row.to_string(),
+            TableColumn::Column => match self.data_frame.gc_column() {
+                Some(gc_column) => gc_column
+                    .name
+                    .str()?
+                    .get(row)
+                    .unwrap_or_default()
+                    .to_owned(),
+                None => String::new(),
+            },
+            TableColumn::OnsetTemperature => self.data_frame["Mode"]
+                .struct_()?
+                .field_by_name("OnsetTemperature")?
+                .str_value(row)?
+                .into_owned(),
+            TableColumn::TemperatureStep => self.data_frame["Mode"]
+                .struct_()?
+                .field_by_name("TemperatureStep")?
+                .str_value(row)?
+                .into_owned(),
+            TableColumn::FattyAcid => match self.data_frame.fa().get(row)? {
+                Some(fatty_acid) => format!("{:#}", fatty_acid.display(COMMON)),
+                None => String::new(),
+            },
+            TableColumn::AbsoluteRetentionTime => {
+                let retention_time = self.data_frame["RetentionTime"].struct_()?;
+                let absolute = retention_time.field_by_name("Absolute")?;
+                float(absolute.struct_()?.field_by_name("Mean")?.f64()?.get(row))
+            }
+            TableColumn::RelativeRetentionTime => {
+                let retention_time = self.data_frame["RetentionTime"].struct_()?;
+                float(retention_time.field_by_name("Relative")?.f64()?.get(row))
+            }
+            TableColumn::DeltaRetentionTime => {
+                let retention_time = self.data_frame["RetentionTime"].struct_()?;
+                float(retention_time.field_by_name("Delta")?.f64()?.get(row))
+            }
+            TableColumn::Temperature => float(self.data_frame["Temperature"].f64()?.get(row)),
+            TableColumn::EquivalentChainLength => {
+                let chain_length = self.data_frame["ChainLength"].struct_()?;
+                float(
+                    chain_length
+                        .field_by_name("EquivalentChainLength")?
+                        .f64()?
+                        .get(row),
+                )
+            }
+            TableColumn::FractionalChainLength => {
+                let chain_length = self.data_frame["ChainLength"].struct_()?;
+                float(chain_length.field_by_name("FCL")?.f64()?.get(row))
+            }
+            TableColumn::EquivalentCarbonNumber => self.data_frame["ChainLength"]
+                .struct_()?
+                .field_by_name("ECN")?
+                .str_value(row)?
+                .into_owned(),
+            TableColumn::Mass => {
+                let mass = self.data_frame["Mass"].struct_()?;
+                float(mass.field_by_name("RCOOCH3")?.f64()?.get(row))
+            }
+            TableColumn::Slope => {
+                let derivative = self.data_frame["Derivative"].struct_()?;
+                float(derivative.field_by_name("Slope")?.f64()?.get(row))
+            }
+            TableColumn::Angle => {
+                let derivative = self.data_frame["Derivative"].struct_()?;
+                float(derivative.field_by_name("Angle")?.f64()?.get(row))
+            }
+        })
+    }
+}
+
+impl Searchable for Cells<'_> {
+    fn height(&self) -> usize {
+        self.data_frame.height()
+    }
+
+    fn width(&self) -> usize {
+        self.columns.len()
+    }
+
+    fn fatty_acids(&self, column: usize) -> PolarsResult<Option<Series>> {
+        Ok(match self.columns[column].1.column {
+            TableColumn::FattyAcid => Some(
+                self.data_frame["FattyAcid"]
+                    .as_materialized_series()
+                    .clone(),
+            ),
+            _ => None,
+        })
+    }
+
+    fn matches(&self, query: &Query, row: usize, column: usize) -> PolarsResult<bool> {
+        Ok(match self.columns[column].1.column {
+            TableColumn::Index => false,
+            TableColumn::FattyAcid => {
+                query.fatty_acid(self.data_frame["FattyAcid"].as_materialized_series(), row)?
+            }
+            TableColumn::OnsetTemperature | TableColumn::TemperatureStep => {
+                query.mode(self.data_frame, row)? || query.text(&self.text(row, column)?)
+            }
+            _ => query.text(&self.text(row, column)?),
+        })
+    }
+}
+
 impl TableDelegate for TableView<'_> {
     fn header_cell_ui(&mut self, ui: &mut Ui, cell: &HeaderCellInfo) {
         Frame::new()
@@ -372,45 +760,69 @@ impl TableDelegate for TableView<'_> {
     }
 
     fn cell_ui(&mut self, ui: &mut Ui, cell: &CellInfo) {
+        let row = match self.rows.get(cell.row_nr as _) {
+            Row::Group(index) => return self.group_cell_ui(ui, index, cell.col_nr),
+            Row::Data(row) => row,
+        };
         if cell.row_nr % 2 == 0 {
             ui.painter()
                 .rect_filled(ui.max_rect(), 0.0, ui.visuals().faint_bg_color);
         }
+        let column = self.columns[cell.col_nr].1.column;
+        if let Some(color) = self
+            .styles
+            .cell(row, |field| field.column() == column)
+            .or_else(|| self.styles.row(row))
+        {
+            ui.painter().rect_filled(ui.max_rect(), 0.0, color);
+        }
+        match self.matches(row, cell.col_nr) {
+            Ok(true) => {
+                let color = ui.visuals().warn_fg_color.gamma_multiply(0.25);
+                ui.painter().rect_filled(ui.max_rect(), 0.0, color);
+            }
+            Ok(false) => {}
+            Err(error) => error!(%error),
+        }
+        if self.state.search.row() == Some(row) {
+            ui.painter().rect_stroke(
+                ui.max_rect(),
+                0.0,
+                ui.visuals().selection.stroke,
+                StrokeKind::Inside,
+            );
+        }
+        let response = ui.interact(ui.max_rect(), ui.id().with("Cell"), Sense::click());
+        Selection::click(
+            &mut self.state.selection,
+            &response,
+            [cell.row_nr as _, cell.col_nr],
+            self.columns.len(),
+            column == TableColumn::Index,
+        );
+        if response.double_clicked() && explain::is_explained(column) {
+            match key(self.data_frame, row) {
+                Ok(key) => self.state.explain = Some((key, column)),
+                Err(error) => error!(%error),
+            }
+        }
+        if let Some(selection) = self.state.selection {
+            if selection.contains(cell.row_nr as _, cell.col_nr) {
+                let color = ui.visuals().selection.bg_fill.gamma_multiply(0.5);
+                ui.painter().rect_filled(ui.max_rect(), 0.0, color);
+            }
+            selection.copy_ui(
+                ui,
+                Some(&response),
+                [self.rows.len(), self.columns.len()],
+                |column| self.headers[column].clone(),
+                |row, column| self.table_cell_text(row, column),
+            );
+        }
         Frame::new()
             .inner_margin(Margin::from(MARGIN))
             .show(ui, |ui| {
-                self.body_cell_content_ui(ui, cell.row_nr as _, cell.col_nr..cell.col_nr + 1)
-                    .unwrap()
+                self.body_cell_content_ui(ui, row, cell.col_nr).unwrap()
             });
     }
 }
-
-mod mode {
-    use super::*;
-
-    pub(super) const ONSET: Range<usize> = MODE.start..MODE.start + 1;
-    pub(super) const STEP: Range<usize> = ONSET.end..ONSET.end + 1;
-}
-
-mod retention_time {
-    use super::*;
-
-    pub(super) const ABSOLUTE: Range<usize> = RETENTION_TIME.start..RETENTION_TIME.start + 1;
-    pub(super) const RELATIVE: Range<usize> = ABSOLUTE.end..ABSOLUTE.end + 1;
-    pub(super) const DELTA: Range<usize> = RELATIVE.end..RELATIVE.end + 1;
-}
-
-mod chain_length {
-    use super::*;
-
-    pub(super) const ECL: Range<usize> = CHAIN_LENGTH.start..CHAIN_LENGTH.start + 1;
-    pub(super) const FCL: Range<usize> = ECL.end..ECL.end + 1;
-    pub(super) const ECN: Range<usize> = FCL.end..FCL.end + 1;
-}
-
-mod derivative {
-    use super::*;
-
-    pub(super) const SLOPE: Range<usize> = DERIVATIVE.start..DERIVATIVE.start + 1;
-    pub(super) const ANGLE: Range<usize> = SLOPE.end..SLOPE.end + 1;
-}
@@ -0,0 +1,137 @@
+use crate::{
+    dead_time::{CarrierGas, Conditions, Operation},
+    localization::Text as _,
+};
+use egui::{ComboBox, DragValue, Grid, Response, Ui, Widget};
+use egui_l20n::{ResponseExt as _, UiExt as _};
+
+/// Column conditions widget
+#[derive(Debug)]
+pub(crate) struct ConditionsWidget<'a> {
+    conditions: &'a mut Conditions,
+}
+
+impl<'a> ConditionsWidget<'a> {
+    pub(crate) fn new(conditions: &'a mut Conditions) -> Self {
+        Self { conditions }
+    }
+}
+
+impl Widget for ConditionsWidget<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        Grid::new(ui.next_auto_id())
+            .show(ui, |ui| {
+                // Carrier gas
+                ui.label(ui.localize("carrier-gas"));
+                let mut response = ComboBox::from_id_salt(ui.next_auto_id())
+                    .selected_text(ui.localize(self.conditions.carrier_gas.text()))
+                    .show_ui(ui, |ui| {
+                        for carrier_gas in CarrierGas::VARIANTS {
+                            ui.selectable_value(
+                                &mut self.conditions.carrier_gas,
+                                carrier_gas,
+                                ui.localize(carrier_gas.text()),
+                            )
+                            .on_hover_localized(carrier_gas.hover_text());
+                        }
+                    })
+                    .response;
+                ui.end_row();
+
+                // Length
+                ui.label(ui.localize("column-length"));
+                response |= ui.add(
+                    DragValue::new(&mut self.conditions.geometry.length)
+                        .range(0.1..=f64::MAX)
+                        .speed(0.1)
+                        .suffix(" m"),
+                );
+                ui.end_row();
+
+                // Inner diameter
+                ui.label(ui.localize("column-diameter"));
+                response |= ui.add(
+                    DragValue::new(&mut self.conditions.geometry.diameter)
+                        .range(0.01..=1.0)
+                        .speed(0.001)
+                        .suffix(" mm"),
+                );
+                ui.end_row();
+
+                // Film thickness
+                ui.label(ui.localize("film-thickness"));
+                response |= ui.add(
+                    DragValue::new(&mut self.conditions.geometry.film_thickness)
+                        .range(0.01..=10.0)
+                        .speed(0.01)
+                        .suffix(" μm"),
+                );
+                ui.end_row();
+
+                // Maximum temperature
+                ui.label(ui.localize("maximum-temperature"))
+                    .on_hover_localized("maximum-temperature.hover");
+                response |= ui.add(
+                    DragValue::new(&mut self.conditions.maximum_temperature)
+                        .range(0.0..=500.0)
+                        .speed(1.0)
+                        .suffix(" °C"),
+                );
+                ui.end_row();
+
+                // Outlet pressure
+                ui.label(ui.localize("outlet-pressure"))
+                    .on_hover_localized("outlet-pressure.hover");
+                response |= ui.add(
+                    DragValue::new(&mut self.conditions.outlet_pressure)
+                        .range(0.0..=f64::MAX)
+                        .speed(0.1)
+                        .suffix(" kPa"),
+                );
+                ui.end_row();
+
+                // Operation
+                ui.label(ui.localize("operation"));
+                response |= ComboBox::from_id_salt(ui.next_auto_id())
+                    .selected_text(ui.localize(self.conditions.operation.text()))
+                    .show_ui(ui, |ui| {
+                        for operation in Operation::VARIANTS {
+                            ui.selectable_value(
+                                &mut self.conditions.operation,
+                                operation,
+                                ui.localize(operation.text()),
+                            )
+                            .on_hover_localized(operation.hover_text());
+                        }
+                    })
+                    .response;
+                ui.end_row();
+
+                match self.conditions.operation {
+                    Operation::ConstantPressure => {
+                        ui.label(ui.localize("inlet-pressure"))
+                            .on_hover_localized("inlet-pressure.hover");
+                        response |= ui.add(
+                            DragValue::new(&mut self.conditions.pressure)
+                                .range(0.1..=f64::MAX)
+                                .speed(0.1)
+                                .suffix(" kPa"),
+                        );
+                    }
+                    Operation::ConstantFlow => {
+                        ui.label(ui.localize("flow"))
+                            .on_hover_localized("flow.hover");
+                        response |= ui.add(
+                            DragValue::new(&mut self.conditions.flow)
+                                .range(0.001..=f64::MAX)
+                                .speed(0.01)
+                                .suffix(" mL/min"),
+                        );
+                    }
+                }
+                ui.end_row();
+                response
+            })
+            .inner
+    }
+}
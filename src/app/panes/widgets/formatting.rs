@@ -0,0 +1,155 @@
+use crate::{
+    app::panes::source::settings::{Comparison, Formatting, Rule, Threshold},
+    localization::Text,
+};
+use egui::{Button, Color32, ComboBox, DragValue, Response, Ui, Widget};
+use egui_l20n::{ResponseExt as _, UiExt as _};
+use egui_phosphor::regular::{PLUS, X};
+use std::fmt::Debug;
+
+/// Default highlight color
+const HIGHLIGHT: [u8; 3] = [255, 215, 0];
+
+/// Formatting widget
+///
+/// Removable chips of the rules, a row to add a new one and the saturation
+/// marking.
+#[derive(Debug)]
+pub(crate) struct FormattingWidget<'a, F> {
+    formatting: &'a mut Formatting<F>,
+    fields: &'a [F],
+}
+
+impl<'a, F> FormattingWidget<'a, F> {
+    pub(crate) fn new(formatting: &'a mut Formatting<F>, fields: &'a [F]) -> Self {
+        Self { formatting, fields }
+    }
+}
+
+impl<F> Widget for FormattingWidget<'_, F>
+where
+    F: Clone + Copy + Debug + Default + PartialEq + Send + Sync + Text + 'static,
+{
+    fn ui(self, ui: &mut Ui) -> Response {
+        ui.vertical(|ui| {
+            // Chips
+            ui.horizontal_wrapped(|ui| {
+                let mut remove = None;
+                for (index, rule) in self.formatting.rules.iter().enumerate() {
+                    let mut button = Button::new(format!("{} {X}", rule.text(ui))).small();
+                    if let Rule::Highlight {
+                        color: [red, green, blue],
+                        ..
+                    } = *rule
+                    {
+                        button = button.stroke((2.0, Color32::from_rgb(red, green, blue)));
+                    }
+                    if ui
+                        .add(button)
+                        .on_hover_localized(rule.field().hover_text())
+                        .clicked()
+                    {
+                        remove = Some(index);
+                    }
+                }
+                if let Some(index) = remove {
+                    self.formatting.rules.remove(index);
+                }
+            });
+            // New
+            let id = ui.auto_id_with("Rule");
+            let mut rule = ui.data_mut(|data| data.get_temp::<Rule<F>>(id).unwrap_or_default());
+            ui.horizontal(|ui| {
+                let field = rule.field();
+                let text = match rule {
+                    Rule::Scale { .. } => "color-scale",
+                    Rule::Highlight { .. } => "highlight",
+                };
+                ComboBox::from_id_salt(id.with("Kind"))
+                    .selected_text(ui.localize(text))
+                    .show_ui(ui, |ui| {
+                        if ui
+                            .selectable_label(
+                                matches!(rule, Rule::Scale { .. }),
+                                ui.localize("color-scale"),
+                            )
+                            .on_hover_localized("color-scale.hover")
+                            .clicked()
+                        {
+                            rule = Rule::Scale {
+                                field,
+                                reverse: false,
+                            };
+                        }
+                        if ui
+                            .selectable_label(
+                                matches!(rule, Rule::Highlight { .. }),
+                                ui.localize("highlight"),
+                            )
+                            .on_hover_localized("highlight.hover")
+                            .clicked()
+                        {
+                            rule = Rule::Highlight {
+                                threshold: Threshold {
+                                    field,
+                                    ..Default::default()
+                                },
+                                color: HIGHLIGHT,
+                            };
+                        }
+                    });
+                let field = match &mut rule {
+                    Rule::Scale { field, .. } => field,
+                    Rule::Highlight { threshold, .. } => &mut threshold.field,
+                };
+                ComboBox::from_id_salt(id.with("Field"))
+                    .selected_text(ui.localize(field.text()))
+                    .show_ui(ui, |ui| {
+                        for &selected_value in self.fields {
+                            ui.selectable_value(
+                                field,
+                                selected_value,
+                                ui.localize(selected_value.text()),
+                            )
+                            .on_hover_localized(selected_value.hover_text());
+                        }
+                    });
+                match &mut rule {
+                    Rule::Scale { reverse, .. } => {
+                        ui.toggle_value(reverse, ui.localize("reverse"))
+                            .on_hover_localized("reverse.hover");
+                    }
+                    Rule::Highlight { threshold, color } => {
+                        ui.toggle_value(&mut threshold.absolute, "|x|")
+                            .on_hover_localized("absolute-value.hover");
+                        ComboBox::from_id_salt(id.with("Comparison"))
+                            .width(0.0)
+                            .selected_text(threshold.comparison.symbol())
+                            .show_ui(ui, |ui| {
+                                for comparison in Comparison::VARIANTS {
+                                    ui.selectable_value(
+                                        &mut threshold.comparison,
+                                        comparison,
+                                        comparison.symbol(),
+                                    );
+                                }
+                            });
+                        ui.add(DragValue::new(&mut threshold.value).speed(0.01));
+                        ui.color_edit_button_srgb(color);
+                    }
+                }
+                if ui.button(PLUS).clicked() {
+                    self.formatting.rules.push(rule);
+                }
+            });
+            ui.data_mut(|data| data.insert_temp(id, rule));
+            // Saturation
+            ui.checkbox(
+                &mut self.formatting.saturation,
+                ui.localize("mark-saturation"),
+            )
+            .on_hover_localized("mark-saturation.hover");
+        })
+        .response
+    }
+}
@@ -1,4 +1,11 @@
-pub(crate) use self::view::ViewWidget;
+pub(crate) use self::{
+    conditions::ConditionsWidget, formatting::FormattingWidget, presets::PresetsWidget,
+    thresholds::ThresholdsWidget, view::ViewWidget,
+};
 
+mod conditions;
 pub(crate) mod float;
+mod formatting;
+mod presets;
+mod thresholds;
 mod view;
@@ -0,0 +1,65 @@
+use crate::app::panes::preset::{Preset, Presettable};
+use egui::{Button, Response, RichText, TextEdit, Ui, Widget};
+use egui_l20n::{ResponseExt as _, UiExt as _};
+use egui_phosphor::regular::{BOOKMARKS_SIMPLE, FLOPPY_DISK, X};
+
+/// Presets widget
+///
+/// Applies, saves and removes the filter and sort presets.
+pub(crate) struct PresetsWidget<'a, T> {
+    presets: &'a mut Vec<Preset>,
+    settings: &'a mut T,
+}
+
+impl<'a, T> PresetsWidget<'a, T> {
+    pub(crate) fn new(presets: &'a mut Vec<Preset>, settings: &'a mut T) -> Self {
+        Self { presets, settings }
+    }
+}
+
+impl<T: Presettable> Widget for PresetsWidget<'_, T> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        ui.menu_button(RichText::new(BOOKMARKS_SIMPLE).heading(), |ui| {
+            let mut remove = None;
+            for (index, preset) in self.presets.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    if ui.button(&preset.name).clicked() {
+                        self.settings.apply(preset);
+                        ui.close_menu();
+                    }
+                    if ui.button(X).on_hover_localized("remove-preset").clicked() {
+                        remove = Some(index);
+                    }
+                });
+            }
+            if let Some(index) = remove {
+                self.presets.remove(index);
+            }
+            if !self.presets.is_empty() {
+                ui.separator();
+            }
+            // Save
+            let id = ui.auto_id_with("PresetName");
+            let mut name = ui.data_mut(|data| data.get_temp::<String>(id).unwrap_or_default());
+            ui.horizontal(|ui| {
+                ui.add(TextEdit::singleline(&mut name).hint_text(ui.localize("preset-name")));
+                if ui
+                    .add_enabled(!name.is_empty(), Button::new(FLOPPY_DISK))
+                    .on_hover_localized("save-preset")
+                    .clicked()
+                {
+                    let preset = self.settings.preset(name.clone());
+                    match self.presets.iter_mut().find(|preset| preset.name == name) {
+                        Some(existing) => *existing = preset,
+                        None => self.presets.push(preset),
+                    }
+                    name.clear();
+                }
+            });
+            ui.data_mut(|data| data.insert_temp(id, name));
+        })
+        .response
+        .on_hover_localized("presets")
+        .on_hover_localized("presets.hover")
+    }
+}
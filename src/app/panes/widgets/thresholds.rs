@@ -0,0 +1,87 @@
+use crate::{
+    app::panes::source::settings::{Comparison, Threshold},
+    localization::Text,
+};
+use egui::{Button, ComboBox, DragValue, Response, Ui, Widget};
+use egui_l20n::{ResponseExt as _, UiExt as _};
+use egui_phosphor::regular::{PLUS, X};
+use std::fmt::Debug;
+
+/// Thresholds widget
+///
+/// Removable chips of the thresholds and a row to add a new one.
+#[derive(Debug)]
+pub(crate) struct ThresholdsWidget<'a, F> {
+    thresholds: &'a mut Vec<Threshold<F>>,
+    fields: &'a [F],
+}
+
+impl<'a, F> ThresholdsWidget<'a, F> {
+    pub(crate) fn new(thresholds: &'a mut Vec<Threshold<F>>, fields: &'a [F]) -> Self {
+        Self { thresholds, fields }
+    }
+}
+
+impl<F> Widget for ThresholdsWidget<'_, F>
+where
+    F: Clone + Copy + Debug + Default + PartialEq + Send + Sync + Text + 'static,
+{
+    fn ui(self, ui: &mut Ui) -> Response {
+        ui.vertical(|ui| {
+            // Chips
+            ui.horizontal_wrapped(|ui| {
+                let mut remove = None;
+                for (index, threshold) in self.thresholds.iter().enumerate() {
+                    if ui
+                        .add(Button::new(format!("{} {X}", threshold.text(ui))).small())
+                        .on_hover_localized(threshold.field.hover_text())
+                        .clicked()
+                    {
+                        remove = Some(index);
+                    }
+                }
+                if let Some(index) = remove {
+                    self.thresholds.remove(index);
+                }
+            });
+            // New
+            let id = ui.auto_id_with("Threshold");
+            let mut threshold =
+                ui.data_mut(|data| data.get_temp::<Threshold<F>>(id).unwrap_or_default());
+            ui.horizontal(|ui| {
+                ComboBox::from_id_salt(id.with("Field"))
+                    .selected_text(ui.localize(threshold.field.text()))
+                    .show_ui(ui, |ui| {
+                        for &field in self.fields {
+                            ui.selectable_value(
+                                &mut threshold.field,
+                                field,
+                                ui.localize(field.text()),
+                            )
+                            .on_hover_localized(field.hover_text());
+                        }
+                    });
+                ui.toggle_value(&mut threshold.absolute, "|x|")
+                    .on_hover_localized("absolute-value.hover");
+                ComboBox::from_id_salt(id.with("Comparison"))
+                    .width(0.0)
+                    .selected_text(threshold.comparison.symbol())
+                    .show_ui(ui, |ui| {
+                        for comparison in Comparison::VARIANTS {
+                            ui.selectable_value(
+                                &mut threshold.comparison,
+                                comparison,
+                                comparison.symbol(),
+                            );
+                        }
+                    });
+                ui.add(DragValue::new(&mut threshold.value).speed(0.01));
+                if ui.button(PLUS).clicked() {
+                    self.thresholds.push(threshold);
+                }
+            });
+            ui.data_mut(|data| data.insert_temp(id, threshold));
+        })
+        .response
+    }
+}
@@ -1,34 +1,45 @@
 use crate::{app::panes::source::settings::View, localization::Text as _};
 use egui::{Response, RichText, Ui, Widget};
 use egui_l20n::{ResponseExt as _, UiExt as _};
-use egui_phosphor::regular::{CHART_BAR, TABLE};
+use egui_phosphor::regular::{CHART_BAR, GRID_FOUR, GRID_NINE, TABLE};
 
 /// View widget
 #[derive(Debug)]
 pub(crate) struct ViewWidget<'a> {
     view: &'a mut View,
+    views: &'a [View],
 }
 
 impl<'a> ViewWidget<'a> {
     pub(crate) fn new(view: &'a mut View) -> Self {
-        Self { view }
+        Self {
+            view,
+            views: &[View::Table, View::Plot],
+        }
+    }
+
+    pub(crate) fn views(self, views: &'a [View]) -> Self {
+        Self { views, ..self }
     }
 }
 
 impl Widget for ViewWidget<'_> {
     fn ui(self, ui: &mut Ui) -> Response {
         let text = match self.view {
+            View::Heatmap => GRID_NINE,
+            View::Pivot => GRID_FOUR,
             View::Plot => CHART_BAR,
             View::Table => TABLE,
         };
         ui.menu_button(RichText::new(text).heading(), |ui| {
-            let mut response = ui
-                .selectable_value(self.view, View::Table, ui.localize(View::Table.text()))
-                .on_hover_localized(View::Table.hover_text());
-            response |= ui
-                .selectable_value(self.view, View::Plot, ui.localize(View::Plot.text()))
-                .on_hover_localized(View::Plot.hover_text());
-            if response.changed() {
+            let mut changed = false;
+            for &view in self.views {
+                changed |= ui
+                    .selectable_value(self.view, view, ui.localize(view.text()))
+                    .on_hover_localized(view.hover_text())
+                    .changed();
+            }
+            if changed {
                 ui.close_menu();
             }
         })
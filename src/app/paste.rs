@@ -0,0 +1,210 @@
+use crate::{app::data::Data, dead_time::Conditions, presets::AGILENT, special::name::Name};
+use anyhow::{Context as _, Result, ensure};
+use egui::{Button, Color32, DragValue, Grid, Id, ScrollArea, TextEdit, Ui};
+use egui_l20n::{ResponseExt as _, UiExt as _};
+use metadata::MetaDataFrame;
+use polars::prelude::*;
+use std::collections::HashSet;
+use tracing::error;
+
+/// Paste
+///
+/// Tabular data pasted from the clipboard: the first column is the fatty acid
+/// name, the next ones are the replicate retention times of the single mode.
+#[derive(Clone, Debug)]
+pub(crate) struct Paste {
+    pub(crate) open: bool,
+    pub(crate) text: String,
+    pub(crate) name: String,
+    pub(crate) onset_temperature: f64,
+    pub(crate) temperature_step: f64,
+}
+
+impl Paste {
+    pub(crate) fn new() -> Self {
+        Self {
+            open: false,
+            text: String::new(),
+            name: String::new(),
+            onset_temperature: 70.0,
+            temperature_step: 1.0,
+        }
+    }
+
+    pub(crate) fn show(&mut self, ui: &mut Ui) {
+        Grid::new(ui.next_auto_id()).show(ui, |ui| {
+            ui.label(ui.localize("name"));
+            ui.text_edit_singleline(&mut self.name);
+            ui.end_row();
+
+            ui.label(ui.localize("onset-temperature"));
+            ui.add(
+                DragValue::new(&mut self.onset_temperature)
+                    .range(0.0..=500.0)
+                    .speed(1.0)
+                    .suffix(" °C"),
+            );
+            ui.end_row();
+
+            ui.label(ui.localize("temperature-step"));
+            ui.add(
+                DragValue::new(&mut self.temperature_step)
+                    .range(0.1..=100.0)
+                    .speed(0.1)
+                    .suffix(" °C/min"),
+            );
+            ui.end_row();
+        });
+        ui.separator();
+        ScrollArea::vertical()
+            .id_salt("Text")
+            .max_height(ui.available_height() / 2.0)
+            .show(ui, |ui| {
+                ui.add(
+                    TextEdit::multiline(&mut self.text)
+                        .code_editor()
+                        .hint_text(ui.localize("paste.hint"))
+                        .desired_width(f32::INFINITY),
+                );
+            });
+        let rows = rows(&self.text);
+        ui.separator();
+        let count = rows.iter().filter(|row| row.is_ok()).count();
+        ui.label(format!("{}: {count}", ui.localize("fatty-acids")));
+        ScrollArea::vertical().id_salt("Errors").show(ui, |ui| {
+            for error in rows.iter().filter_map(|row| row.as_ref().err()) {
+                ui.colored_label(Color32::RED, format!("{error:#}"));
+            }
+        });
+        ui.separator();
+        let enabled = count > 0 && rows.iter().all(Result::is_ok);
+        if ui
+            .add_enabled(enabled, Button::new(ui.localize("create")))
+            .on_hover_localized("create.hover")
+            .clicked()
+        {
+            let rows = rows.into_iter().filter_map(Result::ok).collect::<Vec<_>>();
+            match self.data_frame(&rows) {
+                Ok(data_frame) => {
+                    let mut meta = AGILENT.meta.clone();
+                    meta.name = match self.name.trim() {
+                        "" => ui.localize("clipboard"),
+                        name => name.to_owned(),
+                    };
+                    meta.description = String::new();
+                    meta.version = None;
+                    meta.date = None;
+                    ui.data_mut(|data| {
+                        data.insert_temp(Id::new("Source"), MetaDataFrame::new(meta, data_frame))
+                    });
+                    self.text.clear();
+                    self.open = false;
+                }
+                Err(error) => error!(%error),
+            }
+        }
+    }
+
+    /// Data frame in the schema of the default data
+    fn data_frame(&self, rows: &[Row]) -> PolarsResult<DataFrame> {
+        let schema = Data::schema();
+        let length = rows.len();
+        // Fatty acid
+        let names = rows.iter().map(|row| row.name.clone()).collect::<Vec<_>>();
+        let fatty_acid = Name::to_series("FattyAcid".into(), &names)?;
+        // Mode
+        let mode = StructChunked::from_series(
+            "Mode".into(),
+            length,
+            [
+                Series::new(
+                    "OnsetTemperature".into(),
+                    vec![self.onset_temperature; length],
+                ),
+                Series::new(
+                    "TemperatureStep".into(),
+                    vec![self.temperature_step; length],
+                ),
+            ]
+            .iter(),
+        )?
+        .into_series();
+        // Retention time
+        let retention_time = rows
+            .iter()
+            .map(|row| Some(Series::from_iter(row.retention_times.iter().copied())))
+            .collect::<ListChunked>()
+            .with_name("RetentionTime".into())
+            .into_series();
+        // Dead time
+        let dead_time = Series::new(
+            "DeadTime".into(),
+            vec![Conditions::new().dead_time(self.onset_temperature); length],
+        );
+        let cast = |series: Series| match schema.get(series.name()) {
+            Some(data_type) => series.cast(data_type),
+            None => Ok(series),
+        };
+        DataFrame::new(vec![
+            cast(mode)?.into_column(),
+            cast(fatty_acid)?.into_column(),
+            cast(retention_time)?.into_column(),
+            cast(dead_time)?.into_column(),
+        ])
+    }
+}
+
+impl Default for Paste {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Row of the pasted table
+#[derive(Clone, Debug)]
+struct Row {
+    name: Name,
+    retention_times: Vec<f64>,
+}
+
+/// Parses the rows of the tab separated text, a header is skipped
+fn rows(text: &str) -> Vec<Result<Row>> {
+    let mut names = HashSet::new();
+    let mut rows = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let row = || -> Result<Row> {
+            let mut cells = line.split('\t');
+            let name = cells.next().unwrap_or_default().trim();
+            let name = Name::parse(name)?;
+            ensure!(names.insert(name.clone()), "duplicate fatty acid {name}");
+            let retention_times = cells
+                .map(str::trim)
+                .filter(|cell| !cell.is_empty())
+                .map(|cell| {
+                    cell.replace(',', ".")
+                        .parse::<f64>()
+                        .with_context(|| format!("invalid retention time {cell:?}"))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            ensure!(!retention_times.is_empty(), "no retention times");
+            Ok(Row {
+                name,
+                retention_times,
+            })
+        }()
+        .with_context(|| format!("line {}", index + 1));
+        // Header
+        if rows.is_empty() && row.is_err() && !line.split('\t').skip(1).any(is_number) {
+            continue;
+        }
+        rows.push(row);
+    }
+    rows
+}
+
+fn is_number(cell: &str) -> bool {
+    cell.trim().replace(',', ".").parse::<f64>().is_ok()
+}
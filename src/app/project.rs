@@ -0,0 +1,37 @@
+use super::panes::{Pane, preset::Preset};
+use egui_tiles::{Tile, Tree};
+use egui_tiles_ext::{TreeExt as _, VERTICAL};
+use serde::{Deserialize, Serialize};
+
+/// Project
+///
+/// The panes and the presets, exported to and imported from a RON file.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct Project {
+    pub(crate) tree: Tree<Pane>,
+    pub(crate) presets: Vec<Preset>,
+}
+
+impl Project {
+    pub(crate) const EXTENSION: &str = "ron";
+
+    /// Adds the panes of the project to the open ones
+    pub(crate) fn merge_panes(&self, tree: &mut Tree<Pane>) {
+        for tile in self.tree.tiles.tiles() {
+            if let Tile::Pane(pane) = tile {
+                tree.insert_pane::<VERTICAL>(pane.clone());
+            }
+        }
+    }
+
+    /// Merges the presets, the imported ones replace the ones with the same
+    /// name
+    pub(crate) fn merge_presets(&self, presets: &mut Vec<Preset>) {
+        for preset in &self.presets {
+            match presets.iter_mut().find(|target| target.name == preset.name) {
+                Some(target) => *target = preset.clone(),
+                None => presets.push(preset.clone()),
+            }
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use super::kelvin;
+use crate::localization::Text;
+use serde::{Deserialize, Serialize};
+
+/// Standard temperature of the viscosity approximation (°C)
+const STANDARD_TEMPERATURE: f64 = 0.0;
+
+/// Carrier gas
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub(crate) enum CarrierGas {
+    Hydrogen,
+    #[default]
+    Helium,
+    Nitrogen,
+}
+
+impl CarrierGas {
+    pub(crate) const VARIANTS: [Self; 3] = [Self::Hydrogen, Self::Helium, Self::Nitrogen];
+
+    /// Dynamic viscosity (Pa·s) at the temperature (°C)
+    ///
+    /// Power-law approximation `η = η₀ (T / T₀)^ξ` (Blumberg, Temperature-programmed
+    /// gas chromatography, 2010).
+    pub(crate) fn viscosity(self, temperature: f64) -> f64 {
+        let (viscosity, exponent) = match self {
+            Self::Hydrogen => (8.382e-6, 0.6892),
+            Self::Helium => (18.63e-6, 0.6958),
+            Self::Nitrogen => (16.62e-6, 0.7051),
+        };
+        viscosity * (kelvin(temperature) / kelvin(STANDARD_TEMPERATURE)).powf(exponent)
+    }
+}
+
+impl Text for CarrierGas {
+    fn text(&self) -> &'static str {
+        match self {
+            Self::Hydrogen => "hydrogen",
+            Self::Helium => "helium",
+            Self::Nitrogen => "nitrogen",
+        }
+    }
+
+    fn hover_text(&self) -> &'static str {
+        match self {
+            Self::Hydrogen => "hydrogen.hover",
+            Self::Helium => "helium.hover",
+            Self::Nitrogen => "nitrogen.hover",
+        }
+    }
+}
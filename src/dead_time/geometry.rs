@@ -0,0 +1,48 @@
+use egui::emath::Float as _;
+use serde::{Deserialize, Serialize};
+use std::hash::{Hash, Hasher};
+
+/// Column geometry
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub(crate) struct Geometry {
+    /// Length (m)
+    pub(crate) length: f64,
+    /// Inner diameter (mm)
+    pub(crate) diameter: f64,
+    /// Film thickness (μm)
+    pub(crate) film_thickness: f64,
+}
+
+impl Geometry {
+    pub(crate) fn new() -> Self {
+        Self {
+            length: 100.0,
+            diameter: 0.25,
+            film_thickness: 0.2,
+        }
+    }
+
+    /// Effective inner diameter (m) of the gas channel
+    pub(crate) fn channel_diameter(&self) -> f64 {
+        self.diameter * 1e-3 - 2.0 * self.film_thickness * 1e-6
+    }
+
+    /// Phase ratio `β = d / 4dƒ`
+    pub(crate) fn phase_ratio(&self) -> f64 {
+        self.diameter * 1e3 / (4.0 * self.film_thickness)
+    }
+}
+
+impl Default for Geometry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hash for Geometry {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.length.ord().hash(state);
+        self.diameter.ord().hash(state);
+        self.film_thickness.ord().hash(state);
+    }
+}
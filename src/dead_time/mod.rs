@@ -0,0 +1,101 @@
+pub(crate) use self::{
+    carrier_gas::CarrierGas,
+    geometry::Geometry,
+    operation::{Conditions, Operation},
+};
+
+/// Atmospheric pressure (kPa)
+pub(crate) const ATMOSPHERIC_PRESSURE: f64 = 101.325;
+/// Reference temperature of the flow (°C)
+pub(crate) const REFERENCE_TEMPERATURE: f64 = 25.0;
+/// Absolute zero (°C)
+const ABSOLUTE_ZERO: f64 = -273.15;
+
+/// Kelvin from Celsius
+fn kelvin(celsius: f64) -> f64 {
+    celsius - ABSOLUTE_ZERO
+}
+
+mod carrier_gas;
+mod geometry;
+mod operation;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn conditions(operation: Operation) -> Conditions {
+        Conditions {
+            geometry: Geometry {
+                length: 30.0,
+                diameter: 0.25,
+                film_thickness: 0.0,
+            },
+            carrier_gas: CarrierGas::Helium,
+            operation,
+            pressure: 100.0,
+            flow: 1.0,
+            outlet_pressure: ATMOSPHERIC_PRESSURE,
+            maximum_temperature: 250.0,
+        }
+    }
+
+    #[test]
+    fn constant_pressure() {
+        let conditions = conditions(Operation::ConstantPressure);
+        // 30 m x 0.25 mm, helium, 100 kPa at 100 °C is about 0.46 min
+        let dead_time = conditions.dead_time(100.0);
+        assert!((dead_time - 0.46).abs() < 0.01, "{dead_time}");
+        // Viscosity grows with temperature
+        assert!(conditions.dead_time(200.0) > dead_time);
+    }
+
+    #[test]
+    fn constant_flow() {
+        let conditions = conditions(Operation::ConstantFlow);
+        let temperature = 150.0;
+        let pressure = conditions.inlet_pressure(temperature) - ATMOSPHERIC_PRESSURE;
+        let flow = Conditions {
+            operation: Operation::ConstantPressure,
+            pressure,
+            ..conditions
+        }
+        .flow(temperature);
+        assert!((flow - conditions.flow).abs() < 1e-9, "{flow}");
+        assert!(
+            (conditions.dead_time(temperature)
+                - Conditions {
+                    operation: Operation::ConstantPressure,
+                    pressure,
+                    ..conditions
+                }
+                .dead_time(temperature))
+            .abs()
+                < 1e-9
+        );
+    }
+
+    #[test]
+    fn vacuum_outlet() {
+        for operation in Operation::VARIANTS {
+            let conditions = Conditions {
+                outlet_pressure: 0.0,
+                ..conditions(operation)
+            };
+            let dead_time = conditions.dead_time(150.0);
+            assert!(dead_time.is_finite() && dead_time > 0.0, "{dead_time}");
+            let flow = conditions.flow(150.0);
+            assert!(flow.is_finite() && flow > 0.0, "{flow}");
+        }
+        // Constant flow at the vacuum outlet needs a lower inlet pressure
+        let conditions = conditions(Operation::ConstantFlow);
+        assert!(
+            Conditions {
+                outlet_pressure: 0.0,
+                ..conditions
+            }
+            .inlet_pressure(150.0)
+                < conditions.inlet_pressure(150.0)
+        );
+    }
+}
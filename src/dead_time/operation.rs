@@ -0,0 +1,179 @@
+use super::{ATMOSPHERIC_PRESSURE, CarrierGas, Geometry, REFERENCE_TEMPERATURE, kelvin};
+use crate::localization::Text;
+use egui::emath::Float as _;
+use serde::{Deserialize, Serialize};
+use std::{
+    f64::consts::PI,
+    hash::{Hash, Hasher},
+};
+
+/// Upper bound of the inlet gauge pressure (kPa)
+const MAX_PRESSURE: f64 = 10_000.0;
+
+/// Operation mode of the inlet
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub(crate) enum Operation {
+    #[default]
+    ConstantPressure,
+    ConstantFlow,
+}
+
+impl Operation {
+    pub(crate) const VARIANTS: [Self; 2] = [Self::ConstantPressure, Self::ConstantFlow];
+}
+
+impl Text for Operation {
+    fn text(&self) -> &'static str {
+        match self {
+            Self::ConstantPressure => "constant-pressure",
+            Self::ConstantFlow => "constant-flow",
+        }
+    }
+
+    fn hover_text(&self) -> &'static str {
+        match self {
+            Self::ConstantPressure => "constant-pressure.hover",
+            Self::ConstantFlow => "constant-flow.hover",
+        }
+    }
+}
+
+/// Column conditions
+///
+/// Compressible flow of the carrier gas through an open tubular column
+/// (Poiseuille, James–Martin).
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub(crate) struct Conditions {
+    pub(crate) geometry: Geometry,
+    pub(crate) carrier_gas: CarrierGas,
+    pub(crate) operation: Operation,
+    /// Inlet gauge pressure (kPa), used in constant pressure operation
+    pub(crate) pressure: f64,
+    /// Flow (mL/min) at 25 °C and 101.325 kPa, used in constant flow operation
+    pub(crate) flow: f64,
+    /// Outlet absolute pressure (kPa)
+    pub(crate) outlet_pressure: f64,
+    /// Maximum temperature (°C) of the column
+    pub(crate) maximum_temperature: f64,
+}
+
+impl Conditions {
+    pub(crate) fn new() -> Self {
+        Self {
+            geometry: Geometry::new(),
+            carrier_gas: CarrierGas::Helium,
+            operation: Operation::ConstantPressure,
+            pressure: 200.0,
+            flow: 1.0,
+            outlet_pressure: ATMOSPHERIC_PRESSURE,
+            maximum_temperature: 250.0,
+        }
+    }
+
+    /// Inlet absolute pressure (kPa) at the column temperature (°C)
+    pub(crate) fn inlet_pressure(&self, temperature: f64) -> f64 {
+        match self.operation {
+            Operation::ConstantPressure => self.pressure + ATMOSPHERIC_PRESSURE,
+            Operation::ConstantFlow => {
+                let outlet = self.outlet_pressure * 1e3;
+                let diameter = self.geometry.channel_diameter();
+                let inlet = (outlet.powi(2)
+                    + 256.0
+                        * self.carrier_gas.viscosity(temperature)
+                        * self.geometry.length
+                        * self.outlet_flow(temperature)
+                        / (PI * diameter.powi(4)))
+                .sqrt();
+                inlet * 1e-3
+            }
+        }
+    }
+
+    /// Flow (mL/min) at 25 °C and 101.325 kPa at the column temperature (°C)
+    pub(crate) fn flow(&self, temperature: f64) -> f64 {
+        match self.operation {
+            Operation::ConstantPressure => {
+                let inlet = self.inlet_pressure(temperature) * 1e3;
+                let outlet = self.outlet_pressure * 1e3;
+                let diameter = self.geometry.channel_diameter();
+                // Outlet pressure times the outlet flow (Pa·m³/s)
+                let outlet_flow = PI * diameter.powi(4) * (inlet.powi(2) - outlet.powi(2))
+                    / (256.0 * self.carrier_gas.viscosity(temperature) * self.geometry.length);
+                outlet_flow / (ATMOSPHERIC_PRESSURE * 1e3) * kelvin(REFERENCE_TEMPERATURE)
+                    / kelvin(temperature)
+                    * 60.0
+                    * 1e6
+            }
+            Operation::ConstantFlow => self.flow,
+        }
+    }
+
+    /// Outlet pressure times the outlet flow (Pa·m³/s) at the column
+    /// temperature (°C) in constant flow operation
+    ///
+    /// The product is the mass flow up to a constant, so it does not depend on
+    /// the outlet pressure and stays finite for a vacuum outlet.
+    fn outlet_flow(&self, temperature: f64) -> f64 {
+        self.flow * 1e-6 / 60.0 * kelvin(temperature) / kelvin(REFERENCE_TEMPERATURE)
+            * ATMOSPHERIC_PRESSURE
+            * 1e3
+    }
+
+    /// Dead time (min) at the column temperature (°C)
+    ///
+    /// `tM = 32ηL²(pi³ - po³) / 3d²(pi² - po²)²`
+    pub(crate) fn dead_time(&self, temperature: f64) -> f64 {
+        let inlet = self.inlet_pressure(temperature) * 1e3;
+        let outlet = self.outlet_pressure * 1e3;
+        let diameter = self.geometry.channel_diameter();
+        let dead_time = 32.0
+            * self.carrier_gas.viscosity(temperature)
+            * self.geometry.length.powi(2)
+            * (inlet.powi(3) - outlet.powi(3))
+            / (3.0 * diameter.powi(2) * (inlet.powi(2) - outlet.powi(2)).powi(2));
+        dead_time / 60.0
+    }
+
+    /// Inlet gauge pressure (kPa) giving the dead time (min) at the column
+    /// temperature (°C), `None` if the dead time is unreachable below the
+    /// maximum pressure
+    pub(crate) fn pressure_for(&self, dead_time: f64, temperature: f64) -> Option<f64> {
+        let mut conditions = Self {
+            operation: Operation::ConstantPressure,
+            pressure: MAX_PRESSURE,
+            ..*self
+        };
+        if !(dead_time.is_finite() && dead_time >= conditions.dead_time(temperature)) {
+            return None;
+        }
+        let (mut low, mut high) = (0.0, MAX_PRESSURE);
+        // The dead time strictly decreases with the inlet pressure
+        for _ in 0..64 {
+            conditions.pressure = (low + high) / 2.0;
+            if conditions.dead_time(temperature) > dead_time {
+                low = conditions.pressure;
+            } else {
+                high = conditions.pressure;
+            }
+        }
+        Some(conditions.pressure)
+    }
+}
+
+impl Default for Conditions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hash for Conditions {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.geometry.hash(state);
+        self.carrier_gas.hash(state);
+        self.operation.hash(state);
+        self.pressure.ord().hash(state);
+        self.flow.ord().hash(state);
+        self.outlet_pressure.ord().hash(state);
+        self.maximum_temperature.ord().hash(state);
+    }
+}
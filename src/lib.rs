@@ -3,9 +3,11 @@
 pub use app::App;
 
 mod app;
+mod dead_time;
 mod localization;
 mod presets;
 mod special;
+mod translation;
 mod utils;
 
 #[cfg(test)]
@@ -0,0 +1,75 @@
+use polars::prelude::*;
+
+/// GC column series
+///
+/// The optional `Column` struct (`Name`, `StationaryPhase`, `Manufacturer`,
+/// `Length`, `InnerDiameter`, `FilmThickness`) identifies the column the
+/// measurements are made on. Data without it comes from one unnamed column.
+#[derive(Clone)]
+pub struct GcColumnSeries {
+    pub name: Series,
+    pub stationary_phase: Series,
+    pub manufacturer: Series,
+    pub length: Series,
+    pub inner_diameter: Series,
+    pub film_thickness: Series,
+}
+
+impl GcColumnSeries {
+    pub fn new(column: &Column) -> PolarsResult<Self> {
+        let column = column.struct_()?;
+        Ok(Self {
+            name: column.field_by_name("Name")?,
+            stationary_phase: column.field_by_name("StationaryPhase")?,
+            manufacturer: column.field_by_name("Manufacturer")?,
+            length: column.field_by_name("Length")?,
+            inner_diameter: column.field_by_name("InnerDiameter")?,
+            film_thickness: column.field_by_name("FilmThickness")?,
+        })
+    }
+
+    /// Unique sorted names
+    pub fn names(&self) -> PolarsResult<Vec<String>> {
+        let names = self.name.unique()?.sort(Default::default())?;
+        Ok(names
+            .str()?
+            .iter()
+            .flatten()
+            .map(ToOwned::to_owned)
+            .collect())
+    }
+
+    /// Description of the column at the index
+    pub fn description(&self, index: usize) -> PolarsResult<String> {
+        let mut description = Vec::new();
+        if let Some(stationary_phase) = self.stationary_phase.str()?.get(index) {
+            description.push(stationary_phase.to_owned());
+        }
+        if let Some(manufacturer) = self.manufacturer.str()?.get(index) {
+            description.push(manufacturer.to_owned());
+        }
+        if let (Some(length), Some(inner_diameter), Some(film_thickness)) = (
+            self.length.f64()?.get(index),
+            self.inner_diameter.f64()?.get(index),
+            self.film_thickness.f64()?.get(index),
+        ) {
+            description.push(format!(
+                "{length} m × {inner_diameter} mm × {film_thickness} μm"
+            ));
+        }
+        Ok(description.join(", "))
+    }
+}
+
+/// Unnamed column of the data without the `Column` struct
+pub fn unnamed() -> Expr {
+    as_struct(vec![
+        lit(NULL).cast(DataType::String).alias("Name"),
+        lit(NULL).cast(DataType::String).alias("StationaryPhase"),
+        lit(NULL).cast(DataType::String).alias("Manufacturer"),
+        lit(NULL).cast(DataType::Float64).alias("Length"),
+        lit(NULL).cast(DataType::Float64).alias("InnerDiameter"),
+        lit(NULL).cast(DataType::Float64).alias("FilmThickness"),
+    ])
+    .alias("Column")
+}
@@ -1 +1,2 @@
+pub mod gc_column;
 pub mod mode;
@@ -1,5 +1,8 @@
 use polars::prelude::*;
-use std::ops::RangeInclusive;
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::RangeInclusive,
+};
 
 /// Extension methods for [`Column`]
 pub trait ColumnExt {
@@ -13,22 +16,186 @@ impl ColumnExt for Column {
 }
 
 /// Mode series
+///
+/// The two-field mode (`OnsetTemperature`, `TemperatureStep`) is a single ramp
+/// up to the maximum temperature of the column. The full temperature program
+/// additionally carries an initial `Hold`, `Ramps` (`Rate`, `Temperature`,
+/// `Hold`) and optional `Flow` and `Pressure`. The `TemperatureStep` is the
+/// rate of the first ramp, the `Rate` of the first ramp is ignored.
 #[derive(Clone)]
 pub struct ModeSeries {
     pub onset_temperature: Series,
     pub temperature_step: Series,
+    pub hold: Option<Series>,
+    pub ramps: Option<Series>,
+    pub flow: Option<Series>,
+    pub pressure: Option<Series>,
 }
 
 impl ModeSeries {
     pub fn new(column: &Column) -> PolarsResult<Self> {
-        let onset_temperature = column.struct_()?.field_by_name("OnsetTemperature")?;
-        let temperature_step = column.struct_()?.field_by_name("TemperatureStep")?;
+        let mode = column.struct_()?;
+        let onset_temperature = mode.field_by_name("OnsetTemperature")?;
+        let temperature_step = mode.field_by_name("TemperatureStep")?;
         Ok(Self {
             onset_temperature,
             temperature_step,
+            hold: mode.field_by_name("Hold").ok(),
+            ramps: mode.field_by_name("Ramps").ok(),
+            flow: mode.field_by_name("Flow").ok(),
+            pressure: mode.field_by_name("Pressure").ok(),
         })
     }
 
+    /// Temperature program of the mode at the index
+    pub fn program(&self, index: usize, maximum_temperature: f64) -> PolarsResult<Option<Program>> {
+        let (Some(onset_temperature), Some(temperature_step)) = (
+            self.onset_temperature.f64()?.get(index),
+            self.temperature_step.f64()?.get(index),
+        ) else {
+            return Ok(None);
+        };
+        let get = |series: &Option<Series>| -> PolarsResult<Option<f64>> {
+            match series {
+                Some(series) => Ok(series.f64()?.get(index)),
+                None => Ok(None),
+            }
+        };
+        let mut ramps = Vec::new();
+        if let Some(series) = &self.ramps {
+            if let Some(series) = series.list()?.get_as_series(index) {
+                let series = series.struct_()?;
+                let rate = series.field_by_name("Rate")?;
+                let temperature = series.field_by_name("Temperature")?;
+                let hold = series.field_by_name("Hold")?;
+                for (ramp, ((rate, temperature), hold)) in rate
+                    .f64()?
+                    .iter()
+                    .zip(temperature.f64()?)
+                    .zip(hold.f64()?)
+                    .enumerate()
+                {
+                    let rate = if ramp == 0 {
+                        temperature_step
+                    } else {
+                        let Some(rate) = rate else {
+                            polars_bail!(NoData: "Mode/Ramps/Rate[{index}]");
+                        };
+                        rate
+                    };
+                    ramps.push(Ramp {
+                        rate,
+                        temperature: temperature
+                            .unwrap_or(maximum_temperature)
+                            .min(maximum_temperature),
+                        hold: hold.unwrap_or_default(),
+                    });
+                }
+            }
+        }
+        if ramps.is_empty() {
+            ramps.push(Ramp {
+                rate: temperature_step,
+                temperature: maximum_temperature,
+                hold: 0.0,
+            });
+        }
+        Ok(Some(Program {
+            onset_temperature,
+            hold: get(&self.hold)?.unwrap_or_default(),
+            ramps,
+            flow: get(&self.flow)?,
+            pressure: get(&self.pressure)?,
+        }))
+    }
+
+    /// Whether the mode carries more than the onset temperature and the
+    /// temperature step
+    pub fn is_program(&self) -> bool {
+        self.hold.is_some() || self.ramps.is_some()
+    }
+
+    /// Keys of the temperature programs, the empty one for the single ramp
+    /// modes
+    ///
+    /// The ramps without the final temperature are kept up to the infinity,
+    /// so the keys do not depend on the maximum temperature of the column.
+    pub fn programs(&self) -> PolarsResult<Vec<String>> {
+        let is_set = |series: &Option<Series>, index| {
+            series
+                .as_ref()
+                .is_some_and(|series| series.get(index).is_ok_and(|value| !value.is_null()))
+        };
+        (0..self.onset_temperature.len())
+            .map(|index| {
+                if ![&self.hold, &self.ramps, &self.flow, &self.pressure]
+                    .into_iter()
+                    .any(|series| is_set(series, index))
+                {
+                    return Ok(String::new());
+                }
+                Ok(self
+                    .program(index, f64::INFINITY)?
+                    .map_or_else(String::new, |program| program.to_string()))
+            })
+            .collect()
+    }
+
+    /// Multiply the rates and divide the holds by the factors
+    pub fn translate(&self, factors: &Float64Chunked) -> PolarsResult<Series> {
+        let mut fields = vec![
+            self.onset_temperature.clone(),
+            (self.temperature_step.f64()? * factors)
+                .with_name("TemperatureStep".into())
+                .into_series(),
+        ];
+        if let Some(hold) = &self.hold {
+            fields.push(
+                (hold.f64()? / factors)
+                    .with_name("Hold".into())
+                    .into_series(),
+            );
+        }
+        if let Some(ramps) = &self.ramps {
+            let mut translated = Vec::with_capacity(ramps.len());
+            for (ramps, factor) in ramps.list()?.into_iter().zip(factors) {
+                translated.push(match ramps.zip(factor) {
+                    Some((ramps, factor)) => {
+                        let ramps = ramps.struct_()?;
+                        let rate = ramps.field_by_name("Rate")?;
+                        let temperature = ramps.field_by_name("Temperature")?;
+                        let hold = ramps.field_by_name("Hold")?;
+                        Some(
+                            StructChunked::from_series(
+                                PlSmallStr::EMPTY,
+                                ramps.len(),
+                                [
+                                    (rate.f64()? * factor).into_series(),
+                                    temperature,
+                                    (hold.f64()? / factor).into_series(),
+                                ]
+                                .iter(),
+                            )?
+                            .into_series(),
+                        )
+                    }
+                    None => None,
+                });
+            }
+            fields.push(
+                translated
+                    .into_iter()
+                    .collect::<ListChunked>()
+                    .with_name("Ramps".into())
+                    .into_series(),
+            );
+        }
+        Ok(
+            StructChunked::from_series("Mode".into(), self.onset_temperature.len(), fields.iter())?
+                .into_series(),
+        )
+    }
+
     pub fn onset_temperature_range(&self) -> RangeInclusive<f64> {
         if let Ok(onset_temperatures) = self.onset_temperature.f64() {
             if let Some((min, max)) = onset_temperatures.min_max() {
@@ -101,3 +268,202 @@ impl TemperatureStep<'_> {
         self.0.unique().unwrap()
     }
 }
+
+/// Temperature program
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Program {
+    /// Onset temperature (°C)
+    pub onset_temperature: f64,
+    /// Initial hold (min)
+    pub hold: f64,
+    pub ramps: Vec<Ramp>,
+    /// Flow (mL/min)
+    pub flow: Option<f64>,
+    /// Inlet gauge pressure (kPa)
+    pub pressure: Option<f64>,
+}
+
+impl Program {
+    /// Temperature (°C) at the time (min)
+    pub fn temperature(&self, time: f64) -> f64 {
+        let mut temperature = self.onset_temperature;
+        let mut elapsed = self.hold;
+        if time <= elapsed {
+            return temperature;
+        }
+        for ramp in &self.ramps {
+            if ramp.rate <= 0.0 {
+                return temperature;
+            }
+            let duration = ((ramp.temperature - temperature) / ramp.rate).max(0.0);
+            if time <= elapsed + duration {
+                return temperature + ramp.rate * (time - elapsed);
+            }
+            elapsed += duration + ramp.hold;
+            temperature = ramp.temperature.max(temperature);
+            if time <= elapsed {
+                return temperature;
+            }
+        }
+        temperature
+    }
+
+    /// Final temperature (°C)
+    pub fn final_temperature(&self) -> f64 {
+        self.ramps
+            .iter()
+            .fold(self.onset_temperature, |temperature, ramp| {
+                temperature.max(ramp.temperature)
+            })
+    }
+}
+
+impl Display for Program {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} °C", self.onset_temperature)?;
+        if self.hold > 0.0 {
+            write!(f, " ({} min)", self.hold)?;
+        }
+        for ramp in &self.ramps {
+            write!(f, " → {} °C/min → {} °C", ramp.rate, ramp.temperature)?;
+            if ramp.hold > 0.0 {
+                write!(f, " ({} min)", ramp.hold)?;
+            }
+        }
+        if let Some(flow) = self.flow {
+            write!(f, ", {flow} mL/min")?;
+        }
+        if let Some(pressure) = self.pressure {
+            write!(f, ", {pressure} kPa")?;
+        }
+        Ok(())
+    }
+}
+
+/// Temperature ramp
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Ramp {
+    /// Rate (°C/min)
+    pub rate: f64,
+    /// Final temperature (°C)
+    pub temperature: f64,
+    /// Hold at the final temperature (min)
+    pub hold: f64,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn program(hold: f64, ramps: &[(f64, f64, f64)]) -> Program {
+        Program {
+            onset_temperature: 100.0,
+            hold,
+            ramps: ramps
+                .iter()
+                .map(|&(rate, temperature, hold)| Ramp {
+                    rate,
+                    temperature,
+                    hold,
+                })
+                .collect(),
+            flow: None,
+            pressure: None,
+        }
+    }
+
+    #[test]
+    fn hold() {
+        let program = program(5.0, &[(2.0, 200.0, 0.0)]);
+        assert_eq!(program.temperature(0.0), 100.0);
+        assert_eq!(program.temperature(5.0), 100.0);
+        assert_eq!(program.temperature(10.0), 110.0);
+    }
+
+    #[test]
+    fn ramp_boundaries() {
+        let program = program(0.0, &[(2.0, 200.0, 0.0), (10.0, 250.0, 0.0)]);
+        // End of the first ramp
+        assert_eq!(program.temperature(50.0), 200.0);
+        // Second ramp
+        assert_eq!(program.temperature(51.0), 210.0);
+        // End of the program
+        assert_eq!(program.temperature(55.0), 250.0);
+        assert_eq!(program.temperature(100.0), 250.0);
+        assert_eq!(program.final_temperature(), 250.0);
+    }
+
+    #[test]
+    fn holds_between_ramps() {
+        let program = program(1.0, &[(2.0, 200.0, 10.0), (10.0, 250.0, 0.0)]);
+        // Hold at the end of the first ramp
+        assert_eq!(program.temperature(51.0), 200.0);
+        assert_eq!(program.temperature(61.0), 200.0);
+        assert_eq!(program.temperature(62.0), 210.0);
+    }
+
+    #[test]
+    fn non_positive_rate() {
+        let program = program(0.0, &[(2.0, 150.0, 0.0), (0.0, 250.0, 0.0)]);
+        assert_eq!(program.temperature(25.0), 150.0);
+        assert_eq!(program.temperature(1000.0), 150.0);
+    }
+
+    #[test]
+    fn temperature_step_is_first_rate() {
+        let ramps = StructChunked::from_series(
+            PlSmallStr::EMPTY,
+            2,
+            [
+                Series::new("Rate".into(), [5.0, 10.0]),
+                Series::new("Temperature".into(), [200.0, 250.0]),
+                Series::new("Hold".into(), [0.0, 0.0]),
+            ]
+            .iter(),
+        )
+        .unwrap()
+        .into_series();
+        let mode = StructChunked::from_series(
+            "Mode".into(),
+            1,
+            [
+                Series::new("OnsetTemperature".into(), [100.0]),
+                Series::new("TemperatureStep".into(), [2.0]),
+                [Some(ramps)]
+                    .into_iter()
+                    .collect::<ListChunked>()
+                    .with_name("Ramps".into())
+                    .into_series(),
+            ]
+            .iter(),
+        )
+        .unwrap()
+        .into_column();
+        let program = ModeSeries::new(&mode)
+            .unwrap()
+            .program(0, 300.0)
+            .unwrap()
+            .unwrap();
+        assert_eq!(program.ramps[0].rate, 2.0);
+        assert_eq!(program.ramps[1].rate, 10.0);
+    }
+
+    #[test]
+    fn programs() {
+        let mode = StructChunked::from_series(
+            "Mode".into(),
+            2,
+            [
+                Series::new("OnsetTemperature".into(), [100.0, 100.0]),
+                Series::new("TemperatureStep".into(), [2.0, 2.0]),
+                Series::new("Hold".into(), [None, Some(5.0)]),
+            ]
+            .iter(),
+        )
+        .unwrap()
+        .into_column();
+        let programs = ModeSeries::new(&mode).unwrap().programs().unwrap();
+        assert_eq!(programs[0], "");
+        assert_eq!(programs[1], "100 °C (5 min) → 2 °C/min → inf °C");
+    }
+}
@@ -1,12 +1,21 @@
-use super::column::mode::{ColumnExt, ModeSeries};
+use super::column::{
+    gc_column::GcColumnSeries,
+    mode::{ColumnExt, ModeSeries},
+};
 use polars::frame::DataFrame;
 
 /// Extension methods for [`DataFrame`]
 pub trait DataFrameExt {
+    fn gc_column(&self) -> Option<GcColumnSeries>;
+
     fn mode(&self) -> ModeSeries;
 }
 
 impl DataFrameExt for DataFrame {
+    fn gc_column(&self) -> Option<GcColumnSeries> {
+        GcColumnSeries::new(self.column("Column").ok()?).ok()
+    }
+
     fn mode(&self) -> ModeSeries {
         self["Mode"].mode()
     }
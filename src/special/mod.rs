@@ -1,2 +1,3 @@
 pub mod column;
 pub mod data_frame;
+pub mod name;
@@ -0,0 +1,346 @@
+use anyhow::{Context as _, Result, bail, ensure};
+use polars::prelude::*;
+use std::fmt::{self, Display, Formatter};
+
+/// Fatty acid name
+///
+/// The name is the number of carbons and the number of double bonds
+/// (`18:2`), optionally prefixed with `C`, followed by the positions of the
+/// double bonds from the carboxyl end (`Δ9c,12c`, `D9,12`, `(9Z,12Z)`) or by
+/// the position of the last one from the methyl end (`n-6`, `ω6`). The
+/// isomerism is `c`/`Z` for cis and `t`/`E` for trans. The double bonds of
+/// the methyl end notation are methylene interrupted.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Name {
+    pub carbons: u8,
+    pub bonds: Vec<Bond>,
+}
+
+impl Name {
+    pub fn parse(name: &str) -> Result<Self> {
+        let name = name
+            .chars()
+            .filter(|char| !char.is_whitespace())
+            .collect::<String>();
+        let text = name.strip_prefix(['C', 'c']).unwrap_or(&name);
+        let (carbons, text) =
+            number(text).with_context(|| format!("invalid fatty acid {name:?}"))?;
+        ensure!(carbons > 0, "invalid number of carbons in {name:?}");
+        let (double_bonds, text) = match text.strip_prefix(':') {
+            Some(text) => number(text).with_context(|| format!("invalid fatty acid {name:?}"))?,
+            None => (0, text),
+        };
+        let bonds = if text.is_empty() {
+            ensure!(
+                double_bonds == 0,
+                "positions of the double bonds are missing in {name:?}"
+            );
+            Vec::new()
+        } else if let Some(text) = ["n-", "n", "ω-", "ω", "w"]
+            .into_iter()
+            .find_map(|prefix| text.strip_prefix(prefix))
+        {
+            let (omega, text) =
+                number(text).with_context(|| format!("invalid fatty acid {name:?}"))?;
+            let isomerism =
+                isomerism(text).with_context(|| format!("invalid fatty acid {name:?}"))?;
+            ensure!(
+                double_bonds > 0,
+                "saturated fatty acid {name:?} has a position"
+            );
+            let last = carbons as i32 - omega as i32;
+            (0..double_bonds as i32)
+                .rev()
+                .map(|index| {
+                    let index = last - 3 * index;
+                    ensure!(
+                        0 < index,
+                        "invalid positions of the double bonds in {name:?}"
+                    );
+                    Ok(Bond {
+                        index: index as _,
+                        isomerism,
+                        unsaturation: 1,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?
+        } else {
+            let text = ["Δ", "D", "d"]
+                .into_iter()
+                .find_map(|prefix| text.strip_prefix(prefix))
+                .unwrap_or(text);
+            let text = text
+                .strip_prefix('(')
+                .and_then(|text| text.strip_suffix(')'))
+                .unwrap_or(text);
+            text.split(',')
+                .map(|bond| {
+                    let (index, text) = number(bond)?;
+                    Ok(Bond {
+                        index,
+                        isomerism: isomerism(text)?,
+                        unsaturation: 1,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()
+                .with_context(|| format!("invalid fatty acid {name:?}"))?
+        };
+        ensure!(
+            bonds.len() == double_bonds as usize,
+            "{} positions of the double bonds in {name:?}, expected {double_bonds}",
+            bonds.len(),
+        );
+        ensure!(
+            bonds
+                .iter()
+                .all(|bond| 0 < bond.index && bond.index < carbons)
+                && bonds
+                    .windows(2)
+                    .all(|bonds| bonds[0].index < bonds[1].index),
+            "invalid positions of the double bonds in {name:?}"
+        );
+        Ok(Self { carbons, bonds })
+    }
+
+    /// Names of the fatty acid series
+    pub fn from_series(series: &Series) -> PolarsResult<Vec<Option<Self>>> {
+        let fatty_acid = series.struct_()?;
+        let carbons = fatty_acid
+            .field_by_name("Carbons")?
+            .cast(&DataType::UInt8)?;
+        let unsaturated = fatty_acid.field_by_name("Unsaturated")?;
+        let mut names = Vec::with_capacity(series.len());
+        for (carbons, unsaturated) in carbons.u8()?.iter().zip(unsaturated.list()?.iter()) {
+            let (Some(carbons), Some(unsaturated)) = (carbons, unsaturated) else {
+                names.push(None);
+                continue;
+            };
+            let unsaturated = unsaturated.struct_()?;
+            let index = unsaturated.field_by_name("Index")?.cast(&DataType::UInt8)?;
+            let isomerism = unsaturated
+                .field_by_name("Isomerism")?
+                .cast(&DataType::Int8)?;
+            let unsaturation = unsaturated
+                .field_by_name("Unsaturation")?
+                .cast(&DataType::UInt8)?;
+            let bonds = index
+                .u8()?
+                .iter()
+                .zip(isomerism.i8()?.iter())
+                .zip(unsaturation.u8()?.iter())
+                .map(|((index, isomerism), unsaturation)| Bond {
+                    index: index.unwrap_or_default(),
+                    isomerism: isomerism.unwrap_or_default(),
+                    unsaturation: unsaturation.unwrap_or(1),
+                })
+                .collect();
+            names.push(Some(Self { carbons, bonds }));
+        }
+        Ok(names)
+    }
+
+    /// Fatty acid series of the names
+    pub fn to_series(name: PlSmallStr, names: &[Self]) -> PolarsResult<Series> {
+        let carbons =
+            Series::from_iter(names.iter().map(|name| name.carbons)).with_name("Carbons".into());
+        let unsaturated = names
+            .iter()
+            .map(|name| -> PolarsResult<Option<Series>> {
+                let index = Series::from_iter(name.bonds.iter().map(|bond| bond.index))
+                    .with_name("Index".into());
+                let isomerism = Series::from_iter(name.bonds.iter().map(|bond| bond.isomerism))
+                    .with_name("Isomerism".into());
+                let unsaturation =
+                    Series::from_iter(name.bonds.iter().map(|bond| bond.unsaturation))
+                        .with_name("Unsaturation".into());
+                Ok(Some(
+                    StructChunked::from_series(
+                        PlSmallStr::EMPTY,
+                        name.bonds.len(),
+                        [index, isomerism, unsaturation].iter(),
+                    )?
+                    .into_series(),
+                ))
+            })
+            .collect::<PolarsResult<ListChunked>>()?
+            .with_name("Unsaturated".into())
+            .into_series();
+        Ok(
+            StructChunked::from_series(name, names.len(), [carbons, unsaturated].iter())?
+                .into_series(),
+        )
+    }
+
+    /// Whether the name matches the other one, the unknown isomerism matches
+    /// any
+    pub fn matches(&self, other: &Self) -> bool {
+        self.carbons == other.carbons
+            && self.bonds.len() == other.bonds.len()
+            && self.bonds.iter().zip(&other.bonds).all(|(bond, other)| {
+                bond.index == other.index
+                    && (bond.isomerism == 0 || bond.isomerism.signum() == other.isomerism.signum())
+            })
+    }
+}
+
+impl Display for Name {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.carbons, self.bonds.len())?;
+        for (index, bond) in self.bonds.iter().enumerate() {
+            f.write_str(if index == 0 { "Δ" } else { "," })?;
+            write!(f, "{}", bond.index)?;
+            match bond.isomerism.signum() {
+                1 => f.write_str("c")?,
+                -1 => f.write_str("t")?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Unsaturated bond
+///
+/// Isomerism is positive for cis, negative for trans and zero if unknown.
+/// Unsaturation is one for the double bond and two for the triple one, the
+/// parsed bonds are double.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Bond {
+    pub index: u8,
+    pub isomerism: i8,
+    pub unsaturation: u8,
+}
+
+fn number(text: &str) -> Result<(u8, &str)> {
+    let end = text
+        .find(|char: char| !char.is_ascii_digit())
+        .unwrap_or(text.len());
+    let number = text[..end].parse()?;
+    Ok((number, &text[end..]))
+}
+
+fn isomerism(text: &str) -> Result<i8> {
+    Ok(match text {
+        "" => 0,
+        "c" | "Z" | "cis" => 1,
+        "t" | "E" | "trans" => -1,
+        _ => bail!("invalid isomerism {text:?}"),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn name(carbons: u8, bonds: &[(u8, i8)]) -> Name {
+        Name {
+            carbons,
+            bonds: bonds
+                .iter()
+                .map(|&(index, isomerism)| Bond {
+                    index,
+                    isomerism,
+                    unsaturation: 1,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn saturated() {
+        assert_eq!(Name::parse("16:0").unwrap(), name(16, &[]));
+        assert_eq!(Name::parse("C16:0").unwrap(), name(16, &[]));
+        assert_eq!(Name::parse("16").unwrap(), name(16, &[]));
+    }
+
+    #[test]
+    fn delta() {
+        let linoleic = name(18, &[(9, 1), (12, 1)]);
+        assert_eq!(Name::parse("18:2Δ9c,12c").unwrap(), linoleic);
+        assert_eq!(Name::parse("C18:2 Δ9c, 12c").unwrap(), linoleic);
+        assert_eq!(Name::parse("18:1Δ9t").unwrap(), name(18, &[(9, -1)]));
+        assert_eq!(Name::parse("18:1Δ9").unwrap(), name(18, &[(9, 0)]));
+    }
+
+    #[test]
+    fn d() {
+        assert_eq!(
+            Name::parse("18:2D9,12").unwrap(),
+            name(18, &[(9, 0), (12, 0)])
+        );
+        assert_eq!(
+            Name::parse("18:2d9c,12t").unwrap(),
+            name(18, &[(9, 1), (12, -1)])
+        );
+    }
+
+    #[test]
+    fn parentheses() {
+        assert_eq!(
+            Name::parse("18:2(9Z,12Z)").unwrap(),
+            name(18, &[(9, 1), (12, 1)])
+        );
+        assert_eq!(
+            Name::parse("18:2(9E,12Z)").unwrap(),
+            name(18, &[(9, -1), (12, 1)])
+        );
+    }
+
+    #[test]
+    fn omega() {
+        let linoleic = name(18, &[(9, 0), (12, 0)]);
+        assert_eq!(Name::parse("18:2n-6").unwrap(), linoleic);
+        assert_eq!(Name::parse("18:2n6").unwrap(), linoleic);
+        assert_eq!(Name::parse("18:2ω6").unwrap(), linoleic);
+        assert_eq!(Name::parse("18:2ω-6").unwrap(), linoleic);
+        assert_eq!(Name::parse("18:2w6").unwrap(), linoleic);
+        assert_eq!(
+            Name::parse("18:3n-3c").unwrap(),
+            name(18, &[(9, 1), (12, 1), (15, 1)])
+        );
+    }
+
+    #[test]
+    fn position_out_of_carbons() {
+        assert!(Name::parse("18:1Δ18").is_err());
+        assert!(Name::parse("18:1Δ0").is_err());
+        assert!(Name::parse("6:3n-3").is_err());
+    }
+
+    #[test]
+    fn wrong_bond_count() {
+        assert!(Name::parse("18:2Δ9").is_err());
+        assert!(Name::parse("18:1Δ9,12").is_err());
+        assert!(Name::parse("18:1").is_err());
+        assert!(Name::parse("18:0n-3").is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(Name::parse("").is_err());
+        assert!(Name::parse("0:0").is_err());
+        assert!(Name::parse("18:1Δ9x").is_err());
+        assert!(Name::parse("18:2Δ12,9").is_err());
+    }
+
+    #[test]
+    fn series() {
+        let mut names = vec![
+            Name::parse("18:2Δ9c,12c").unwrap(),
+            Name::parse("18:2Δ9c,15").unwrap(),
+        ];
+        names[1].bonds[1].unsaturation = 2;
+        let series = Name::to_series("FattyAcid".into(), &names).unwrap();
+        let names = names.into_iter().map(Some).collect::<Vec<_>>();
+        assert_eq!(Name::from_series(&series).unwrap(), names);
+    }
+
+    #[test]
+    fn display() {
+        for text in ["16:0", "18:1Δ9t", "18:2Δ9c,12c", "18:2Δ9,12"] {
+            let name = Name::parse(text).unwrap();
+            assert_eq!(name.to_string(), text);
+            assert_eq!(Name::parse(&name.to_string()).unwrap(), name);
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use crate::{
+    dead_time::{Conditions, Operation},
+    special::{column::gc_column, data_frame::DataFrameExt as _},
+};
+use egui::emath::Float as _;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::hash::{Hash, Hasher};
+
+/// Relative tolerance of the phase ratios
+const PHASE_RATIO_TOLERANCE: f64 = 0.01;
+
+/// Method translation
+///
+/// All times of the translated method are divided and all heating rates are
+/// multiplied by the translation factor `G = tM / t'M`, so the elution order
+/// and the equivalent chain lengths are preserved for columns with the same
+/// phase ratio (Blumberg, Klee, 1998).
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub(crate) struct Translation {
+    pub(crate) target: Conditions,
+    /// Speed-up factor, the target inlet pressure is calculated to reach it
+    pub(crate) speed_up: Option<f64>,
+}
+
+impl Translation {
+    pub(crate) fn new() -> Self {
+        Self {
+            target: Conditions::new(),
+            speed_up: None,
+        }
+    }
+
+    /// Translation factor at the temperature (°C)
+    pub(crate) fn factor(&self, source: &Conditions, temperature: f64) -> f64 {
+        match self.speed_up {
+            Some(speed_up) => speed_up,
+            None => source.dead_time(temperature) / self.target.dead_time(temperature),
+        }
+    }
+
+    /// Target conditions at the temperature (°C)
+    ///
+    /// With the speed-up the target is operated at the constant inlet pressure
+    /// giving the sped up dead time, `None` if the pressure is unreachable.
+    pub(crate) fn target(&self, source: &Conditions, temperature: f64) -> Option<Conditions> {
+        match self.speed_up {
+            Some(speed_up) => {
+                let dead_time = source.dead_time(temperature) / speed_up;
+                let mut target = self.target;
+                target.pressure = target.pressure_for(dead_time, temperature)?;
+                target.operation = Operation::ConstantPressure;
+                Some(target)
+            }
+            None => Some(self.target),
+        }
+    }
+
+    /// Whether the phase ratios of the source and the target columns are equal
+    pub(crate) fn is_exact(&self, source: &Conditions) -> bool {
+        let source = source.geometry.phase_ratio();
+        let target = self.target.geometry.phase_ratio();
+        (source - target).abs() <= source * PHASE_RATIO_TOLERANCE
+    }
+
+    /// Translate the modes, retention times and dead times of the data frame
+    ///
+    /// The flow and the pressure of the temperature programs are dropped, they
+    /// are set by the target conditions. The geometry of the column is set to
+    /// the target one, the name, the stationary phase and the manufacturer are
+    /// kept. The error is returned if the target
+    /// is unreachable at any onset temperature.
+    pub(crate) fn translate(
+        &self,
+        data_frame: &DataFrame,
+        source: &Conditions,
+    ) -> PolarsResult<DataFrame> {
+        let mode = data_frame.mode();
+        let onset_temperature = mode.onset_temperature.f64()?;
+        if let Some(temperature) = onset_temperature
+            .iter()
+            .flatten()
+            .find(|&temperature| self.target(source, temperature).is_none())
+        {
+            polars_bail!(ComputeError: "unreachable speed-up at the onset temperature {temperature} °C");
+        }
+        let factors =
+            onset_temperature.apply_values(|temperature| self.factor(source, temperature));
+        // Mode
+        let mode = mode.translate(&factors)?;
+        // Retention time
+        let mut retention_times = Vec::with_capacity(data_frame.height());
+        for (values, factor) in data_frame["RetentionTime"]
+            .list()?
+            .into_iter()
+            .zip(factors.iter())
+        {
+            retention_times.push(match values.zip(factor) {
+                Some((values, factor)) => Some((values.f64()? / factor).into_series()),
+                None => None,
+            });
+        }
+        let retention_time = retention_times
+            .into_iter()
+            .collect::<ListChunked>()
+            .with_name("RetentionTime".into());
+        // Dead time
+        let dead_time = (data_frame["DeadTime"].f64()? / &factors).with_name("DeadTime".into());
+        // Column
+        let column = match data_frame.column("Column") {
+            Ok(_) => col("Column"),
+            Err(_) => gc_column::unnamed(),
+        };
+        let geometry = self.target.geometry;
+        let column = as_struct(vec![
+            column.clone().struct_().field_by_name("Name"),
+            column.clone().struct_().field_by_name("StationaryPhase"),
+            column.struct_().field_by_name("Manufacturer"),
+            lit(geometry.length).alias("Length"),
+            lit(geometry.diameter).alias("InnerDiameter"),
+            lit(geometry.film_thickness).alias("FilmThickness"),
+        ])
+        .alias("Column");
+        let mut data_frame = data_frame.clone();
+        data_frame
+            .with_column(mode)?
+            .with_column(retention_time.into_series())?
+            .with_column(dead_time.into_series())?;
+        data_frame.lazy().with_column(column).collect()
+    }
+}
+
+impl Default for Translation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hash for Translation {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.target.hash(state);
+        self.speed_up.map(|speed_up| speed_up.ord()).hash(state);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn factor() {
+        let source = Conditions::new();
+        let mut translation = Translation::new();
+        translation.target.geometry.length = source.geometry.length;
+        assert!((translation.factor(&source, 100.0) - 1.0).abs() < 1e-9);
+        translation.speed_up = Some(3.0);
+        let target = translation.target(&source, 100.0).unwrap();
+        let factor = source.dead_time(100.0) / target.dead_time(100.0);
+        assert!((factor - 3.0).abs() < 1e-6, "{factor}");
+    }
+
+    #[test]
+    fn constant_flow_target() {
+        let source = Conditions::new();
+        let mut translation = Translation::new();
+        translation.target.operation = Operation::ConstantFlow;
+        translation.speed_up = Some(2.0);
+        let target = translation.target(&source, 100.0).unwrap();
+        assert_eq!(target.operation, Operation::ConstantPressure);
+        let factor = source.dead_time(100.0) / target.dead_time(100.0);
+        assert!((factor - 2.0).abs() < 1e-6, "{factor}");
+    }
+
+    #[test]
+    fn unreachable() {
+        let source = Conditions::new();
+        let mut translation = Translation::new();
+        translation.speed_up = Some(1e6);
+        assert!(translation.target(&source, 100.0).is_none());
+    }
+}
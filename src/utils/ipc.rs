@@ -0,0 +1,44 @@
+use crate::app::panes::properties::{METADATA_KEYS, Properties};
+use anyhow::Result;
+use metadata::{MetaDataFrame, Metadata};
+use polars::prelude::*;
+use std::{
+    io::{Cursor, Write},
+    sync::Arc,
+};
+
+/// Reads the frame, the properties of the schema metadata are kept in the
+/// description
+pub fn read(bytes: &[u8]) -> Result<MetaDataFrame> {
+    let mut frame = MetaDataFrame::read(Cursor::new(bytes))?;
+    if let Some(custom) = IpcReader::new(Cursor::new(bytes)).custom_metadata()? {
+        let entries = custom
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .filter(|(key, _)| !METADATA_KEYS.contains(key));
+        frame.meta.description = Properties::new(&frame.meta.description, entries).to_string();
+    }
+    Ok(frame)
+}
+
+/// Writes the frame, the properties of the description are written to the
+/// schema metadata next to the name, the version and the date
+pub fn write(writer: impl Write, meta: &Metadata, data: &mut DataFrame) -> Result<()> {
+    let properties = Properties::parse(&meta.description);
+    let mut meta = meta.clone();
+    meta.description = properties.description.clone();
+    // Schema metadata of the metadata
+    let mut bytes = Vec::new();
+    MetaDataFrame::new(meta, &mut *data).write(&mut bytes)?;
+    let mut custom = IpcReader::new(Cursor::new(bytes))
+        .custom_metadata()?
+        .map(Arc::unwrap_or_clone)
+        .unwrap_or_default();
+    for (key, value) in properties.entries() {
+        custom.insert(key.into(), value.into());
+    }
+    let mut writer = IpcWriter::new(writer);
+    writer.set_custom_schema_metadata(Arc::new(custom));
+    writer.finish(data)?;
+    Ok(())
+}
@@ -1,6 +1,12 @@
-pub use self::{cross::cross, save::save, try_f::unwrap_f, vec::VecExt};
+pub use self::{
+    cross::cross,
+    save::{save, save_csv, save_ron},
+    try_f::unwrap_f,
+    vec::VecExt,
+};
 
 mod cross;
+pub mod ipc;
 mod save;
 mod try_f;
 mod vec;
@@ -1,13 +1,15 @@
+use super::ipc;
 use anyhow::Result;
 use metadata::{MetaDataFrame, Metadata};
 use polars::prelude::DataFrame;
-use std::fs::File;
+use ron::ser::{PrettyConfig, to_string_pretty};
+use serde::Serialize;
+use std::fs::{File, write};
 
 #[cfg(not(target_arch = "wasm32"))]
 pub fn save(name: &str, frame: MetaDataFrame<&Metadata, &mut DataFrame>) -> Result<()> {
     let file = File::create(name)?;
-    MetaDataFrame::new(frame.meta.clone(), frame.data).write(file)?;
-    Ok(())
+    ipc::write(file, frame.meta, frame.data)
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -16,6 +18,35 @@ pub fn save(name: &str, frame: MetaDataFrame<&Metadata, &mut DataFrame>) -> Resu
     use egui_ext::download;
 
     let mut bytes = Vec::new();
-    MetaDataFrame::new(frame.meta.clone(), frame.data).write(&mut bytes)?;
+    ipc::write(&mut bytes, frame.meta, frame.data)?;
     download(name, &bytes).map_err(|error| anyhow!(error))
 }
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_ron<T: Serialize>(name: &str, value: &T) -> Result<()> {
+    write(name, to_string_pretty(value, PrettyConfig::default())?)?;
+    Ok(())
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn save_ron<T: Serialize>(name: &str, value: &T) -> Result<()> {
+    use anyhow::anyhow;
+    use egui_ext::download;
+
+    let text = to_string_pretty(value, PrettyConfig::default())?;
+    download(name, text.as_bytes()).map_err(|error| anyhow!(error))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_csv(name: &str, text: &str) -> Result<()> {
+    write(name, text)?;
+    Ok(())
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn save_csv(name: &str, text: &str) -> Result<()> {
+    use anyhow::anyhow;
+    use egui_ext::download;
+
+    download(name, text.as_bytes()).map_err(|error| anyhow!(error))
+}